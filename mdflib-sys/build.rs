@@ -333,6 +333,11 @@ fn build_bundled(out_dir: &Path, manifest_dir: &Path) {
         .include(install_dir.join("include"))
         .include(bundled_dir.join("include"));
 
+    let version = bundled_mdflib_version(&bundled_dir).unwrap_or_else(|| "unknown".to_string());
+    define_mdflib_version(&mut cc_build, &version);
+    // mdflib's CMake requires zlib so the bundled build always has compression
+    define_zlib_available(&mut cc_build);
+
     // On Windows, mdflib's CMake installs headers to <prefix>/mdf/include/
     let mdf_include = install_dir.join("mdf").join("include");
     if mdf_include.exists() {
@@ -357,6 +362,30 @@ fn build_bundled(out_dir: &Path, manifest_dir: &Path) {
     setup_bundled_linking(&install_dir);
 }
 
+/// Reads the mdflib version from the `project()` command of the bundled CMakeLists.txt
+fn bundled_mdflib_version(bundled_dir: &Path) -> Option<String> {
+    let cmake_lists = std::fs::read_to_string(bundled_dir.join("CMakeLists.txt")).ok()?;
+    let project = cmake_lists
+        .split("project(")
+        .nth(1)?
+        .split(')')
+        .next()?
+        .to_string();
+    let mut tokens = project.split_whitespace();
+    tokens.find(|token| *token == "VERSION")?;
+    tokens.next().map(|version| version.to_string())
+}
+
+/// Passes the linked mdflib version to the C wrapper, see `MdfGetLibraryVersion()`
+fn define_mdflib_version(cc_build: &mut cc::Build, version: &str) {
+    cc_build.define("MDFLIB_VERSION", Some(format!("\"{version}\"").as_str()));
+}
+
+/// Tells the C wrapper that zlib is linked, see `MdfWriterCompressionAvailable()`
+fn define_zlib_available(cc_build: &mut cc::Build) {
+    cc_build.define("MDFLIB_HAS_ZLIB", None);
+}
+
 fn setup_dependencies() {
     println!("cargo:rerun-if-env-changed=PKG_CONFIG_PATH");
     if is_msvc() {
@@ -661,6 +690,15 @@ fn setup_system_linking(_manifest_dir: &Path) {
         .atleast_version("2.3")
         .probe("mdflib")
     {
+        define_mdflib_version(&mut cc_build, &library.version);
+        // Only probe zlib, linking comes from the mdflib pkg-config file
+        if pkg_config::Config::new()
+            .cargo_metadata(false)
+            .probe("zlib")
+            .is_ok()
+        {
+            define_zlib_available(&mut cc_build);
+        }
         for path in library.include_paths {
             cc_build.include(path);
         }
@@ -668,8 +706,14 @@ fn setup_system_linking(_manifest_dir: &Path) {
         println!("cargo:warning=pkg-config failed for mdflib, trying manual discovery");
         println!("cargo:rustc-link-lib=mdf");
 
+        // Without pkg-config there is no version metadata, allow it to be supplied
+        println!("cargo:rerun-if-env-changed=MDFLIB_VERSION");
+        let version = env::var("MDFLIB_VERSION").unwrap_or_else(|_| "unknown".to_string());
+        define_mdflib_version(&mut cc_build, &version);
+
         // Link dependencies after the main library
         setup_dependencies();
+        define_zlib_available(&mut cc_build);
 
         if cfg!(target_os = "linux") {
             if is_musl() {
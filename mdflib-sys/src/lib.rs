@@ -5,6 +5,136 @@
 // Include the generated bindings from bindgen
 include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
 
+/// Implements `TryFrom<u8>` for the `uint8_t` enums that mdflib passes as raw
+/// values. The error is the unknown value.
+macro_rules! impl_try_from_u8 {
+    ($name:ident { $($variant:ident),+ $(,)? }) => {
+        impl TryFrom<u8> for $name {
+            type Error = u8;
+
+            fn try_from(value: u8) -> Result<Self, Self::Error> {
+                match value {
+                    $(v if v == $name::$variant as u8 => Ok($name::$variant),)+
+                    _ => Err(value),
+                }
+            }
+        }
+    };
+}
+
+impl_try_from_u8!(EventType {
+    RecordingPeriod,
+    RecordingInterrupt,
+    AcquisitionInterrupt,
+    StartRecording,
+    StopRecording,
+    Trigger,
+    Marker,
+});
+impl_try_from_u8!(SyncType {
+    SyncTime,
+    SyncAngle,
+    SyncDistance,
+    SyncIndex,
+});
+impl_try_from_u8!(RangeType {
+    RangePoint,
+    RangeStart,
+    RangeEnd,
+});
+impl_try_from_u8!(EventCause {
+    CauseOther,
+    CauseError,
+    CauseTool,
+    CauseScript,
+    CauseUser,
+});
+impl_try_from_u8!(ChType {
+    Group,
+    Function,
+    Structure,
+    MapList,
+    InputVariable,
+    OutputVariable,
+    LocalVariable,
+    CalibrationDefinition,
+    CalibrationObject,
+});
+impl_try_from_u8!(ChannelDataType {
+    UnsignedIntegerLe,
+    UnsignedIntegerBe,
+    SignedIntegerLe,
+    SignedIntegerBe,
+    FloatLe,
+    FloatBe,
+    StringAscii,
+    StringUTF8,
+    StringUTF16Le,
+    StringUTF16Be,
+    ByteArray,
+    MimeSample,
+    MimeStream,
+    CanOpenDate,
+    CanOpenTime,
+    ComplexLe,
+    ComplexBe,
+});
+impl_try_from_u8!(SourceType {
+    Other,
+    Ecu,
+    Bus,
+    IoDevice,
+    Tool,
+    User,
+});
+impl_try_from_u8!(BusType {
+    None,
+    Other,
+    Can,
+    Lin,
+    Most,
+    FlexRay,
+    Kline,
+    Ethernet,
+    Usb,
+});
+
+/// Maps the writer bus type to the bus type stored in the source information
+/// of a channel group.
+///
+/// The writer uses [`MdfBusType`] bit flags when creating the bus logging
+/// channel groups, which are then recorded as the matching [`BusType`] in their
+/// source information. [`MdfBusType::UNKNOWN`] maps to [`BusType::None`].
+impl From<MdfBusType> for BusType {
+    fn from(bus_type: MdfBusType) -> Self {
+        match bus_type {
+            MdfBusType::CAN => BusType::Can,
+            MdfBusType::LIN => BusType::Lin,
+            MdfBusType::FlexRay => BusType::FlexRay,
+            MdfBusType::MOST => BusType::Most,
+            MdfBusType::Ethernet => BusType::Ethernet,
+            MdfBusType::UNKNOWN => BusType::None,
+        }
+    }
+}
+
+/// Maps a source information bus type back to the writer bus type.
+///
+/// Bus types the writer cannot log ([`BusType::Other`], [`BusType::Kline`] and
+/// [`BusType::Usb`]) and [`BusType::None`] map to [`MdfBusType::UNKNOWN`].
+impl From<BusType> for MdfBusType {
+    fn from(bus_type: BusType) -> Self {
+        match bus_type {
+            BusType::Can => MdfBusType::CAN,
+            BusType::Lin => MdfBusType::LIN,
+            BusType::FlexRay => MdfBusType::FlexRay,
+            BusType::Most => MdfBusType::MOST,
+            BusType::Ethernet => MdfBusType::Ethernet,
+            BusType::None | BusType::Other | BusType::Kline | BusType::Usb => MdfBusType::UNKNOWN,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -13,6 +143,79 @@ mod tests {
     use std::os::raw::c_char;
     use std::path::Path;
 
+    #[test]
+    fn test_event_enum_round_trip() {
+        for value in 0..=u8::MAX {
+            if let Ok(event_type) = EventType::try_from(value) {
+                assert_eq!(event_type as u8, value);
+            }
+            if let Ok(sync_type) = SyncType::try_from(value) {
+                assert_eq!(sync_type as u8, value);
+            }
+            if let Ok(range_type) = RangeType::try_from(value) {
+                assert_eq!(range_type as u8, value);
+            }
+            if let Ok(cause) = EventCause::try_from(value) {
+                assert_eq!(cause as u8, value);
+            }
+        }
+
+        assert_eq!(EventType::try_from(6), Ok(EventType::Marker));
+        assert_eq!(EventType::try_from(7), Err(7));
+        assert_eq!(SyncType::try_from(0), Err(0));
+        assert_eq!(SyncType::try_from(4), Ok(SyncType::SyncIndex));
+        assert_eq!(RangeType::try_from(2), Ok(RangeType::RangeEnd));
+        assert_eq!(RangeType::try_from(3), Err(3));
+        assert_eq!(EventCause::try_from(4), Ok(EventCause::CauseUser));
+        assert_eq!(EventCause::try_from(5), Err(5));
+    }
+
+    #[test]
+    fn test_bus_type_round_trip() {
+        for value in 0..=8 {
+            assert_eq!(BusType::try_from(value).map(|bus| bus as u8), Ok(value));
+        }
+        assert_eq!(BusType::try_from(2), Ok(BusType::Can));
+        assert_eq!(BusType::try_from(9), Err(9));
+        for value in 0..=5 {
+            assert_eq!(SourceType::try_from(value).map(|ty| ty as u8), Ok(value));
+        }
+        assert_eq!(SourceType::try_from(2), Ok(SourceType::Bus));
+        assert_eq!(SourceType::try_from(6), Err(6));
+    }
+
+    #[test]
+    fn test_mdf_bus_type_mapping() {
+        assert_eq!(BusType::from(MdfBusType::CAN), BusType::Can);
+        assert_eq!(BusType::from(MdfBusType::UNKNOWN), BusType::None);
+        for bus_type in [
+            MdfBusType::CAN,
+            MdfBusType::LIN,
+            MdfBusType::FlexRay,
+            MdfBusType::MOST,
+            MdfBusType::Ethernet,
+            MdfBusType::UNKNOWN,
+        ] {
+            assert_eq!(MdfBusType::from(BusType::from(bus_type)), bus_type);
+        }
+        assert_eq!(MdfBusType::from(BusType::Usb), MdfBusType::UNKNOWN);
+    }
+
+    #[test]
+    fn test_channel_data_type_round_trip() {
+        for value in 0..=16 {
+            assert_eq!(
+                ChannelDataType::try_from(value).map(|ty| ty as u8),
+                Ok(value)
+            );
+        }
+        assert_eq!(
+            ChannelDataType::try_from(10),
+            Ok(ChannelDataType::ByteArray)
+        );
+        assert_eq!(ChannelDataType::try_from(17), Err(17));
+    }
+
     #[test]
     fn test_writer_and_reader() {
         let filename_str = "test_rust.mdf";
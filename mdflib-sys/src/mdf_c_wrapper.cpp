@@ -11,23 +11,33 @@
 #include <mdf/ichannel.h>
 #include <mdf/ichannelarray.h>
 #include <mdf/ichannelconversion.h>
+#include <mdf/ichannelhierarchy.h>
 #include <mdf/ichannelgroup.h>
 #include <mdf/ichannelobserver.h>
 #include <mdf/canbusobserver.h>
 #include <mdf/idatagroup.h>
+#include <mdf/isampleobserver.h>
 #include <mdf/ievent.h>
 #include <mdf/ifilehistory.h>
 #include <mdf/imetadata.h>
 #include <mdf/isourceinformation.h>
+#include <mdf/itimestamp.h>
 #include <mdf/mdffactory.h>
 #include <mdf/mdffile.h>
 #include <mdf/mdfreader.h>
 #include <mdf/mdfwriter.h>
 #include <mdf/mdflogstream.h>
 
+#include <new>
+
 using namespace mdf;
 
 
+// Set by build.rs from the bundled CMakeLists.txt or pkg-config
+#ifndef MDFLIB_VERSION
+#define MDFLIB_VERSION "unknown"
+#endif
+
 // Export macros for different platforms
 #if defined(_WIN32)
 #define EXPORT __declspec(dllexport)
@@ -37,6 +47,61 @@ using namespace mdf;
 #define EXPORT
 #endif
 
+// Counts the records of one channel group as they are read from file
+class SampleCounter : public ISampleObserver {
+ public:
+  SampleCounter(const IDataGroup& data_group, const IChannelGroup& channel_group)
+      : ISampleObserver(data_group),
+        record_id_(channel_group.RecordId()) {}
+
+  bool OnSample(uint64_t, uint64_t record_id,
+                const std::vector<uint8_t>&) override {
+    if (record_id == record_id_) {
+      ++count_;
+    }
+    return true;
+  }
+
+  uint64_t Count() const { return count_; }
+
+ private:
+  uint64_t record_id_;
+  uint64_t count_ = 0;
+};
+
+// Keeps the bytes of one channel from each record of its group as read from file
+class RecordObserver : public ISampleObserver {
+ public:
+  RecordObserver(const IDataGroup& data_group, const IChannelGroup& channel_group,
+                 const IChannel& channel)
+      : ISampleObserver(data_group),
+        record_id_(channel_group.RecordId()),
+        offset_(channel.ByteOffset()),
+        size_((channel.BitOffset() + channel.BitCount() + 7) / 8) {}
+
+  bool OnSample(uint64_t, uint64_t record_id,
+                const std::vector<uint8_t>& record) override {
+    if (record_id == record_id_) {
+      const size_t begin = std::min(record.size(), offset_);
+      const size_t end = std::min(record.size(), offset_ + size_);
+      bytes_.emplace_back(record.begin() + begin, record.begin() + end);
+    }
+    return true;
+  }
+
+  size_t NofRecords() const { return bytes_.size(); }
+
+  const std::vector<uint8_t>* Bytes(size_t index) const {
+    return index < bytes_.size() ? &bytes_[index] : nullptr;
+  }
+
+ private:
+  uint64_t record_id_;
+  size_t offset_;
+  size_t size_;
+  std::vector<std::vector<uint8_t>> bytes_;
+};
+
 extern "C" {
 
 // Global function pointers for C-style callbacks
@@ -80,6 +145,16 @@ EXPORT void MdfSetLogFunction2(MdfCLogFunction2 func) {
     }
 }
 
+EXPORT size_t MdfGetLibraryVersion(char *version, size_t max_length) {
+  const std::string library_version = MDFLIB_VERSION;
+  size_t copy_length = std::min(library_version.length(), max_length - 1);
+  if (version && max_length > 0) {
+    std::memcpy(version, library_version.c_str(), copy_length);
+    version[copy_length] = '\0';
+  }
+  return library_version.length();
+}
+
 // MdfReader functions
 EXPORT MdfReader *MdfReaderInit(const char *filename) {
   return new MdfReader(filename);
@@ -121,6 +196,10 @@ EXPORT bool MdfReaderOpen(MdfReader *reader) { return reader->Open(); }
 
 EXPORT void MdfReaderClose(MdfReader *reader) { reader->Close(); }
 
+EXPORT bool MdfReaderIsOpen(MdfReader *reader) {
+  return reader ? reader->IsOpen() : false;
+}
+
 EXPORT bool MdfReaderReadHeader(MdfReader *reader) {
   return reader->ReadHeader();
 }
@@ -137,6 +216,19 @@ EXPORT bool MdfReaderReadData(MdfReader *reader, IDataGroup *group) {
   return reader->ReadData(*group);
 }
 
+EXPORT bool MdfReaderReadPartialData(MdfReader *reader, IDataGroup *group,
+                                     size_t min_sample, size_t max_sample) {
+  return reader->ReadPartialData(*group, min_sample, max_sample);
+}
+
+EXPORT bool MdfReaderExportAttachmentData(MdfReader *reader,
+                                          const IAttachment *attachment,
+                                          const char *dest_file) {
+  if (!attachment || !dest_file)
+    return false;
+  return reader->ExportAttachmentData(*attachment, dest_file);
+}
+
 // MdfWriter functions
 EXPORT MdfWriter *MdfWriterInit(MdfWriterType type, const char *filename) {
   auto *writer = MdfFactory::CreateMdfWriterEx(type);
@@ -168,6 +260,14 @@ EXPORT void MdfWriterSetCompressData(MdfWriter *writer, bool compress) {
   writer->CompressData(compress);
 }
 
+EXPORT bool MdfWriterCompressionAvailable() {
+#ifdef MDFLIB_HAS_ZLIB
+  return true;
+#else
+  return false;
+#endif
+}
+
 EXPORT double MdfWriterGetPreTrigTime(MdfWriter *writer) {
   return writer->PreTrigTime();
 }
@@ -192,6 +292,14 @@ EXPORT void MdfWriterSetBusType(MdfWriter *writer, uint16_t type) {
   writer->BusType(type);
 }
 
+EXPORT MdfStorageType MdfWriterGetStorageType(MdfWriter *writer) {
+  return writer->StorageType();
+}
+
+EXPORT void MdfWriterSetStorageType(MdfWriter *writer, MdfStorageType type) {
+  writer->StorageType(type);
+}
+
 EXPORT bool MdfWriterCreateBusLogConfiguration(MdfWriter *writer) {
   return writer->CreateBusLogConfiguration();
 }
@@ -295,6 +403,27 @@ EXPORT size_t MdfFileGetProgramId(const MdfFile *file, char *buffer,
   return program_id.length();
 }
 
+// Builds the ID block text from the file identifier, format version and
+// program identifier, each padded with spaces to 8 characters
+EXPORT size_t MdfFileGetIdText(const MdfFile *file, char *buffer,
+                               size_t max_length) {
+  if (!file) {
+    return 0;
+  }
+  auto field = [](std::string text) {
+    text.resize(8, ' ');
+    return text;
+  };
+  const std::string id_text = field(file->IsFinalizedDone() ? "MDF" : "UnFinMF") +
+                              field(file->Version()) + field(file->ProgramId());
+  size_t copy_length = std::min(id_text.length(), max_length - 1);
+  if (buffer && max_length > 0) {
+    std::memcpy(buffer, id_text.c_str(), copy_length);
+    buffer[copy_length] = '\0';
+  }
+  return id_text.length();
+}
+
 // TODO these need file stream
 // EXPORT void MdfFileReadHeader(MdfFile *file) {
 //   file->ReadHeader();
@@ -426,6 +555,19 @@ EXPORT IChannelGroup *DataGroupCreateChannelGroup(IDataGroup *group) {
 
 EXPORT void DataGroupClearData(IDataGroup *group) {
   group->ClearData();
+  group->SetAsRead(false);
+}
+
+EXPORT bool DataGroupIsRead(const IDataGroup *group) {
+  return group ? group->IsRead() : false;
+}
+
+EXPORT const IMetaData *DataGroupGetMetaData(const IDataGroup *group) {
+  return group ? group->MetaData() : nullptr;
+}
+
+EXPORT IMetaData *DataGroupCreateMetaData(IDataGroup *group) {
+  return group ? group->CreateMetaData() : nullptr;
 }
 
 // IChannelGroup functions
@@ -472,6 +614,20 @@ EXPORT void ChannelGroupSetNofSamples(IChannelGroup *group, uint64_t samples) {
   group->NofSamples(samples);
 }
 
+EXPORT uint64_t ChannelGroupGetRecordId(const IChannelGroup *group) {
+  return group ? group->RecordId() : 0;
+}
+
+EXPORT void ChannelGroupSetRecordId(IChannelGroup *group, uint64_t record_id) {
+  if (group) {
+    group->RecordId(record_id);
+  }
+}
+
+EXPORT uint16_t ChannelGroupGetFlags(const IChannelGroup *group) {
+  return group ? group->Flags() : 0;
+}
+
 EXPORT size_t ChannelGroupGetChannelCount(const IChannelGroup *group) {
   const auto &channels = group->Channels();
   return channels.size();
@@ -485,6 +641,14 @@ EXPORT const IChannel *ChannelGroupGetChannelByIndex(const IChannelGroup *group,
   return channels[index];
 }
 
+EXPORT IChannel *ChannelGroupGetMutableChannel(IChannelGroup *group,
+                                               size_t index) {
+  const auto channels = group->Channels();
+  if (index >= channels.size())
+    return nullptr;
+  return channels[index];
+}
+
 EXPORT const IChannel *ChannelGroupGetChannelByName(
     const IChannelGroup *group, const char *name) {
   return group->GetChannel(name);
@@ -516,6 +680,23 @@ EXPORT uint8_t ChannelGroupGetBusType(const IChannelGroup *group) {
   return (uint8_t) group->GetBusType();
 }
 
+EXPORT size_t ChannelGroupGetSampleBufferSize(const IChannelGroup *group) {
+  return group ? group->SampleBuffer().size() : 0;
+}
+
+EXPORT bool ChannelGroupSetSampleBuffer(IChannelGroup *group,
+                                        const uint8_t *data, size_t size) {
+  if (!group || !data) {
+    return false;
+  }
+  auto &buffer = group->SampleBuffer();
+  if (size != buffer.size()) {
+    return false;
+  }
+  std::memcpy(buffer.data(), data, size);
+  return true;
+}
+
 // IHeader functions
 EXPORT size_t IHeaderGetMeasurementId(const IHeader *header, char *id,
                                       size_t max_length) {
@@ -722,10 +903,42 @@ EXPORT size_t IHeaderGetEvents(const IHeader *header, const IEvent *events[],
   return temp_list.size();
 }
 
+EXPORT size_t IHeaderGetEventCount(const IHeader *header) {
+  return header ? header->Events().size() : 0;
+}
+
+EXPORT const IEvent *IHeaderGetEventByIndex(const IHeader *header, size_t index) {
+  if (!header)
+    return nullptr;
+
+  auto temp_list = header->Events();
+  return index < temp_list.size() ? temp_list[index] : nullptr;
+}
+
 EXPORT IEvent *IHeaderCreateEvent(IHeader *header) {
   return header ? header->CreateEvent() : nullptr;
 }
 
+EXPORT size_t IHeaderGetChannelHierarchies(const IHeader *header,
+                                           const IChannelHierarchy *hierarchies[],
+                                           size_t max_count) {
+  if (!header || !hierarchies)
+    return 0;
+
+  std::vector<IChannelHierarchy *> temp_list;
+  header->ChannelHierarchies(temp_list);
+
+  size_t copy_count = std::min(temp_list.size(), max_count);
+  for (size_t i = 0; i < copy_count; ++i) {
+    hierarchies[i] = temp_list[i];
+  }
+  return temp_list.size();
+}
+
+EXPORT IChannelHierarchy *IHeaderCreateChannelHierarchy(IHeader *header) {
+  return header ? header->CreateChannelHierarchy() : nullptr;
+}
+
 EXPORT IDataGroup *IHeaderCreateDataGroup(IHeader *header) {
   return header->CreateDataGroup();
 }
@@ -843,10 +1056,58 @@ EXPORT uint64_t ChannelGetDataBytes(const IChannel *channel) {
   return channel->DataBytes();
 }
 
+EXPORT uint32_t ChannelGetByteOffset(const IChannel *channel) {
+  return channel ? channel->ByteOffset() : 0;
+}
+
 EXPORT void ChannelSetDataBytes(IChannel *channel, uint64_t bytes) {
   channel->DataBytes(bytes);
 }
 
+EXPORT void ChannelSetByteOffset(IChannel *channel, uint32_t offset) {
+  if (channel) channel->ByteOffset(offset);
+}
+
+EXPORT uint32_t ChannelGetBitCount(const IChannel *channel) {
+  return channel ? channel->BitCount() : 0;
+}
+
+EXPORT void ChannelSetBitCount(IChannel *channel, uint32_t bits) {
+  if (channel) channel->BitCount(bits);
+}
+
+EXPORT uint16_t ChannelGetBitOffset(const IChannel *channel) {
+  return channel ? channel->BitOffset() : 0;
+}
+
+EXPORT void ChannelSetBitOffset(IChannel *channel, uint16_t offset) {
+  if (channel) channel->BitOffset(offset);
+}
+
+EXPORT bool ChannelIsPrecisionUsed(const IChannel *channel) {
+  return channel ? channel->IsDecimalUsed() : false;
+}
+
+EXPORT uint8_t ChannelGetPrecision(const IChannel *channel) {
+  return channel ? channel->Decimals() : 0;
+}
+
+EXPORT void ChannelSetPrecision(IChannel *channel, uint8_t precision) {
+  if (channel) {
+    channel->Decimals(precision);
+  }
+}
+
+EXPORT uint8_t ChannelGetSync(const IChannel *channel) {
+  return channel ? static_cast<uint8_t>(channel->Sync()) : 0;
+}
+
+EXPORT void ChannelSetSync(IChannel *channel, uint8_t sync) {
+  if (channel) {
+    channel->Sync(static_cast<ChannelSyncType>(sync));
+  }
+}
+
 EXPORT void ChannelSetChannelValue(IChannel *channel, uint32_t value,
                                    bool valid) {
   if (channel) {
@@ -854,6 +1115,42 @@ EXPORT void ChannelSetChannelValue(IChannel *channel, uint32_t value,
   }
 }
 
+EXPORT void ChannelSetChannelValueUnsigned(IChannel *channel, uint64_t value,
+                                           bool valid) {
+  if (channel) {
+    channel->SetChannelValue(value, valid);
+  }
+}
+
+EXPORT void ChannelSetChannelValueSigned(IChannel *channel, int64_t value,
+                                         bool valid) {
+  if (channel) {
+    channel->SetChannelValue(value, valid);
+  }
+}
+
+EXPORT void ChannelSetChannelValueFloat(IChannel *channel, double value,
+                                        bool valid) {
+  if (channel) {
+    channel->SetChannelValue(value, valid);
+  }
+}
+
+EXPORT void ChannelSetChannelValueBytes(IChannel *channel, const uint8_t *value,
+                                        size_t length, bool valid) {
+  if (channel && (value || length == 0)) {
+    const std::vector<uint8_t> bytes(value, value + length);
+    channel->SetChannelValue(bytes, valid);
+  }
+}
+
+EXPORT void ChannelSetChannelValueString(IChannel *channel, const char *value,
+                                         bool valid) {
+  if (channel && value) {
+    channel->SetChannelValue(std::string(value), valid);
+  }
+}
+
 EXPORT const IMetaData *ChannelGetMetaData(const IChannel *channel) {
   return channel ? channel->MetaData() : nullptr;
 }
@@ -889,7 +1186,7 @@ EXPORT IChannelArray *ChannelCreateChannelArray(IChannel *channel) {
 }
 
 // CanMessage functions
-EXPORT CanMessage *CanMessageInit() { return new CanMessage; }
+EXPORT CanMessage *CanMessageInit() { return new (std::nothrow) CanMessage; }
 
 EXPORT void CanMessageUnInit(CanMessage *can) { delete can; }
 
@@ -915,6 +1212,16 @@ EXPORT uint8_t CanMessageGetDlc(const CanMessage *can) { return can->Dlc(); }
 
 EXPORT void CanMessageSetDlc(CanMessage *can, uint8_t dlc) { can->Dlc(dlc); }
 
+EXPORT bool CanMessageGetEdl(const CanMessage *can) {
+  return can ? can->Edl() : false;
+}
+
+EXPORT void CanMessageSetEdl(CanMessage *can, bool edl) {
+  if (can) {
+    can->Edl(edl);
+  }
+}
+
 EXPORT size_t CanMessageGetDataLength(const CanMessage *can) {
   return can->DataLength();
 }
@@ -1261,6 +1568,102 @@ EXPORT const IMetaData *EventGetMetaData(const IEvent *event) {
   return event->MetaData();
 }
 
+EXPORT const IEvent *EventGetRangeEvent(const IEvent *event) {
+  return event->RangeEvent();
+}
+
+EXPORT void EventSetRangeEvent(IEvent *event, const IEvent *range_event) {
+  event->RangeEvent(range_event);
+}
+
+// IChannelHierarchy functions
+EXPORT uint64_t ChannelHierarchyGetIndex(const IChannelHierarchy *hierarchy) {
+  return hierarchy->Index();
+}
+
+EXPORT size_t ChannelHierarchyGetName(const IChannelHierarchy *hierarchy,
+                                      char *name, size_t max_length) {
+  const auto &str = hierarchy->Name();
+  if (name && max_length > 0) {
+    size_t copy_len = std::min(str.size(), max_length - 1);
+    std::memcpy(name, str.c_str(), copy_len);
+    name[copy_len] = '\0';
+  }
+  return str.size();
+}
+
+EXPORT void ChannelHierarchySetName(IChannelHierarchy *hierarchy,
+                                    const char *name) {
+  hierarchy->Name(name ? name : "");
+}
+
+EXPORT uint8_t ChannelHierarchyGetType(const IChannelHierarchy *hierarchy) {
+  return static_cast<uint8_t>(hierarchy->Type());
+}
+
+EXPORT void ChannelHierarchySetType(IChannelHierarchy *hierarchy, uint8_t type) {
+  hierarchy->Type(static_cast<ChType>(type));
+}
+
+EXPORT size_t ChannelHierarchyGetChildren(const IChannelHierarchy *hierarchy,
+                                          const IChannelHierarchy *children[],
+                                          size_t max_count) {
+  if (!hierarchy || !children)
+    return 0;
+
+  std::vector<IChannelHierarchy *> temp_list;
+  hierarchy->ChannelHierarchies(temp_list);
+
+  size_t copy_count = std::min(temp_list.size(), max_count);
+  for (size_t i = 0; i < copy_count; ++i) {
+    children[i] = temp_list[i];
+  }
+  return temp_list.size();
+}
+
+EXPORT IChannelHierarchy *
+ChannelHierarchyCreateChild(IChannelHierarchy *hierarchy) {
+  return hierarchy ? hierarchy->CreateChannelHierarchy() : nullptr;
+}
+
+EXPORT size_t
+ChannelHierarchyGetElementCount(const IChannelHierarchy *hierarchy) {
+  return hierarchy ? hierarchy->ElementLinks().size() : 0;
+}
+
+EXPORT bool ChannelHierarchyGetElement(const IChannelHierarchy *hierarchy,
+                                       size_t index,
+                                       const IDataGroup **data_group,
+                                       const IChannelGroup **channel_group,
+                                       const IChannel **channel) {
+  if (!hierarchy)
+    return false;
+
+  const auto &links = hierarchy->ElementLinks();
+  if (index >= links.size())
+    return false;
+
+  const auto &link = links[index];
+  if (data_group)
+    *data_group = link.data_group;
+  if (channel_group)
+    *channel_group = link.channel_group;
+  if (channel)
+    *channel = link.channel;
+  return true;
+}
+
+EXPORT void ChannelHierarchyAddElement(IChannelHierarchy *hierarchy,
+                                       const IDataGroup *data_group,
+                                       const IChannelGroup *channel_group,
+                                       const IChannel *channel) {
+  ElementLink link;
+  link.data_group = data_group;
+  link.channel_group = channel_group;
+  link.channel = channel;
+  hierarchy->AddElementLink(link);
+}
+
 // IFileHistory functions
 EXPORT uint64_t FileHistoryGetIndex(const IFileHistory *file_history) {
   return file_history->Index();
@@ -1274,6 +1677,32 @@ EXPORT void FileHistorySetTime(IFileHistory *file_history, uint64_t time) {
   file_history->Time(time);
 }
 
+EXPORT int16_t
+FileHistoryGetTimezoneOffsetMin(const IFileHistory *file_history) {
+  if (!file_history) {
+    return 0;
+  }
+  const auto *timestamp = file_history->StartTimestamp();
+  return timestamp ? timestamp->GetTzOffsetMin() : 0;
+}
+
+EXPORT int16_t FileHistoryGetDstOffsetMin(const IFileHistory *file_history) {
+  if (!file_history) {
+    return 0;
+  }
+  const auto *timestamp = file_history->StartTimestamp();
+  return timestamp ? timestamp->GetDstOffsetMin() : 0;
+}
+
+EXPORT void FileHistorySetTimezone(IFileHistory *file_history,
+                                   int16_t tz_offset_min,
+                                   int16_t dst_offset_min) {
+  if (file_history) {
+    file_history->Time(
+        TimezoneTimestamp(file_history->Time(), tz_offset_min, dst_offset_min));
+  }
+}
+
 EXPORT const IMetaData *
 FileHistoryGetMetaData(const IFileHistory *file_history) {
   return file_history->MetaData();
@@ -1409,9 +1838,11 @@ EXPORT size_t MetaDataGetProperties(const IMetaData *metadata,
 
   auto temp_list = metadata->Properties();
 
+  // The property list is a temporary copy, so hand out heap copies that the
+  // caller owns and releases with ETagUnInit()
   size_t copy_count = std::min(temp_list.size(), max_count);
   for (size_t i = 0; i < copy_count; ++i) {
-    properties[i] = &temp_list[i];
+    properties[i] = new ETag(temp_list[i]);
   }
   return temp_list.size();
 }
@@ -1425,9 +1856,11 @@ EXPORT size_t MetaDataGetCommonProperties(const IMetaData *metadata,
   auto temp_list =
       metadata->Properties(); // Use the general Properties for reading
 
+  // The property list is a temporary copy, so hand out heap copies that the
+  // caller owns and releases with ETagUnInit()
   size_t copy_count = std::min(temp_list.size(), max_count);
   for (size_t i = 0; i < copy_count; ++i) {
-    properties[i] = &temp_list[i];
+    properties[i] = new ETag(temp_list[i]);
   }
   return temp_list.size();
 }
@@ -1624,6 +2057,96 @@ EXPORT uint64_t ChannelArrayGetNofElements(const IChannelArray *array) {
   return array->Dimensions();
 }
 
+EXPORT size_t ChannelArrayGetAxisValues(const IChannelArray *array,
+                                        size_t dimension, double *values,
+                                        size_t max_count) {
+  if (!array || dimension >= array->Dimensions()) {
+    return 0;
+  }
+  // The fixed axis values of all dimensions are stored one after the other
+  size_t offset = 0;
+  for (size_t dim = 0; dim < dimension; ++dim) {
+    offset += array->DimensionSize(dim);
+  }
+  const auto &axis_values = array->AxisValues();
+  const size_t count = array->DimensionSize(dimension);
+  if (offset + count > axis_values.size()) {
+    return 0;
+  }
+  if (values) {
+    const size_t copy_count = std::min(count, max_count);
+    std::copy_n(axis_values.begin() + offset, copy_count, values);
+  }
+  return count;
+}
+
+EXPORT size_t ChannelArrayGetShape(const IChannelArray *array,
+                                   uint64_t *sizes, size_t max_count) {
+  if (!array) {
+    return 0;
+  }
+  const size_t count = array->Dimensions();
+  if (sizes) {
+    for (size_t dim = 0; dim < std::min(count, max_count); ++dim) {
+      sizes[dim] = array->DimensionSize(dim);
+    }
+  }
+  return count;
+}
+
+EXPORT void ChannelArraySetShape(IChannelArray *array, const uint64_t *sizes,
+                                 size_t count) {
+  if (!array || (!sizes && count > 0)) {
+    return;
+  }
+  array->Shape().assign(sizes, sizes + count);
+}
+
+EXPORT bool ChannelArraySetAxisValues(IChannelArray *array, size_t dimension,
+                                      const double *values, size_t count) {
+  if (!array || (!values && count > 0) || dimension >= array->Dimensions() ||
+      count != array->DimensionSize(dimension)) {
+    return false;
+  }
+
+  // The fixed axis values of all dimensions are stored one after the other
+  size_t offset = 0;
+  size_t total = 0;
+  for (size_t dim = 0; dim < array->Dimensions(); ++dim) {
+    if (dim < dimension) {
+      offset += array->DimensionSize(dim);
+    }
+    total += array->DimensionSize(dim);
+  }
+  auto &axis_values = array->AxisValues();
+  if (axis_values.size() < total) {
+    axis_values.resize(total, 0.0);
+  }
+  std::copy_n(values, count, axis_values.begin() + offset);
+  array->Flags(array->Flags() | CaFlag::FixedAxis);
+  return true;
+}
+  auto &shape = array->Shape();
+  if (shape.size() <= dimension) {
+    shape.resize(dimension + 1, 0);
+  }
+
+  size_t offset = 0;
+  for (size_t dim = 0; dim < dimension; ++dim) {
+    offset += shape[dim];
+  }
+  auto &axis_values = array->AxisValues();
+  if (axis_values.size() < offset + shape[dimension]) {
+    axis_values.resize(offset + shape[dimension], 0.0);
+  }
+  const auto first = axis_values.begin() + offset;
+  axis_values.erase(first, first + shape[dimension]);
+  axis_values.insert(axis_values.begin() + offset, values, values + count);
+
+  shape[dimension] = count;
+  array->Flags(array->Flags() | CaFlag::FixedAxis);
+}
+
 // IChannelConversion functions
 EXPORT uint64_t
 ChannelConversionGetIndex(const IChannelConversion *conversion) {
@@ -1752,12 +2275,48 @@ ChannelConversionGetParameterAsDouble(const IChannelConversion *conversion,
   return conversion->Parameter(index);
 }
 
+EXPORT bool
+ChannelConversionConvertValue(const IChannelConversion *conversion,
+                              double channel_value, double *eng_value) {
+  if (!conversion || !eng_value) {
+    return false;
+  }
+  return conversion->Convert(channel_value, *eng_value);
+}
+
 EXPORT void
 ChannelConversionSetParameterAsDouble(IChannelConversion *conversion,
                                       uint16_t index, double parameter) {
   conversion->Parameter(index, parameter);
 }
 
+EXPORT uint16_t
+ChannelConversionGetNofParameters(const IChannelConversion *conversion) {
+  return conversion ? conversion->NofParameters() : 0;
+}
+
+EXPORT uint16_t
+ChannelConversionGetNofReferences(const IChannelConversion *conversion) {
+  return conversion ? conversion->NofReferences() : 0;
+}
+
+EXPORT size_t
+ChannelConversionGetReference(const IChannelConversion *conversion,
+                              uint16_t index, char *text, size_t max_length) {
+  const auto str = conversion->Reference(index);
+  if (text && max_length > 0) {
+    size_t copy_len = std::min(str.size(), max_length - 1);
+    std::memcpy(text, str.c_str(), copy_len);
+    text[copy_len] = '\0';
+  }
+  return str.size();
+}
+
+EXPORT void ChannelConversionSetReference(IChannelConversion *conversion,
+                                          uint16_t index, const char *text) {
+  conversion->Reference(index, text ? text : "");
+}
+
 EXPORT uint64_t ChannelConversionGetParameterAsUInt64(
     const IChannelConversion *conversion, uint16_t index) {
   return static_cast<uint64_t>(conversion->Parameter(index));
@@ -1779,6 +2338,16 @@ ChannelConversionCreateMetaData(IChannelConversion *conversion) {
   return conversion->CreateMetaData();
 }
 
+EXPORT const IChannelConversion *
+ChannelConversionGetInverse(const IChannelConversion *conversion) {
+  return conversion ? conversion->Inverse() : nullptr;
+}
+
+EXPORT IChannelConversion *
+ChannelConversionCreateInverse(IChannelConversion *conversion) {
+  return conversion ? conversion->CreateInverse() : nullptr;
+}
+
 // IChannelObserver functions
 EXPORT IChannelObserver* CreateChannelObserver(const IDataGroup* dataGroup, const IChannelGroup* channelGroup, const IChannel* channel) {
   if (!dataGroup || !channelGroup || !channel) {
@@ -1818,6 +2387,67 @@ EXPORT bool ChannelObserverGetValid(const IChannelObserver* observer, size_t sam
   return sample < valid_list.size() && valid_list[sample];
 }
 
+EXPORT size_t ChannelObserverGetName(const IChannelObserver* observer, char* name, size_t max_length) {
+  if (!observer) {
+    return 0;
+  }
+  const std::string observer_name = observer->Name();
+  if (name && max_length > 0) {
+    size_t copy_length = std::min(observer_name.length(), max_length - 1);
+    std::memcpy(name, observer_name.c_str(), copy_length);
+    name[copy_length] = '\0';
+  }
+  return observer_name.length();
+}
+
+EXPORT const IChannel* ChannelObserverGetChannel(const IChannelObserver* observer) {
+  return observer ? &observer->Channel() : nullptr;
+}
+
+EXPORT uint8_t ChannelObserverGetDataType(const IChannelObserver* observer) {
+  return observer ? static_cast<uint8_t>(observer->Channel().DataType()) : 0;
+}
+
+EXPORT bool ChannelObserverGetChannelValueUnsigned(const IChannelObserver* observer, size_t sample, uint64_t* value) {
+  if (!observer || !value) {
+    return false;
+  }
+  return observer->GetChannelValue(sample, *value);
+}
+
+EXPORT bool ChannelObserverGetChannelValueSigned(const IChannelObserver* observer, size_t sample, int64_t* value) {
+  if (!observer || !value) {
+    return false;
+  }
+  return observer->GetChannelValue(sample, *value);
+}
+
+EXPORT size_t ChannelObserverGetChannelValueString(const IChannelObserver* observer, size_t sample, char* value, size_t max_length) {
+  if (!observer) {
+    return 0;
+  }
+  std::string text;
+  observer->GetChannelValue(sample, text);
+  if (value && max_length > 0) {
+    size_t copy_length = std::min(text.length(), max_length - 1);
+    std::memcpy(value, text.c_str(), copy_length);
+    value[copy_length] = '\0';
+  }
+  return text.length();
+}
+
+EXPORT size_t ChannelObserverGetChannelValueBytes(const IChannelObserver* observer, size_t sample, uint8_t* value, size_t max_length) {
+  if (!observer) {
+    return 0;
+  }
+  std::vector<uint8_t> bytes;
+  observer->GetChannelValue(sample, bytes);
+  if (value && max_length > 0) {
+    std::memcpy(value, bytes.data(), std::min(bytes.size(), max_length));
+  }
+  return bytes.size();
+}
+
 // CanBusObserver functions
 EXPORT CanBusObserver* CreateCanBusObserver(const IDataGroup* dataGroup, const IChannelGroup* channelGroup) {
   if (!dataGroup || !channelGroup) {
@@ -1858,4 +2488,49 @@ EXPORT const CanMessage* CanBusObserverGetCanMessage(CanBusObserver* observer, s
   return observer->GetCanMessage(sample);
 }
 
+// Sample counter functions
+EXPORT SampleCounter* CreateSampleCounter(const IDataGroup* dataGroup, const IChannelGroup* channelGroup) {
+  if (!dataGroup || !channelGroup) {
+    return nullptr;
+  }
+  return new SampleCounter(*dataGroup, *channelGroup);
+}
+
+EXPORT void SampleCounterUnInit(SampleCounter* counter) {
+  delete counter;
+}
+
+EXPORT uint64_t SampleCounterGetCount(const SampleCounter* counter) {
+  return counter ? counter->Count() : 0;
+}
+
+// Record observer functions
+EXPORT RecordObserver* CreateRecordObserver(const IDataGroup* dataGroup, const IChannelGroup* channelGroup,
+                                            const IChannel* channel) {
+  if (!dataGroup || !channelGroup || !channel) {
+    return nullptr;
+  }
+  return new RecordObserver(*dataGroup, *channelGroup, *channel);
+}
+
+EXPORT void RecordObserverUnInit(RecordObserver* observer) {
+  delete observer;
+}
+
+EXPORT size_t RecordObserverGetNofRecords(const RecordObserver* observer) {
+  return observer ? observer->NofRecords() : 0;
+}
+
+EXPORT size_t RecordObserverGetBytes(const RecordObserver* observer, size_t index, uint8_t* buffer,
+                                     size_t max_length) {
+  const auto* bytes = observer ? observer->Bytes(index) : nullptr;
+  if (!bytes) {
+    return 0;
+  }
+  if (buffer && max_length > 0) {
+    std::memcpy(buffer, bytes->data(), std::min(max_length, bytes->size()));
+  }
+  return bytes->size();
+}
+
 } // extern "C"
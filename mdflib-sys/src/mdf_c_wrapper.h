@@ -23,11 +23,14 @@ typedef struct ISourceInformation ISourceInformation;
 typedef struct IAttachment IAttachment;
 typedef struct IFileHistory IFileHistory;
 typedef struct IEvent IEvent;
+typedef struct IChannelHierarchy IChannelHierarchy;
 typedef struct ETag ETag;
 typedef struct IMetaData IMetaData;
 typedef struct CanMessage CanMessage;
 typedef struct IChannelObserver IChannelObserver;
 typedef struct CanBusObserver CanBusObserver;
+typedef struct SampleCounter SampleCounter;
+typedef struct RecordObserver RecordObserver;
 
 enum class MdfWriterType : int {
   Mdf3Basic = 0, ///< Basic MDF version 3 writer.
@@ -176,6 +179,15 @@ constexpr uint32_t EventSignal = 0x2000; ///< Event signal.
 constexpr uint32_t VlsdDataStream = 0x4000; ///< VLSD data stream channel.
 }
 
+/** \brief Channel group flags. See also IChannelGroup::Flags(). */
+namespace CgFlag {
+constexpr uint16_t VlsdChannel = 0x0001; ///< Variable length signal data group.
+constexpr uint16_t BusEvent = 0x0002; ///< Bus event group.
+constexpr uint16_t PlainBusEvent = 0x0004; ///< Plain bus event group.
+constexpr uint16_t RemoteMaster = 0x0008; ///< Remote master group.
+constexpr uint16_t EventSignal = 0x0010; ///< Event signal group.
+}  // namespace CgFlag
+
 /** \brief Type of array.
  *
  */
@@ -361,6 +373,19 @@ enum class EventCause : uint8_t {
   CauseUser = 4,    ///< A user generated this event.
 };
 
+/** \brief Type of channel hierarchy (CH) block. */
+enum class ChType : uint8_t {
+  Group = 0,                  ///< Group of elements.
+  Function = 1,               ///< Function object.
+  Structure = 2,              ///< Structure of elements.
+  MapList = 3,                ///< Map list.
+  InputVariable = 4,          ///< Input variable of a function.
+  OutputVariable = 5,         ///< Output variable of a function.
+  LocalVariable = 6,          ///< Local variable of a function.
+  CalibrationDefinition = 7,  ///< Calibration object defined in a function.
+  CalibrationObject = 8       ///< Calibration object referenced in a function.
+};
+
 /** \brief The e-tag may optional have a data type below. The value in the
  * XML file is of course string but the data type may be used for
  * interpretation of the value. Note that unit property can also be added.
@@ -428,6 +453,9 @@ typedef struct {
 typedef void (*MdfCLogFunction1)(MdfLogSeverity severity, const char* text);
 typedef void (*MdfCLogFunction2)(MdfLogSeverity severity, const char* function, const char* text);
 
+// Gets the version of the linked mdflib library, "unknown" if it could not be detected at build time
+EXPORT size_t MdfGetLibraryVersion(char* version, size_t max_length);
+
 // Functions to set the log callbacks
 EXPORT void MdfSetLogFunction1(MdfCLogFunction1 func);
 EXPORT void MdfSetLogFunction2(MdfCLogFunction2 func);
@@ -444,10 +472,13 @@ EXPORT const IDataGroup* MdfReaderGetDataGroup(MdfReader* reader, size_t index);
 EXPORT size_t MdfReaderGetDataGroupCount(MdfReader* reader);
 EXPORT bool MdfReaderOpen(MdfReader* reader);
 EXPORT void MdfReaderClose(MdfReader* reader);
+EXPORT bool MdfReaderIsOpen(MdfReader* reader);
 EXPORT bool MdfReaderReadHeader(MdfReader* reader);
 EXPORT bool MdfReaderReadMeasurementInfo(MdfReader* reader);
 EXPORT bool MdfReaderReadEverythingButData(MdfReader* reader);
 EXPORT bool MdfReaderReadData(MdfReader* reader, IDataGroup* group);
+EXPORT bool MdfReaderReadPartialData(MdfReader* reader, IDataGroup* group, size_t min_sample, size_t max_sample);
+EXPORT bool MdfReaderExportAttachmentData(MdfReader* reader, const IAttachment* attachment, const char* dest_file);
 
 // MdfWriter functions
 EXPORT MdfWriter* MdfWriterInit(MdfWriterType type, const char* filename);
@@ -457,12 +488,15 @@ EXPORT IHeader* MdfWriterGetHeader(MdfWriter* writer);
 EXPORT bool MdfWriterIsFileNew(MdfWriter* writer);
 EXPORT bool MdfWriterGetCompressData(MdfWriter* writer);
 EXPORT void MdfWriterSetCompressData(MdfWriter* writer, bool compress);
+EXPORT bool MdfWriterCompressionAvailable();
 EXPORT double MdfWriterGetPreTrigTime(MdfWriter* writer);
 EXPORT void MdfWriterSetPreTrigTime(MdfWriter* writer, double pre_trig_time);
 EXPORT uint64_t MdfWriterGetStartTime(MdfWriter* writer);
 EXPORT uint64_t MdfWriterGetStopTime(MdfWriter* writer);
 EXPORT uint16_t MdfWriterGetBusType(MdfWriter* writer);
 EXPORT void MdfWriterSetBusType(MdfWriter* writer, uint16_t type);
+EXPORT MdfStorageType MdfWriterGetStorageType(MdfWriter* writer);
+EXPORT void MdfWriterSetStorageType(MdfWriter* writer, MdfStorageType type);
 EXPORT bool MdfWriterCreateBusLogConfiguration(MdfWriter* writer);
 EXPORT IDataGroup* MdfWriterCreateDataGroup(MdfWriter* writer);
 EXPORT bool MdfWriterInitMeasurement(MdfWriter* writer);
@@ -492,6 +526,7 @@ EXPORT size_t MdfFileGetDataGroups(const MdfFile* file, IDataGroup* dest[], size
 EXPORT IDataGroup* MdfFileFindParentDataGroup(const MdfFile *file, const IChannel &channel);
 EXPORT void MdfFileSetProgramId(MdfFile *file, const char *program_id);
 EXPORT size_t MdfFileGetProgramId(const MdfFile *file, char *buffer, size_t max_length);
+EXPORT size_t MdfFileGetIdText(const MdfFile *file, char *buffer, size_t max_length);
 // EXPORT void MdfFileReadHeader(MdfFile *file);
 // EXPORT void MdfFileReadMeasurementInfo(MdfFile *file);
 // EXPORT void MdfFileReadEverythingButData(MdfFile *file);
@@ -508,6 +543,9 @@ EXPORT IChannelGroup* DataGroupGetChannelGroupByIndex(const IDataGroup* group, s
 EXPORT IChannelGroup* DataGroupGetChannelGroupByName(const IDataGroup* group, const char* name);
 EXPORT IChannelGroup* DataGroupCreateChannelGroup(IDataGroup* group);
 EXPORT void DataGroupClearData(IDataGroup *group);
+EXPORT bool DataGroupIsRead(const IDataGroup* group);
+EXPORT const IMetaData* DataGroupGetMetaData(const IDataGroup* group);
+EXPORT IMetaData* DataGroupCreateMetaData(IDataGroup* group);
 
 // IChannelGroup functions
 EXPORT uint64_t ChannelGroupGetIndex(const IChannelGroup* group);
@@ -517,15 +555,21 @@ EXPORT size_t ChannelGroupGetDescription(const IChannelGroup* group, char* descr
 EXPORT void ChannelGroupSetDescription(IChannelGroup* group, const char* description);
 EXPORT uint64_t ChannelGroupGetNofSamples(const IChannelGroup* group);
 EXPORT void ChannelGroupSetNofSamples(IChannelGroup* group, uint64_t samples);
+EXPORT uint64_t ChannelGroupGetRecordId(const IChannelGroup* group);
+EXPORT void ChannelGroupSetRecordId(IChannelGroup* group, uint64_t record_id);
+EXPORT uint16_t ChannelGroupGetFlags(const IChannelGroup* group);
 EXPORT size_t ChannelGroupGetChannelCount(const IChannelGroup* group);
 EXPORT const IChannel* ChannelGroupGetChannelByIndex(const IChannelGroup* group, size_t index);
 EXPORT const IChannel *ChannelGroupGetChannelByName(const IChannelGroup *group, const char *name);
+EXPORT IChannel* ChannelGroupGetMutableChannel(IChannelGroup* group, size_t index);
 EXPORT IChannel* ChannelGroupCreateChannel(IChannelGroup* group);
 EXPORT const IMetaData* ChannelGroupGetMetaData(const IChannelGroup* group);
 EXPORT IMetaData* ChannelGroupCreateMetaData(IChannelGroup* group);
 EXPORT const ISourceInformation* ChannelGroupGetSourceInformation(const IChannelGroup* group);
 EXPORT ISourceInformation* ChannelGroupCreateSourceInformation(IChannelGroup* group);
 EXPORT uint8_t ChannelGroupGetBusType(const IChannelGroup* group);
+EXPORT size_t ChannelGroupGetSampleBufferSize(const IChannelGroup* group);
+EXPORT bool ChannelGroupSetSampleBuffer(IChannelGroup* group, const uint8_t* data, size_t size);
 
 // IChannel functions
 EXPORT uint64_t ChannelGetIndex(const IChannel* channel);
@@ -542,8 +586,24 @@ EXPORT void ChannelSetType(IChannel* channel, uint8_t type);
 EXPORT uint8_t ChannelGetDataType(const IChannel* channel);
 EXPORT void ChannelSetDataType(IChannel* channel, uint8_t data_type);
 EXPORT uint64_t ChannelGetDataBytes(const IChannel* channel);
+EXPORT uint32_t ChannelGetByteOffset(const IChannel* channel);
 EXPORT void ChannelSetDataBytes(IChannel* channel, uint64_t bytes);
+EXPORT void ChannelSetByteOffset(IChannel* channel, uint32_t offset);
+EXPORT uint32_t ChannelGetBitCount(const IChannel* channel);
+EXPORT void ChannelSetBitCount(IChannel* channel, uint32_t bits);
+EXPORT uint16_t ChannelGetBitOffset(const IChannel* channel);
+EXPORT void ChannelSetBitOffset(IChannel* channel, uint16_t offset);
+EXPORT bool ChannelIsPrecisionUsed(const IChannel* channel);
+EXPORT uint8_t ChannelGetPrecision(const IChannel* channel);
+EXPORT void ChannelSetPrecision(IChannel* channel, uint8_t precision);
+EXPORT uint8_t ChannelGetSync(const IChannel* channel);
+EXPORT void ChannelSetSync(IChannel* channel, uint8_t sync);
 EXPORT void ChannelSetChannelValue(IChannel* channel, uint32_t value, bool valid);
+EXPORT void ChannelSetChannelValueUnsigned(IChannel* channel, uint64_t value, bool valid);
+EXPORT void ChannelSetChannelValueSigned(IChannel* channel, int64_t value, bool valid);
+EXPORT void ChannelSetChannelValueFloat(IChannel* channel, double value, bool valid);
+EXPORT void ChannelSetChannelValueString(IChannel* channel, const char* value, bool valid);
+EXPORT void ChannelSetChannelValueBytes(IChannel* channel, const uint8_t* value, size_t length, bool valid);
 EXPORT const IMetaData* ChannelGetMetaData(const IChannel* channel);
 EXPORT IMetaData* ChannelCreateMetaData(IChannel* channel);
 EXPORT const ISourceInformation* ChannelGetSourceInformation(const IChannel* channel);
@@ -583,7 +643,11 @@ EXPORT IAttachment* IHeaderCreateAttachment(IHeader* header);
 EXPORT size_t IHeaderGetFileHistories(const IHeader* header, const IFileHistory* histories[], size_t max_count);
 EXPORT IFileHistory* IHeaderCreateFileHistory(IHeader* header);
 EXPORT size_t IHeaderGetEvents(const IHeader* header, const IEvent* events[], size_t max_count);
+EXPORT size_t IHeaderGetEventCount(const IHeader* header);
+EXPORT const IEvent* IHeaderGetEventByIndex(const IHeader* header, size_t index);
 EXPORT IEvent* IHeaderCreateEvent(IHeader* header);
+EXPORT size_t IHeaderGetChannelHierarchies(const IHeader* header, const IChannelHierarchy* hierarchies[], size_t max_count);
+EXPORT IChannelHierarchy* IHeaderCreateChannelHierarchy(IHeader* header);
 EXPORT IDataGroup *IHeaderLastDataGroup(IHeader *header);
 EXPORT size_t IHeaderGetDataGroups(const IHeader *header, const IDataGroup *groups[], size_t max_count);
 EXPORT size_t IHeaderGetDataGroupCount(const IHeader *header);
@@ -648,11 +712,28 @@ EXPORT void EventSetPreTrig(IEvent* event, double time);
 EXPORT double EventGetPostTrig(const IEvent* event);
 EXPORT void EventSetPostTrig(IEvent* event, double time);
 EXPORT const IMetaData* EventGetMetaData(const IEvent* event);
+EXPORT const IEvent* EventGetRangeEvent(const IEvent* event);
+EXPORT void EventSetRangeEvent(IEvent* event, const IEvent* range_event);
+
+// IChannelHierarchy functions
+EXPORT uint64_t ChannelHierarchyGetIndex(const IChannelHierarchy* hierarchy);
+EXPORT size_t ChannelHierarchyGetName(const IChannelHierarchy* hierarchy, char* name, size_t max_length);
+EXPORT void ChannelHierarchySetName(IChannelHierarchy* hierarchy, const char* name);
+EXPORT uint8_t ChannelHierarchyGetType(const IChannelHierarchy* hierarchy);
+EXPORT void ChannelHierarchySetType(IChannelHierarchy* hierarchy, uint8_t type);
+EXPORT size_t ChannelHierarchyGetChildren(const IChannelHierarchy* hierarchy, const IChannelHierarchy* children[], size_t max_count);
+EXPORT IChannelHierarchy* ChannelHierarchyCreateChild(IChannelHierarchy* hierarchy);
+EXPORT size_t ChannelHierarchyGetElementCount(const IChannelHierarchy* hierarchy);
+EXPORT bool ChannelHierarchyGetElement(const IChannelHierarchy* hierarchy, size_t index, const IDataGroup** data_group, const IChannelGroup** channel_group, const IChannel** channel);
+EXPORT void ChannelHierarchyAddElement(IChannelHierarchy* hierarchy, const IDataGroup* data_group, const IChannelGroup* channel_group, const IChannel* channel);
 
 // IFileHistory functions
 EXPORT uint64_t FileHistoryGetIndex(const IFileHistory* file_history);
 EXPORT uint64_t FileHistoryGetTime(const IFileHistory* file_history);
 EXPORT void FileHistorySetTime(IFileHistory* file_history, uint64_t time);
+EXPORT int16_t FileHistoryGetTimezoneOffsetMin(const IFileHistory* file_history);
+EXPORT int16_t FileHistoryGetDstOffsetMin(const IFileHistory* file_history);
+EXPORT void FileHistorySetTimezone(IFileHistory* file_history, int16_t tz_offset_min, int16_t dst_offset_min);
 EXPORT const IMetaData* FileHistoryGetMetaData(const IFileHistory* file_history);
 EXPORT size_t FileHistoryGetDescription(const IFileHistory* file_history, char* desc, size_t max_length);
 EXPORT void FileHistorySetDescription(IFileHistory* file_history, const char* desc);
@@ -672,6 +753,7 @@ EXPORT double MetaDataGetPropertyAsFloat(const IMetaData* metadata, const char*
 EXPORT void MetaDataSetPropertyAsFloat(IMetaData* metadata, const char* index, double prop);
 EXPORT size_t MetaDataGetXmlSnippet(const IMetaData* metadata, char* xml, size_t max_length);
 EXPORT void MetaDataSetXmlSnippet(IMetaData* metadata, const char* xml);
+// Returned properties are copies owned by the caller, free with ETagUnInit()
 EXPORT size_t MetaDataGetProperties(const IMetaData* metadata, ETag* properties[], size_t max_count);
 EXPORT size_t MetaDataGetCommonProperties(const IMetaData* metadata, ETag* properties[], size_t max_count);
 EXPORT void MetaDataAddCommonProperty(IMetaData* metadata, ETag* tag);
@@ -715,6 +797,10 @@ EXPORT void ChannelArraySetStorage(IChannelArray* array, uint8_t storage);
 EXPORT uint32_t ChannelArrayGetFlags(const IChannelArray* array);
 EXPORT void ChannelArraySetFlags(IChannelArray* array, uint32_t flags);
 EXPORT uint64_t ChannelArrayGetNofElements(const IChannelArray* array);
+EXPORT size_t ChannelArrayGetAxisValues(const IChannelArray* array, size_t dimension, double* values, size_t max_count);
+EXPORT size_t ChannelArrayGetShape(const IChannelArray* array, uint64_t* sizes, size_t max_count);
+EXPORT void ChannelArraySetShape(IChannelArray* array, const uint64_t* sizes, size_t count);
+EXPORT bool ChannelArraySetAxisValues(IChannelArray* array, size_t dimension, const double* values, size_t count);
 EXPORT void ChannelArraySetNofElements(IChannelArray* array, uint64_t elements);
 
 // IChannelConversion functions
@@ -738,10 +824,17 @@ EXPORT size_t ChannelConversionGetFormula(const IChannelConversion* conversion,
 EXPORT void ChannelConversionSetFormula(IChannelConversion* conversion, const char* formula);
 EXPORT double ChannelConversionGetParameterAsDouble(const IChannelConversion* conversion, uint16_t index);
 EXPORT void ChannelConversionSetParameterAsDouble(IChannelConversion* conversion, uint16_t index, double parameter);
+EXPORT uint16_t ChannelConversionGetNofParameters(const IChannelConversion* conversion);
+EXPORT bool ChannelConversionConvertValue(const IChannelConversion* conversion, double channel_value, double* eng_value);
+EXPORT uint16_t ChannelConversionGetNofReferences(const IChannelConversion* conversion);
+EXPORT size_t ChannelConversionGetReference(const IChannelConversion* conversion, uint16_t index, char* text, size_t max_length);
+EXPORT void ChannelConversionSetReference(IChannelConversion* conversion, uint16_t index, const char* text);
 EXPORT uint64_t ChannelConversionGetParameterAsUInt64(const IChannelConversion* conversion, uint16_t index);
 EXPORT void ChannelConversionSetParameterAsUInt64(IChannelConversion* conversion, uint16_t index, uint64_t parameter);
 EXPORT const IMetaData* ChannelConversionGetMetaData(const IChannelConversion* conversion);
 EXPORT IMetaData* ChannelConversionCreateMetaData(IChannelConversion* conversion);
+EXPORT const IChannelConversion* ChannelConversionGetInverse(const IChannelConversion* conversion);
+EXPORT IChannelConversion* ChannelConversionCreateInverse(IChannelConversion* conversion);
 
 // CanMessage functions
 EXPORT CanMessage* CanMessageInit();
@@ -753,6 +846,8 @@ EXPORT bool CanMessageGetExtendedId(const CanMessage* can);
 EXPORT void CanMessageSetExtendedId(CanMessage* can, bool extendedId);
 EXPORT uint8_t CanMessageGetDlc(const CanMessage* can);
 EXPORT void CanMessageSetDlc(CanMessage* can, uint8_t dlc);
+EXPORT bool CanMessageGetEdl(const CanMessage* can);
+EXPORT void CanMessageSetEdl(CanMessage* can, bool edl);
 EXPORT size_t CanMessageGetDataLength(const CanMessage* can);
 EXPORT void CanMessageSetDataLength(CanMessage* can, uint32_t dataLength);
 EXPORT size_t CanMessageGetDataBytes(const CanMessage* can, uint8_t* dataList, size_t max_length);
@@ -773,6 +868,13 @@ EXPORT size_t ChannelObserverGetNofSamples(const IChannelObserver* observer);
 EXPORT bool ChannelObserverGetChannelValue(const IChannelObserver* observer, size_t sample, double* value);
 EXPORT bool ChannelObserverGetEngValue(const IChannelObserver* observer, size_t sample, double* value);
 EXPORT bool ChannelObserverGetValid(const IChannelObserver* observer, size_t sample);
+EXPORT size_t ChannelObserverGetName(const IChannelObserver* observer, char* name, size_t max_length);
+EXPORT const IChannel* ChannelObserverGetChannel(const IChannelObserver* observer);
+EXPORT uint8_t ChannelObserverGetDataType(const IChannelObserver* observer);
+EXPORT bool ChannelObserverGetChannelValueUnsigned(const IChannelObserver* observer, size_t sample, uint64_t* value);
+EXPORT bool ChannelObserverGetChannelValueSigned(const IChannelObserver* observer, size_t sample, int64_t* value);
+EXPORT size_t ChannelObserverGetChannelValueString(const IChannelObserver* observer, size_t sample, char* value, size_t max_length);
+EXPORT size_t ChannelObserverGetChannelValueBytes(const IChannelObserver* observer, size_t sample, uint8_t* value, size_t max_length);
 
 // CanBusObserver functions
 EXPORT CanBusObserver* CreateCanBusObserver(const IDataGroup* dataGroup, const IChannelGroup* channelGroup);
@@ -781,6 +883,19 @@ EXPORT size_t CanBusObserverGetName(const CanBusObserver* observer, char* name,
 EXPORT size_t CanBusObserverGetNofSamples(const CanBusObserver* observer);
 EXPORT const CanMessage* CanBusObserverGetCanMessage(CanBusObserver* observer, size_t sample);
 
+// SampleCounter functions
+EXPORT SampleCounter* CreateSampleCounter(const IDataGroup* dataGroup, const IChannelGroup* channelGroup);
+EXPORT void SampleCounterUnInit(SampleCounter* counter);
+EXPORT uint64_t SampleCounterGetCount(const SampleCounter* counter);
+
+// RecordObserver functions
+EXPORT RecordObserver* CreateRecordObserver(const IDataGroup* dataGroup, const IChannelGroup* channelGroup,
+                                            const IChannel* channel);
+EXPORT void RecordObserverUnInit(RecordObserver* observer);
+EXPORT size_t RecordObserverGetNofRecords(const RecordObserver* observer);
+EXPORT size_t RecordObserverGetBytes(const RecordObserver* observer, size_t index, uint8_t* buffer,
+                                     size_t max_length);
+
 #ifdef __cplusplus
 }
 #endif
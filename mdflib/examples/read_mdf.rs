@@ -78,8 +78,11 @@ fn main() -> Result<()> {
             for (j, channel_group) in channel_groups.iter().enumerate() {
                 println!("      Channel Group {j}: {channel_group}");
 
-                if let Some(si) = channel_group.get_source_information() {
-                    println!("        Source Info: {si}");
+                if let (Some(name), Some(path)) = (
+                    channel_group.get_source_name(),
+                    channel_group.get_source_path(),
+                ) {
+                    println!("        Source: {name} ({path})");
                 }
 
                 println!("        Channels ({})", channel_group.get_channel_count());
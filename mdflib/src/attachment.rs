@@ -4,13 +4,66 @@
 //! This module provides access to the attachment's metadata, such as its filename,
 //! file type, and MD5 checksum.
 
+#[cfg(feature = "md5")]
+use crate::error::MdfError;
 use crate::error::Result;
 use crate::metadata::{MetaData, MetaDataRef};
+#[cfg(feature = "md5")]
+use crate::reader::MdfReader;
+#[cfg(feature = "md5")]
+use crate::util::TempFile;
 use mdflib_sys as ffi;
 use std::ffi::{CStr, CString};
 use std::marker::PhantomData;
 use std::ops::Deref;
 use std::os::raw::c_char;
+use std::path::Path;
+
+/// Commonly attached file types and their MIME content type strings.
+///
+/// Use with [`Attachment::set_file_type_typed`] so attachments are labeled
+/// consistently, or [`Attachment::set_file_type`] for any other type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AttachmentFileType {
+    /// CAN database
+    Dbc,
+    /// ASAM MCD-2 MC calibration description
+    A2l,
+    /// AUTOSAR XML
+    Arxml,
+    /// Generic XML
+    Xml,
+    /// Plain text
+    Text,
+    /// Comma separated values
+    Csv,
+    /// ZIP archive
+    Zip,
+    /// Arbitrary binary data
+    OctetStream,
+}
+
+impl AttachmentFileType {
+    /// Gets the MIME content type string stored in the attachment.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AttachmentFileType::Dbc => "application/x-dbc",
+            AttachmentFileType::A2l => "application/x-a2l",
+            AttachmentFileType::Arxml => "application/x-arxml",
+            AttachmentFileType::Xml => "application/xml",
+            AttachmentFileType::Text => "text/plain",
+            AttachmentFileType::Csv => "text/csv",
+            AttachmentFileType::Zip => "application/zip",
+            AttachmentFileType::OctetStream => "application/octet-stream",
+        }
+    }
+}
+
+impl std::fmt::Display for AttachmentFileType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
 
 /// Represents an immutable reference to an attachment in an MDF file.
 #[derive(Debug, Clone, Copy)]
@@ -104,6 +157,36 @@ impl<'a> AttachmentRef<'a> {
         }
     }
 
+    /// Checks the stored MD5 checksum against the attachment data.
+    ///
+    /// The data is exported by `reader`, which must be the reader of the file
+    /// holding the attachment, so checking several attachments of a file
+    /// reuses the open reader. Returns [`MdfError::NotFound`] if no checksum
+    /// is stored and [`MdfError::DataRead`] if the data can't be read. Only
+    /// available with the `md5` feature.
+    #[cfg(feature = "md5")]
+    pub fn check_md5(&self, reader: &mut MdfReader) -> Result<bool> {
+        let expected = self
+            .get_md5()
+            .ok_or_else(|| MdfError::NotFound("attachment MD5 checksum".to_string()))?;
+
+        let dest = TempFile::new("mdflib-md5")?;
+        // mdflib writes the data to the destination file and then compares the
+        // MD5 of that file with the stored checksum, failing the export on a
+        // mismatch. The data is complete in that case so it is still hashed,
+        // only a failure before any data was written is an error.
+        let exported = reader.export_attachment_data(self, dest.path());
+        let data = std::fs::read(dest.path())?;
+        if let Err(err) = exported {
+            if data.is_empty() {
+                return Err(err);
+            }
+        }
+
+        let actual = format!("{:x}", md5::compute(data));
+        Ok(actual.eq_ignore_ascii_case(&expected))
+    }
+
     /// Gets the metadata of the attachment.
     pub fn get_metadata(&self) -> Option<MetaDataRef<'a>> {
         unsafe {
@@ -172,6 +255,26 @@ impl<'a> Attachment<'a> {
         Ok(())
     }
 
+    /// Sets the file type of the attachment from an [`AttachmentFileType`].
+    pub fn set_file_type_typed(&mut self, file_type: AttachmentFileType) -> Result<()> {
+        self.set_file_type(file_type.as_str())
+    }
+
+    /// References an external file instead of embedding its data.
+    ///
+    /// Sets the filename to `path` and clears the embedded and compressed
+    /// flags, so the file stays on disk next to the MDF file. If the file exists
+    /// when the MDF file is written, mdflib calculates and stores its MD5
+    /// checksum.
+    pub fn set_external_file<P: AsRef<Path>>(&mut self, path: P) -> Result<()> {
+        let path_str = crate::util::path_to_utf8(path.as_ref())?;
+
+        self.set_filename(&path_str)?;
+        self.set_embedded(false);
+        self.set_compressed(false);
+        Ok(())
+    }
+
     /// Creates metadata for the attachment.
     pub fn create_metadata(&mut self) -> Option<MetaData<'a>> {
         unsafe {
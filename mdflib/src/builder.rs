@@ -0,0 +1,170 @@
+//! Declarative creation of a data group for writing
+//!
+//! [`DataGroupBuilder`] describes a data group with a single channel group and
+//! its channels, then creates the whole structure in a writer in one call.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use mdflib::{ChannelDataType, ChannelSpec, DataGroupBuilder, MdfWriter, MdfWriterType, Result};
+//!
+//! fn main() -> Result<()> {
+//!     let mut writer = MdfWriter::new(MdfWriterType::Mdf4Basic, "test.mf4")?;
+//!     let data_group = DataGroupBuilder::new("Engine")
+//!         .channel(ChannelSpec::new("Speed", ChannelDataType::FloatLe).unit("rpm"))
+//!         .channel(
+//!             ChannelSpec::new("Temp", ChannelDataType::UnsignedIntegerLe)
+//!                 .data_bytes(1)
+//!                 .unit("degC")
+//!                 .linear(-40.0, 1.0),
+//!         )
+//!         .build(&mut writer)?;
+//!     Ok(())
+//! }
+//! ```
+
+use crate::channel::data_type_default_bytes;
+use crate::datagroup::DataGroup;
+use crate::error::{MdfError, Result};
+use crate::writer::MdfWriter;
+use mdflib_sys::{ChannelDataType, ChannelSyncType, ChannelType, ConversionType};
+
+/// Description of a channel created by [`DataGroupBuilder`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChannelSpec {
+    name: String,
+    unit: String,
+    description: String,
+    data_type: ChannelDataType,
+    data_bytes: u64,
+    linear: Option<(f64, f64)>,
+}
+
+impl ChannelSpec {
+    /// Creates a channel of `data_type` with the natural size of the type
+    /// (see [`data_type_default_bytes`]).
+    pub fn new(name: &str, data_type: ChannelDataType) -> Self {
+        Self {
+            name: name.to_string(),
+            unit: String::new(),
+            description: String::new(),
+            data_type,
+            data_bytes: data_type_default_bytes(data_type),
+            linear: None,
+        }
+    }
+
+    /// Sets the unit of the channel.
+    pub fn unit(mut self, unit: &str) -> Self {
+        self.unit = unit.to_string();
+        self
+    }
+
+    /// Sets the description of the channel.
+    pub fn description(mut self, description: &str) -> Self {
+        self.description = description.to_string();
+        self
+    }
+
+    /// Sets the number of bytes of the channel value, required for text and
+    /// byte array channels.
+    pub fn data_bytes(mut self, data_bytes: u64) -> Self {
+        self.data_bytes = data_bytes;
+        self
+    }
+
+    /// Adds a linear conversion `eng = offset + factor * raw`.
+    pub fn linear(mut self, offset: f64, factor: f64) -> Self {
+        self.linear = Some((offset, factor));
+        self
+    }
+}
+
+/// Builder for a data group with one channel group.
+///
+/// A time master channel "Time" in seconds is created before the declared
+/// channels unless [`DataGroupBuilder::without_master`] is used.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DataGroupBuilder {
+    name: String,
+    description: String,
+    master: bool,
+    channels: Vec<ChannelSpec>,
+}
+
+impl DataGroupBuilder {
+    /// Creates a builder for a channel group named `name`.
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            description: String::new(),
+            master: true,
+            channels: Vec::new(),
+        }
+    }
+
+    /// Sets the description of the data group and channel group.
+    pub fn description(mut self, description: &str) -> Self {
+        self.description = description.to_string();
+        self
+    }
+
+    /// Adds a channel.
+    pub fn channel(mut self, channel: ChannelSpec) -> Self {
+        self.channels.push(channel);
+        self
+    }
+
+    /// Doesn't create the time master channel.
+    pub fn without_master(mut self) -> Self {
+        self.master = false;
+        self
+    }
+
+    /// Creates the data group, channel group and channels in `writer`.
+    ///
+    /// Must be called before [`MdfWriter::init_measurement`]. The channel
+    /// group is the first of the returned data group.
+    pub fn build(&self, writer: &mut MdfWriter) -> Result<DataGroup> {
+        let mut data_group = writer.create_data_group().ok_or(MdfError::NullPointer)?;
+        data_group.set_description(&self.description);
+        let mut channel_group = data_group
+            .create_channel_group()
+            .ok_or(MdfError::NullPointer)?;
+        channel_group.set_name(&self.name);
+        channel_group.set_description(&self.description);
+
+        if self.master {
+            let mut master = channel_group
+                .create_channel()
+                .ok_or(MdfError::NullPointer)?;
+            master.set_name("Time");
+            master.set_unit("s");
+            master.set_type(ChannelType::Master as u8);
+            master.set_sync(ChannelSyncType::Time as u8);
+            master.set_data_type(ChannelDataType::FloatLe as u8);
+            master.set_data_bytes(8);
+        }
+
+        for spec in &self.channels {
+            let mut channel = channel_group
+                .create_channel()
+                .ok_or(MdfError::NullPointer)?;
+            channel.set_name(&spec.name);
+            channel.set_unit(&spec.unit);
+            channel.set_description(&spec.description);
+            channel.set_data_type(spec.data_type as u8);
+            channel.set_data_bytes(spec.data_bytes);
+            if let Some((offset, factor)) = spec.linear {
+                let mut conversion = channel
+                    .create_channel_conversion()
+                    .ok_or(MdfError::NullPointer)?;
+                conversion.set_type(ConversionType::Linear as u8);
+                conversion.set_parameter_as_double(0, offset);
+                conversion.set_parameter_as_double(1, factor);
+            }
+        }
+
+        Ok(data_group)
+    }
+}
@@ -4,7 +4,10 @@
 //! access to CAN messages parsed from the channel data.
 
 use crate::canmessage::CanMessageRef;
-use crate::error::Result;
+use crate::channelgroup::ChannelGroupRef;
+use crate::datagroup::DataGroupRef;
+use crate::error::{MdfError, Result};
+use crate::reader::MdfReader;
 use mdflib_sys as ffi;
 use std::marker::PhantomData;
 
@@ -80,6 +83,35 @@ impl<'a> CanBusObserverRef<'a> {
         }
     }
 
+    /// Gets the CAN ID (without the extended flag) of a sample.
+    ///
+    /// Reads only the ID of the stored message, which is cheaper than
+    /// [`CanBusObserverRef::get_can_message`] when scanning for an ID.
+    pub fn get_can_id(&self, sample: usize) -> Option<u32> {
+        let can_msg_ptr = unsafe {
+            ffi::CanBusObserverGetCanMessage(self.inner as *mut ffi::CanBusObserver, sample)
+        };
+        if can_msg_ptr.is_null() {
+            None
+        } else {
+            Some(unsafe { ffi::CanMessageGetCanId(can_msg_ptr) })
+        }
+    }
+
+    /// Gets the timestamp of a sample in nanoseconds relative to the start of
+    /// the measurement.
+    pub fn get_timestamp(&self, sample: usize) -> Option<u64> {
+        let can_msg_ptr = unsafe {
+            ffi::CanBusObserverGetCanMessage(self.inner as *mut ffi::CanBusObserver, sample)
+        };
+        if can_msg_ptr.is_null() {
+            None
+        } else {
+            let timestamp = unsafe { ffi::CanMessageGetTimestamp(can_msg_ptr) };
+            Some((timestamp * 1e9).round() as u64)
+        }
+    }
+
     /// Gets all CAN messages for all samples.
     ///
     /// # Returns
@@ -98,11 +130,16 @@ impl<'a> CanBusObserverRef<'a> {
 /// Represents a mutable CAN bus observer in an MDF file.
 ///
 /// This wrapper provides ownership of the underlying CanBusObserver and automatically
-/// cleans up resources when dropped.
+/// cleans up resources when dropped. The observer borrows the [`MdfReader`] that
+/// owns its data group, so it can't outlive the reader.
+///
+/// The observer is neither `Send` nor `Sync` as it holds a borrow of the
+/// reader: mdflib registers it with the data group and fills it when the
+/// group's data is read, so it must stay on the reader's thread.
 #[derive(Debug)]
 pub struct CanBusObserver<'a> {
     pub(crate) inner: *mut ffi::CanBusObserver,
-    _marker: PhantomData<&'a ()>,
+    _marker: PhantomData<&'a MdfReader>,
 }
 
 impl<'a> CanBusObserver<'a> {
@@ -135,48 +172,41 @@ impl<'a> Drop for CanBusObserver<'a> {
     }
 }
 
-// Safety: CanBusObserver can be safely sent between threads
-unsafe impl<'a> Send for CanBusObserver<'a> {}
-unsafe impl<'a> Sync for CanBusObserver<'a> {}
-
 /// Creates a CAN bus observer for a specific channel group in a data group.
 ///
 /// This function creates a CAN bus observer that can be used to read CAN message data
 /// from a channel group that contains CAN bus data. The observer automatically parses
 /// the CAN messages from the underlying channel data.
 ///
-/// # Safety
-///
-/// This function is unsafe because it takes raw pointers to C++ objects. The caller
-/// must ensure that:
-/// - The pointers are valid and point to live objects
-/// - The objects remain valid for the lifetime of the observer
-/// - The pointers are properly aligned and non-null
-/// - The channel group contains CAN bus data
+/// The observer borrows `reader`, which owns the data group, so it can't outlive
+/// the reader. [`MdfReader::read_data`] only needs a shared reference to the
+/// reader, so the group can still be read while the observer exists.
 ///
 /// # Arguments
-/// * `data_group` - Raw pointer to the data group containing the channel group
-/// * `channel_group` - Raw pointer to the channel group containing CAN data
+/// * `reader` - The reader whose file contains the data group
+/// * `data_group` - The data group containing the channel group
+/// * `channel_group` - The channel group containing CAN data
 ///
 /// # Returns
-/// Returns a `Result<CanBusObserver>` if successful, or an error if creation fails.
+/// Returns a `Result<CanBusObserver>` if successful, [`MdfError::NotFound`] if the
+/// data group doesn't belong to the reader's file or the channel group doesn't
+/// belong to the data group, or an error if creation fails.
 ///
 /// # Example
 /// ```no_run
 /// use mdflib::*;
 ///
 /// # fn example() -> mdflib::Result<()> {
-/// let reader = reader::MdfReader::new("can_data.mf4")?;
-/// // ... get data_group and channel_group from file ...
-/// # let file = reader.get_file().unwrap();
-/// # let data_group = file.get_data_group(0).unwrap();
-/// # let channel_group = data_group.get_channel_group_by_index(0).unwrap();
+/// let mut reader = reader::MdfReader::new("can_data.mf4")?;
+/// reader.read_everything_but_data()?;
+/// let file = reader.get_file().unwrap();
+/// let mut data_group = file.get_data_group(0).unwrap();
+/// let channel_group = data_group.get_channel_group_by_index(0).unwrap();
 ///
 /// // Only create CAN bus observer for CAN channel groups
 /// if channel_group.get_bus_type() == BusType::Can as u8 {
-///     let observer = unsafe {
-///         create_can_bus_observer(data_group.as_ptr(), channel_group.as_ptr())?
-///     };
+///     let observer = create_can_bus_observer(&reader, &data_group, &channel_group)?;
+///     reader.read_data(&mut data_group)?;
 ///     let nof_samples = observer.get_nof_samples();
 ///
 ///     for sample in 0..nof_samples {
@@ -189,14 +219,32 @@ unsafe impl<'a> Sync for CanBusObserver<'a> {}
 /// # Ok(())
 /// # }
 /// ```
-pub unsafe fn create_can_bus_observer<'a>(
-    data_group: *const ffi::IDataGroup,
-    channel_group: *const ffi::IChannelGroup,
+pub fn create_can_bus_observer<'a>(
+    reader: &'a MdfReader,
+    data_group: &DataGroupRef,
+    channel_group: &ChannelGroupRef,
 ) -> Result<CanBusObserver<'a>> {
-    let observer = unsafe { ffi::CreateCanBusObserver(data_group, channel_group) };
+    let file = reader.get_file().ok_or(MdfError::NullPointer)?;
+    if !file
+        .get_data_groups()
+        .iter()
+        .any(|dg| dg.as_ptr() == data_group.as_ptr())
+    {
+        return Err(MdfError::NotFound(data_group.get_description()));
+    }
+    if !data_group
+        .get_channel_groups()
+        .iter()
+        .any(|cg| cg.as_ptr() == channel_group.as_ptr())
+    {
+        return Err(MdfError::NotFound(channel_group.get_name()));
+    }
+
+    let observer =
+        unsafe { ffi::CreateCanBusObserver(data_group.as_ptr(), channel_group.as_ptr()) };
 
     if observer.is_null() {
-        return Err(crate::error::MdfError::NullPointer);
+        return Err(MdfError::NullPointer);
     }
 
     Ok(CanBusObserver::new(observer))
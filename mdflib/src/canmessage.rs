@@ -14,10 +14,89 @@
 //! msg.set_data_bytes(&test_data);
 //! ```
 
+use crate::error::{MdfError, Result};
 use mdflib_sys as ffi;
 use std::marker::PhantomData;
 use std::ops::Deref;
 
+/// Maximum number of data bytes in a CAN FD frame.
+pub const MAX_CAN_DATA_LENGTH: usize = 64;
+
+/// Gets the number of data bytes for a DLC (Data Length Code).
+///
+/// Classic CAN frames carry at most 8 bytes, so DLC values above 8 still mean
+/// 8 bytes. CAN FD frames map DLC 9 to 15 to 12, 16, 20, 24, 32, 48 and 64 bytes.
+pub fn dlc_to_length(dlc: u8, fd: bool) -> usize {
+    match dlc {
+        0..=8 => dlc as usize,
+        _ if !fd => 8,
+        9 => 12,
+        10 => 16,
+        11 => 20,
+        12 => 24,
+        13 => 32,
+        14 => 48,
+        _ => 64,
+    }
+}
+
+/// Owned copy of a CAN message with a fixed size data buffer.
+///
+/// Copying into a frame needs no heap allocation, which suits high throughput
+/// consumers reading many messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OwnedCanFrame {
+    /// CAN ID without the extended flag bit
+    pub id: u32,
+    /// Whether the ID is an extended (29-bit) ID
+    pub extended: bool,
+    /// DLC (Data Length Code)
+    pub dlc: u8,
+    /// Number of valid bytes in `data`
+    pub len: usize,
+    /// Data bytes, only the first `len` bytes are valid
+    pub data: [u8; MAX_CAN_DATA_LENGTH],
+}
+
+impl OwnedCanFrame {
+    /// Gets the valid data bytes of the frame.
+    pub fn data(&self) -> &[u8] {
+        &self.data[..self.len]
+    }
+}
+
+/// Plain CAN frame with no ties to mdflib.
+///
+/// Unlike [`CanMessage`], a `Frame` owns all its data and can be freely passed
+/// around, making it suitable for interop with other CAN stacks. Convert with
+/// `Frame::from(&msg)` and `CanMessage::from(&frame)`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Frame {
+    /// CAN ID without the extended flag bit
+    pub id: u32,
+    /// Whether the ID is an extended (29-bit) ID
+    pub extended: bool,
+    /// DLC (Data Length Code)
+    pub dlc: u8,
+    /// Data bytes
+    pub data: Vec<u8>,
+    /// Timestamp in nanoseconds relative to the start of measurement
+    pub timestamp: u64,
+}
+
+impl From<&CanMessageRef<'_>> for Frame {
+    fn from(msg: &CanMessageRef<'_>) -> Self {
+        Self {
+            id: msg.get_can_id(),
+            extended: msg.get_extended_id(),
+            dlc: msg.get_dlc(),
+            data: msg.get_data_bytes(),
+            timestamp: (msg.get_timestamp() * 1e9).round() as u64,
+        }
+    }
+}
+
 /// Represents an immutable reference to a CAN message.
 #[derive(Debug, Clone, Copy)]
 pub struct CanMessageRef<'a> {
@@ -69,6 +148,18 @@ impl<'a> CanMessageRef<'a> {
         unsafe { ffi::CanMessageGetDlc(self.inner) }
     }
 
+    /// Checks if the message is a CAN FD frame (the EDL flag is set).
+    pub fn is_fd(&self) -> bool {
+        unsafe { ffi::CanMessageGetEdl(self.inner) }
+    }
+
+    /// Gets the frame length in bytes decoded from the DLC.
+    ///
+    /// Uses the CAN FD length table for FD frames, see [`dlc_to_length`].
+    pub fn get_frame_length(&self) -> usize {
+        dlc_to_length(self.get_dlc(), self.is_fd())
+    }
+
     /// Gets the data length.
     pub fn get_data_length(&self) -> usize {
         unsafe { ffi::CanMessageGetDataLength(self.inner) }
@@ -84,6 +175,28 @@ impl<'a> CanMessageRef<'a> {
         }
     }
 
+    /// Copies the data bytes into `out` without allocating.
+    ///
+    /// Returns the number of bytes copied, which is the smaller of the data
+    /// length and the length of `out`.
+    pub fn get_data_bytes_into(&self, out: &mut [u8]) -> usize {
+        let len = unsafe { ffi::CanMessageGetDataBytes(self.inner, out.as_mut_ptr(), out.len()) };
+        len.min(out.len())
+    }
+
+    /// Copies the message into an [`OwnedCanFrame`].
+    pub fn to_owned_frame(&self) -> OwnedCanFrame {
+        let mut data = [0u8; MAX_CAN_DATA_LENGTH];
+        let len = self.get_data_bytes_into(&mut data);
+        OwnedCanFrame {
+            id: self.get_can_id(),
+            extended: self.get_extended_id(),
+            dlc: self.get_dlc(),
+            len,
+            data,
+        }
+    }
+
     /// Gets the bus channel.
     pub fn get_bus_channel(&self) -> u32 {
         unsafe { ffi::CanMessageGetBusChannel(self.inner) }
@@ -122,14 +235,24 @@ impl Default for CanMessage<'_> {
 
 impl<'a> CanMessage<'a> {
     /// Creates a new `CanMessage`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the message can't be allocated, see [`CanMessage::try_new`].
     pub fn new() -> Self {
-        unsafe {
-            let msg = ffi::CanMessageInit();
-            Self {
-                inner: msg,
-                inner_ref: CanMessageRef::new(msg),
-            }
+        Self::try_new().expect("Failed to allocate CanMessage")
+    }
+
+    /// Creates a new `CanMessage`, failing if mdflib can't allocate it.
+    pub fn try_new() -> Result<Self> {
+        let msg = unsafe { ffi::CanMessageInit() };
+        if msg.is_null() {
+            return Err(MdfError::NullPointer);
         }
+        Ok(Self {
+            inner: msg,
+            inner_ref: CanMessageRef::new(msg),
+        })
     }
 
     /// Sets the message ID.
@@ -147,6 +270,11 @@ impl<'a> CanMessage<'a> {
         unsafe { ffi::CanMessageSetDlc(self.inner, dlc) }
     }
 
+    /// Marks the message as a CAN FD frame (sets the EDL flag).
+    pub fn set_fd(&mut self, fd: bool) {
+        unsafe { ffi::CanMessageSetEdl(self.inner, fd) }
+    }
+
     /// Sets the data length.
     pub fn set_data_length(&mut self, data_length: u32) {
         unsafe { ffi::CanMessageSetDataLength(self.inner, data_length) }
@@ -182,6 +310,18 @@ impl<'a> CanMessage<'a> {
     }
 }
 
+impl From<&Frame> for CanMessage<'_> {
+    fn from(frame: &Frame) -> Self {
+        let mut msg = CanMessage::new();
+        msg.set_message_id(frame.id);
+        msg.set_extended_id(frame.extended);
+        msg.set_dlc(frame.dlc);
+        msg.set_data_bytes(&frame.data);
+        msg.set_timestamp(frame.timestamp as f64 / 1e9);
+        msg
+    }
+}
+
 impl<'a> Deref for CanMessage<'a> {
     type Target = CanMessageRef<'a>;
 
@@ -199,3 +339,29 @@ impl<'a> Drop for CanMessage<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_try_new() {
+        let mut msg = CanMessage::try_new().expect("Failed to allocate CanMessage");
+        assert!(!msg.inner.is_null());
+        msg.set_message_id(0x123);
+        assert_eq!(msg.get_message_id(), 0x123);
+    }
+
+    #[test]
+    fn test_dlc_to_length() {
+        for dlc in 0..=8 {
+            assert_eq!(dlc_to_length(dlc, false), dlc as usize);
+            assert_eq!(dlc_to_length(dlc, true), dlc as usize);
+        }
+        assert_eq!(dlc_to_length(9, false), 8);
+        assert_eq!(dlc_to_length(15, false), 8);
+        assert_eq!(dlc_to_length(9, true), 12);
+        assert_eq!(dlc_to_length(13, true), 32);
+        assert_eq!(dlc_to_length(15, true), 64);
+    }
+}
@@ -0,0 +1,88 @@
+//! Plain catalog types describing the structure of an MDF file
+//!
+//! These types own all their data and have no ties to mdflib, so they can be
+//! stored, compared and, with the `serde` feature, serialized freely.
+//!
+//! ```no_run
+//! use mdflib::{ChannelInfo, MdfReader};
+//! let mut reader = MdfReader::new("example.mf4").unwrap();
+//! reader.read_everything_but_data().unwrap();
+//!
+//! let dg = reader.get_data_group(0).unwrap();
+//! let cg = dg.get_channel_group_by_index(0).unwrap();
+//! let channels: Vec<ChannelInfo> = cg.get_channels().iter().map(ChannelInfo::from).collect();
+//! ```
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::channel::ChannelRef;
+use crate::header::MdfHeaderRef;
+
+/// Owned description of a channel.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ChannelInfo {
+    /// Channel name
+    pub name: String,
+    /// Engineering unit
+    pub unit: String,
+    /// Channel description
+    pub description: String,
+    /// Channel type, see [`crate::ChannelType`]
+    pub channel_type: u8,
+    /// Data type, see [`crate::ChannelDataType`]
+    pub data_type: u8,
+    /// Number of data bytes
+    pub data_bytes: u64,
+}
+
+impl From<&ChannelRef<'_>> for ChannelInfo {
+    fn from(channel: &ChannelRef<'_>) -> Self {
+        Self {
+            name: channel.get_name(),
+            unit: channel.get_unit(),
+            description: channel.get_description(),
+            channel_type: channel.get_type(),
+            data_type: channel.get_data_type(),
+            data_bytes: channel.get_data_bytes(),
+        }
+    }
+}
+
+/// Owned description of the file header.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HeaderInfo {
+    /// Measurement ID
+    pub measurement_id: String,
+    /// Recorder ID
+    pub recorder_id: String,
+    /// Author
+    pub author: String,
+    /// Department
+    pub department: String,
+    /// Project
+    pub project: String,
+    /// Subject
+    pub subject: String,
+    /// Description
+    pub description: String,
+    /// Start time in nanoseconds since 1970-01-01
+    pub start_time: u64,
+}
+
+impl From<&MdfHeaderRef> for HeaderInfo {
+    fn from(header: &MdfHeaderRef) -> Self {
+        Self {
+            measurement_id: header.get_measurement_id(),
+            recorder_id: header.get_recorder_id(),
+            author: header.get_author(),
+            department: header.get_department(),
+            project: header.get_project(),
+            subject: header.get_subject(),
+            description: header.get_description(),
+            start_time: header.get_start_time(),
+        }
+    }
+}
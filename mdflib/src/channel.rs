@@ -12,8 +12,123 @@ use std::os::raw::c_char;
 
 use crate::channelarray::{ChannelArray, ChannelArrayRef};
 use crate::channelconversion::{ChannelConversion, ChannelConversionRef};
+use crate::error::{MdfError, Result};
 use crate::metadata::{MetaData, MetaDataRef};
+use crate::reader::MdfReader;
 use crate::sourceinformation::{SourceInformation, SourceInformationRef};
+use crate::util::{decode_text, read_cbytes, read_cstring};
+
+pub use mdflib_sys::ChannelDataType;
+use mdflib_sys::MdfStorageType;
+
+/// Gets the natural number of bytes for a channel data type.
+///
+/// The value is suitable for [`Channel::set_data_bytes`]. Integers default to
+/// 32-bit, floats to double precision and complex values to two doubles.
+/// Variable length types (strings, byte arrays and MIME data) return 0 as
+/// their size depends on the stored value.
+pub fn data_type_default_bytes(ty: ChannelDataType) -> u64 {
+    match ty {
+        ChannelDataType::UnsignedIntegerLe
+        | ChannelDataType::UnsignedIntegerBe
+        | ChannelDataType::SignedIntegerLe
+        | ChannelDataType::SignedIntegerBe => 4,
+        ChannelDataType::FloatLe | ChannelDataType::FloatBe => 8,
+        ChannelDataType::CanOpenDate => 7,
+        ChannelDataType::CanOpenTime => 6,
+        ChannelDataType::ComplexLe | ChannelDataType::ComplexBe => 16,
+        _ => 0,
+    }
+}
+
+/// Byte order of a channel value in the record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ByteOrder {
+    /// Least significant byte first (Intel)
+    LittleEndian,
+    /// Most significant byte first (Motorola)
+    BigEndian,
+    /// Stored byte by byte, e.g. ASCII and UTF-8 text or byte arrays
+    NotApplicable,
+}
+
+/// Gets the byte order encoded in a channel data type.
+///
+/// CANopen date and time values are always little endian.
+pub fn data_type_byte_order(ty: ChannelDataType) -> ByteOrder {
+    match ty {
+        ChannelDataType::UnsignedIntegerLe
+        | ChannelDataType::SignedIntegerLe
+        | ChannelDataType::FloatLe
+        | ChannelDataType::StringUTF16Le
+        | ChannelDataType::CanOpenDate
+        | ChannelDataType::CanOpenTime
+        | ChannelDataType::ComplexLe => ByteOrder::LittleEndian,
+        ChannelDataType::UnsignedIntegerBe
+        | ChannelDataType::SignedIntegerBe
+        | ChannelDataType::FloatBe
+        | ChannelDataType::StringUTF16Be
+        | ChannelDataType::ComplexBe => ByteOrder::BigEndian,
+        _ => ByteOrder::NotApplicable,
+    }
+}
+
+/// A typed channel value for writing.
+///
+/// Used by [`Channel::set_channel_value_typed`] and
+/// [`crate::MdfWriter::save_record`] in place of the separate typed setters.
+/// mdflib converts the value to the data type of the channel it is written to.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SampleValue {
+    /// Unsigned integer value
+    U64(u64),
+    /// Signed integer value
+    I64(i64),
+    /// Floating point value
+    F64(f64),
+    /// Text value
+    Str(String),
+    /// Byte array value
+    Bytes(Vec<u8>),
+    /// No value, the sample is marked invalid
+    Invalid,
+}
+
+impl From<u64> for SampleValue {
+    fn from(value: u64) -> Self {
+        SampleValue::U64(value)
+    }
+}
+
+impl From<i64> for SampleValue {
+    fn from(value: i64) -> Self {
+        SampleValue::I64(value)
+    }
+}
+
+impl From<f64> for SampleValue {
+    fn from(value: f64) -> Self {
+        SampleValue::F64(value)
+    }
+}
+
+impl From<String> for SampleValue {
+    fn from(value: String) -> Self {
+        SampleValue::Str(value)
+    }
+}
+
+impl From<&str> for SampleValue {
+    fn from(value: &str) -> Self {
+        SampleValue::Str(value.to_owned())
+    }
+}
+
+impl From<Vec<u8>> for SampleValue {
+    fn from(value: Vec<u8>) -> Self {
+        SampleValue::Bytes(value)
+    }
+}
 
 /// Represents an immutable reference to a channel in an MDF file.
 #[derive(Debug, Clone, Copy)]
@@ -59,18 +174,20 @@ impl<'a> ChannelRef<'a> {
         unsafe { ffi::ChannelGetIndex(self.inner) }
     }
 
+    /// Gets the byte offset of the channel block in the file.
+    ///
+    /// mdflib identifies blocks by their file position, so this is the same
+    /// value as [`Self::get_index`]. It is 0 for a block that has not been
+    /// read from or written to a file yet.
+    pub fn get_file_position(&self) -> u64 {
+        self.get_index()
+    }
+
     /// Gets the name of the channel.
     pub fn get_name(&self) -> String {
-        unsafe {
-            let mut len = ffi::ChannelGetName(self.inner, std::ptr::null_mut(), 0);
-            if len == 0 {
-                return String::new();
-            }
-            len += 1; // For null terminator
-            let mut buf = vec![0 as c_char; len as usize];
-            ffi::ChannelGetName(self.inner, buf.as_mut_ptr(), len);
-            CStr::from_ptr(buf.as_ptr()).to_string_lossy().into_owned()
-        }
+        read_cstring(64, |buf, len| unsafe {
+            ffi::ChannelGetName(self.inner, buf, len)
+        })
     }
 
     /// Gets the display name of the channel.
@@ -103,16 +220,9 @@ impl<'a> ChannelRef<'a> {
 
     /// Gets the unit of the channel.
     pub fn get_unit(&self) -> String {
-        unsafe {
-            let mut len = ffi::ChannelGetUnit(self.inner, std::ptr::null_mut(), 0);
-            if len == 0 {
-                return String::new();
-            }
-            len += 1; // For null terminator
-            let mut buf = vec![0 as c_char; len as usize];
-            ffi::ChannelGetUnit(self.inner, buf.as_mut_ptr(), len);
-            CStr::from_ptr(buf.as_ptr()).to_string_lossy().into_owned()
-        }
+        read_cstring(64, |buf, len| unsafe {
+            ffi::ChannelGetUnit(self.inner, buf, len)
+        })
     }
 
     /// Gets the type of the channel.
@@ -125,11 +235,59 @@ impl<'a> ChannelRef<'a> {
         unsafe { ffi::ChannelGetDataType(self.inner) }
     }
 
+    /// Gets the byte order of the channel value, see [`data_type_byte_order`].
+    ///
+    /// Unknown data types give [`ByteOrder::NotApplicable`].
+    pub fn byte_order(&self) -> ByteOrder {
+        self.get_channel_data_type()
+            .map(data_type_byte_order)
+            .unwrap_or(ByteOrder::NotApplicable)
+    }
+
+    /// Gets the data type of the channel as [`ChannelDataType`].
+    pub fn get_channel_data_type(&self) -> Result<ChannelDataType> {
+        let value = self.get_data_type();
+        ChannelDataType::try_from(value).map_err(|_| MdfError::InvalidDataType(value))
+    }
+
+    /// Gets the synchronization type of the channel.
+    pub fn get_sync(&self) -> u8 {
+        unsafe { ffi::ChannelGetSync(self.inner) }
+    }
+
     /// Gets the data bytes of the channel.
     pub fn get_data_bytes(&self) -> u64 {
         unsafe { ffi::ChannelGetDataBytes(self.inner) }
     }
 
+    /// Gets the byte offset of the channel value in the record.
+    pub fn get_byte_offset(&self) -> u32 {
+        unsafe { ffi::ChannelGetByteOffset(self.inner) }
+    }
+
+    /// Gets the number of bits of the channel value.
+    pub fn get_bit_count(&self) -> u32 {
+        unsafe { ffi::ChannelGetBitCount(self.inner) }
+    }
+
+    /// Gets the bit offset of the channel value within its first byte.
+    pub fn get_bit_offset(&self) -> u16 {
+        unsafe { ffi::ChannelGetBitOffset(self.inner) }
+    }
+
+    /// Checks if the channel has a display precision set.
+    pub fn is_precision_used(&self) -> bool {
+        unsafe { ffi::ChannelIsPrecisionUsed(self.inner) }
+    }
+
+    /// Gets the number of decimals to display the channel values with.
+    ///
+    /// Only meaningful if [`ChannelRef::is_precision_used`] is true. mdflib
+    /// doesn't store any other display format for a channel.
+    pub fn get_precision(&self) -> u8 {
+        unsafe { ffi::ChannelGetPrecision(self.inner) }
+    }
+
     /// Gets the metadata of the channel.
     pub fn get_metadata(&self) -> Option<MetaDataRef<'_>> {
         unsafe {
@@ -142,6 +300,94 @@ impl<'a> ChannelRef<'a> {
         }
     }
 
+    /// Gets the comment of the channel.
+    ///
+    /// The comment is taken from the `TX` element of the channel's metadata and
+    /// falls back to the plain description if the channel has no metadata.
+    pub fn get_comment(&self) -> String {
+        self.get_metadata()
+            .and_then(|md| md.get_property_as_string("TX").ok())
+            .filter(|comment| !comment.is_empty())
+            .unwrap_or_else(|| self.get_description())
+    }
+
+    /// Gets the name of the channel, decoded for the version of the reader's file.
+    ///
+    /// [`ChannelRef::get_name`] reads all text as UTF-8, but MDF3 files store
+    /// text in the code page of the writing tool. With the `encoding` feature,
+    /// text of an MDF3 file that isn't valid UTF-8 is decoded as Windows-1252.
+    pub fn decode_name(&self, reader: &MdfReader) -> String {
+        let bytes = read_cbytes(64, |buf, len| unsafe {
+            ffi::ChannelGetName(self.inner, buf, len)
+        });
+        decode_text(&bytes, is_mdf3(reader))
+    }
+
+    /// Gets the unit of the channel, decoded as in [`ChannelRef::decode_name`].
+    pub fn decode_unit(&self, reader: &MdfReader) -> String {
+        let bytes = read_cbytes(64, |buf, len| unsafe {
+            ffi::ChannelGetUnit(self.inner, buf, len)
+        });
+        decode_text(&bytes, is_mdf3(reader))
+    }
+
+    /// Gets the description of the channel, decoded as in
+    /// [`ChannelRef::decode_name`].
+    pub fn decode_description(&self, reader: &MdfReader) -> String {
+        let bytes = read_cbytes(256, |buf, len| unsafe {
+            ffi::ChannelGetDescription(self.inner, buf, len)
+        });
+        decode_text(&bytes, is_mdf3(reader))
+    }
+
+    /// Gets the unit of the channel, resolving unit references.
+    ///
+    /// Returns the literal unit if set. Otherwise the first tag of the
+    /// channel's metadata with a unit reference is looked up by name in the
+    /// common properties of the file header's metadata (the unit catalog) and
+    /// the catalog entry's value is returned. Returns an empty string if the
+    /// unit cannot be resolved.
+    pub fn resolve_unit(&self, reader: &MdfReader) -> String {
+        let unit = self.get_unit();
+        if !unit.is_empty() {
+            return unit;
+        }
+
+        let Some(unit_ref) = self.get_metadata().and_then(|md| {
+            md.get_common_properties()
+                .iter()
+                .chain(md.get_properties().iter())
+                .map(|tag| tag.get_unit_ref())
+                .find(|unit_ref| !unit_ref.is_empty())
+        }) else {
+            return String::new();
+        };
+
+        reader
+            .get_header()
+            .and_then(|header| {
+                header.get_metadata().and_then(|md| {
+                    md.get_common_properties()
+                        .iter()
+                        .find(|tag| tag.get_name() == unit_ref)
+                        .map(|tag| tag.get_value_as_string())
+                })
+            })
+            .unwrap_or_default()
+    }
+
+    /// Gets the structured tags of the channel's metadata as `(name, value)` pairs.
+    pub fn get_etags(&self) -> Vec<(String, String)> {
+        self.get_metadata()
+            .map(|md| {
+                md.get_properties()
+                    .iter()
+                    .map(|tag| (tag.get_name(), tag.get_value_as_string()))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
     /// Gets the source information of the channel.
     pub fn get_source_information(&self) -> Option<SourceInformationRef<'_>> {
         unsafe {
@@ -166,6 +412,26 @@ impl<'a> ChannelRef<'a> {
         }
     }
 
+    /// Gets the conversion that applies to the channel values.
+    ///
+    /// `None` means the engineering values equal the raw values: the channel
+    /// has no conversion block or its conversion is a 1:1
+    /// [`ffi::ConversionType::NoConversion`].
+    pub fn effective_conversion(&self) -> Option<ChannelConversionRef<'_>> {
+        self.get_channel_conversion()
+            .filter(|cc| cc.get_type() != ffi::ConversionType::NoConversion as u8)
+    }
+
+    /// Converts a raw value of the channel to its engineering value.
+    ///
+    /// Returns `raw` unchanged if there is no [`ChannelRef::effective_conversion`]
+    /// or the conversion has no numeric result.
+    pub fn apply_conversion(&self, raw: f64) -> f64 {
+        self.effective_conversion()
+            .and_then(|cc| cc.convert(raw))
+            .unwrap_or(raw)
+    }
+
     /// Gets the channel array of the channel.
     pub fn get_channel_array(&self) -> Option<ChannelArrayRef<'_>> {
         unsafe {
@@ -240,6 +506,29 @@ impl<'a> Channel<'a> {
         }
     }
 
+    /// Sets how the channel's values are stored in the record.
+    ///
+    /// [`MdfStorageType::FixedLengthStorage`] stores the value in the record,
+    /// [`MdfStorageType::VlsdStorage`] stores it in a separate signal data
+    /// block and [`MdfStorageType::MlsdStorage`] reserves the maximum length
+    /// set with [`Channel::set_data_bytes`] in the record, padded with zeros.
+    /// This sets the channel type so should not be used on master channels.
+    pub fn set_storage_type(&mut self, storage: MdfStorageType) {
+        let channel_type = match storage {
+            MdfStorageType::VlsdStorage => ffi::ChannelType::VariableLength,
+            MdfStorageType::MlsdStorage => ffi::ChannelType::MaxLength,
+            _ => ffi::ChannelType::FixedLength,
+        };
+        self.set_type(channel_type as u8);
+    }
+
+    /// Sets the synchronization type of the channel.
+    pub fn set_sync(&mut self, sync: u8) {
+        unsafe {
+            ffi::ChannelSetSync(self.inner, sync);
+        }
+    }
+
     /// Sets the data bytes of the channel.
     pub fn set_data_bytes(&mut self, bytes: u64) {
         unsafe {
@@ -247,6 +536,85 @@ impl<'a> Channel<'a> {
         }
     }
 
+    /// Sets the byte offset of the channel value in the record.
+    pub fn set_byte_offset(&mut self, offset: u32) {
+        unsafe {
+            ffi::ChannelSetByteOffset(self.inner, offset);
+        }
+    }
+
+    /// Sets the number of bits of the channel value.
+    ///
+    /// mdflib derives the bit count from [`Channel::set_data_bytes`], so set
+    /// the data bytes first when packing values into fewer bits.
+    pub fn set_bit_count(&mut self, bits: u32) {
+        unsafe {
+            ffi::ChannelSetBitCount(self.inner, bits);
+        }
+    }
+
+    /// Sets the bit offset of the channel value.
+    ///
+    /// Offsets of 8 or more are moved into the byte offset by
+    /// [`crate::ChannelGroup::finalize_layout`].
+    pub fn set_bit_offset(&mut self, offset: u16) {
+        unsafe {
+            ffi::ChannelSetBitOffset(self.inner, offset);
+        }
+    }
+
+    /// Checks and completes the layout of the channel value in the record.
+    ///
+    /// Whole bytes of a bit offset of 8 or more are moved into the byte
+    /// offset, a data bytes of 0 is derived from the bit offset and count, and
+    /// a bit count of 0 is filled with the bits of the data bytes after the
+    /// bit offset. Returns [`MdfError::InvalidLayout`] if neither is set or if
+    /// the bits don't fit within the data bytes, which would corrupt the
+    /// neighbouring values of packed records. Used by
+    /// [`crate::ChannelGroup::finalize_layout`].
+    pub(crate) fn finalize_layout(&mut self) -> Result<()> {
+        let mut bit_offset = u64::from(self.get_bit_offset());
+        if bit_offset >= 8 {
+            let byte_offset = u64::from(self.get_byte_offset()) + bit_offset / 8;
+            let byte_offset = u32::try_from(byte_offset).map_err(|_| {
+                MdfError::InvalidLayout(format!("byte offset {byte_offset} too large"))
+            })?;
+            bit_offset %= 8;
+            self.set_byte_offset(byte_offset);
+            self.set_bit_offset(bit_offset as u16);
+        }
+
+        let bit_count = u64::from(self.get_bit_count());
+        let data_bytes = self.get_data_bytes();
+        match (data_bytes, bit_count) {
+            (0, 0) => {
+                return Err(MdfError::InvalidLayout(
+                    "neither data bytes nor bit count set".to_string(),
+                ))
+            }
+            (0, bits) => {
+                self.set_data_bytes((bit_offset + bits).div_ceil(8));
+                // Setting the data bytes resets the bit count in mdflib
+                self.set_bit_count(bits as u32);
+            }
+            (bytes, 0) => self.set_bit_count((bytes * 8 - bit_offset) as u32),
+            (bytes, bits) if bit_offset + bits > bytes * 8 => {
+                return Err(MdfError::InvalidLayout(format!(
+                    "{bits} bits at bit offset {bit_offset} don't fit in {bytes} data bytes"
+                )))
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Sets the number of decimals to display the channel values with.
+    pub fn set_precision(&mut self, precision: u8) {
+        unsafe {
+            ffi::ChannelSetPrecision(self.inner, precision);
+        }
+    }
+
     /// Sets the channel value.
     pub fn set_channel_value(&mut self, value: u32, valid: bool) {
         unsafe {
@@ -254,6 +622,62 @@ impl<'a> Channel<'a> {
         }
     }
 
+    /// Sets the channel value from a 64-bit unsigned integer.
+    pub fn set_channel_value_u64(&mut self, value: u64, valid: bool) {
+        unsafe {
+            ffi::ChannelSetChannelValueUnsigned(self.inner, value, valid);
+        }
+    }
+
+    /// Sets the channel value from a 64-bit signed integer.
+    pub fn set_channel_value_i64(&mut self, value: i64, valid: bool) {
+        unsafe {
+            ffi::ChannelSetChannelValueSigned(self.inner, value, valid);
+        }
+    }
+
+    /// Sets the channel value from a 64-bit float.
+    pub fn set_channel_value_f64(&mut self, value: f64, valid: bool) {
+        unsafe {
+            ffi::ChannelSetChannelValueFloat(self.inner, value, valid);
+        }
+    }
+
+    /// Sets the channel value from a string.
+    ///
+    /// Strings longer than the channel's data bytes are truncated for fixed
+    /// and maximum length storage.
+    pub fn set_channel_value_str(&mut self, value: &str, valid: bool) -> Result<()> {
+        let c_value = CString::new(value)?;
+        unsafe {
+            ffi::ChannelSetChannelValueString(self.inner, c_value.as_ptr(), valid);
+        }
+        Ok(())
+    }
+
+    /// Sets the channel value from a byte array.
+    pub fn set_channel_value_bytes(&mut self, value: &[u8], valid: bool) {
+        unsafe {
+            ffi::ChannelSetChannelValueBytes(self.inner, value.as_ptr(), value.len(), valid);
+        }
+    }
+
+    /// Sets the channel value from a [`SampleValue`].
+    ///
+    /// [`SampleValue::Invalid`] marks the sample invalid. Fails only for text
+    /// containing a null byte.
+    pub fn set_channel_value_typed(&mut self, value: &SampleValue) -> Result<()> {
+        match value {
+            SampleValue::U64(value) => self.set_channel_value_u64(*value, true),
+            SampleValue::I64(value) => self.set_channel_value_i64(*value, true),
+            SampleValue::F64(value) => self.set_channel_value_f64(*value, true),
+            SampleValue::Str(value) => self.set_channel_value_str(value, true)?,
+            SampleValue::Bytes(value) => self.set_channel_value_bytes(value, true),
+            SampleValue::Invalid => self.set_channel_value_u64(0, false),
+        }
+        Ok(())
+    }
+
     /// Creates metadata for the channel.
     pub fn create_metadata(&mut self) -> Option<MetaData<'_>> {
         unsafe {
@@ -290,6 +714,40 @@ impl<'a> Channel<'a> {
         }
     }
 
+    /// Copies the configuration of `src` into this channel.
+    ///
+    /// The name, display name, description, unit, type, sync type, data type
+    /// and size, the conversion with its inverse and the source information
+    /// are copied. Channel arrays and metadata are not.
+    pub fn copy_from(&mut self, src: &ChannelRef) -> Result<()> {
+        self.set_name(&src.get_name());
+        self.set_display_name(&src.get_display_name());
+        self.set_description(&src.get_description());
+        self.set_unit(&src.get_unit());
+        self.set_type(src.get_type());
+        self.set_sync(src.get_sync());
+        self.set_data_type(src.get_data_type());
+        self.set_data_bytes(src.get_data_bytes());
+
+        if let Some(src_cc) = src.get_channel_conversion() {
+            let mut dst_cc = self
+                .create_channel_conversion()
+                .ok_or(MdfError::NullPointer)?;
+            dst_cc.copy_from(&src_cc)?;
+            if let Some(inverse) = src_cc.get_inverse() {
+                dst_cc.set_inverse(&inverse)?;
+            }
+        }
+
+        if let Some(src_si) = src.get_source_information() {
+            self.create_source_information()
+                .ok_or(MdfError::NullPointer)?
+                .copy_from(&src_si)?;
+        }
+
+        Ok(())
+    }
+
     /// Creates channel array for the channel.
     pub fn create_channel_array(&mut self) -> Option<ChannelArray<'_>> {
         unsafe {
@@ -310,3 +768,40 @@ impl<'a> Deref for Channel<'a> {
         &self.inner_ref
     }
 }
+
+/// Returns true if the reader's file is an MDF3 file, see [`crate::util::decode_text`]
+fn is_mdf3(reader: &MdfReader) -> bool {
+    reader.get_file().is_some_and(|file| !file.is_mdf4())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_data_type_default_bytes() {
+        let expected = [
+            (ChannelDataType::UnsignedIntegerLe, 4),
+            (ChannelDataType::UnsignedIntegerBe, 4),
+            (ChannelDataType::SignedIntegerLe, 4),
+            (ChannelDataType::SignedIntegerBe, 4),
+            (ChannelDataType::FloatLe, 8),
+            (ChannelDataType::FloatBe, 8),
+            (ChannelDataType::StringAscii, 0),
+            (ChannelDataType::StringUTF8, 0),
+            (ChannelDataType::StringUTF16Le, 0),
+            (ChannelDataType::StringUTF16Be, 0),
+            (ChannelDataType::ByteArray, 0),
+            (ChannelDataType::MimeSample, 0),
+            (ChannelDataType::MimeStream, 0),
+            (ChannelDataType::CanOpenDate, 7),
+            (ChannelDataType::CanOpenTime, 6),
+            (ChannelDataType::ComplexLe, 16),
+            (ChannelDataType::ComplexBe, 16),
+        ];
+
+        for (ty, bytes) in expected {
+            assert_eq!(data_type_default_bytes(ty), bytes, "{ty:?}");
+        }
+    }
+}
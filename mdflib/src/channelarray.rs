@@ -3,6 +3,7 @@
 //! A channel array is a special type of channel that represents an array of values.
 //! This is used for signals that are arrays, such as a spectrum or a map.
 
+use crate::error::{MdfError, Result};
 use mdflib_sys as ffi;
 use std::marker::PhantomData;
 use std::ops::Deref;
@@ -61,6 +62,29 @@ impl<'a> ChannelArrayRef<'a> {
     pub fn get_nof_elements(&self) -> u64 {
         unsafe { ffi::ChannelArrayGetNofElements(self.inner) }
     }
+
+    /// Gets the size of each dimension of the channel array.
+    pub fn get_shape(&self) -> Vec<u64> {
+        unsafe {
+            let count = ffi::ChannelArrayGetShape(self.inner, std::ptr::null_mut(), 0);
+            let mut sizes = vec![0; count];
+            ffi::ChannelArrayGetShape(self.inner, sizes.as_mut_ptr(), count);
+            sizes
+        }
+    }
+
+    /// Gets the fixed axis values of a dimension.
+    ///
+    /// Returns an empty `Vec` if the dimension doesn't exist or has no fixed
+    /// axis values.
+    pub fn get_axis_values(&self, dim: usize) -> Vec<f64> {
+        unsafe {
+            let count = ffi::ChannelArrayGetAxisValues(self.inner, dim, std::ptr::null_mut(), 0);
+            let mut values = vec![0.0; count];
+            ffi::ChannelArrayGetAxisValues(self.inner, dim, values.as_mut_ptr(), count);
+            values
+        }
+    }
 }
 
 /// Represents a mutable channel array in an MDF file.
@@ -100,6 +124,43 @@ impl<'a> ChannelArray<'a> {
         }
     }
 
+    /// Sets the size of each dimension of the channel array.
+    pub fn set_shape(&mut self, sizes: &[u64]) {
+        unsafe {
+            ffi::ChannelArraySetShape(self.inner, sizes.as_ptr(), sizes.len());
+        }
+    }
+
+    /// Sets the fixed axis values of a dimension.
+    ///
+    /// There must be one value for each element of the dimension, see
+    /// [`ChannelArray::set_shape`], so the shape and with it the record layout
+    /// of the channel is left as it is. Sets the fixed axis flag
+    /// (`CaFlag_FixedAxis`) on the array. Returns
+    /// [`MdfError::IndexOutOfBounds`] if the dimension doesn't exist and
+    /// [`MdfError::InvalidLayout`] if the number of values doesn't match its
+    /// size.
+    pub fn set_axis_values(&mut self, dim: usize, values: &[f64]) -> Result<()> {
+        let size = *self
+            .get_shape()
+            .get(dim)
+            .ok_or(MdfError::IndexOutOfBounds(dim))?;
+        if values.len() as u64 != size {
+            return Err(MdfError::InvalidLayout(format!(
+                "{} axis values for dimension {dim} of size {size}",
+                values.len()
+            )));
+        }
+        if unsafe { ffi::ChannelArraySetAxisValues(self.inner, dim, values.as_ptr(), values.len()) }
+        {
+            Ok(())
+        } else {
+            Err(MdfError::InvalidLayout(format!(
+                "axis values of dimension {dim}"
+            )))
+        }
+    }
+
     // TODO
     // /// Sets the number of elements in the channel array.
     // pub fn set_nof_elements(&mut self, elements: u64) {
@@ -4,7 +4,7 @@
 //! channel to a physical value. This module provides access to the conversion's
 //! parameters, such as the formula, unit, and range.
 
-use crate::error::Result;
+use crate::error::{MdfError, Result};
 use crate::metadata::{MetaData, MetaDataRef};
 use mdflib_sys as ffi;
 use std::ffi::{CStr, CString};
@@ -145,6 +145,11 @@ impl<'a> ChannelConversionRef<'a> {
         }
     }
 
+    /// Gets the number of parameters.
+    pub fn get_nof_parameters(&self) -> u16 {
+        unsafe { ffi::ChannelConversionGetNofParameters(self.inner) }
+    }
+
     /// Gets a parameter as a double.
     pub fn get_parameter_as_double(&self, index: u16) -> f64 {
         unsafe { ffi::ChannelConversionGetParameterAsDouble(self.inner, index) }
@@ -155,6 +160,81 @@ impl<'a> ChannelConversionRef<'a> {
         unsafe { ffi::ChannelConversionGetParameterAsUInt64(self.inner, index) }
     }
 
+    /// Converts a raw channel value to its engineering value.
+    ///
+    /// Returns `None` if the conversion doesn't give a numeric value, for
+    /// example text conversions, or fails.
+    pub fn convert(&self, raw: f64) -> Option<f64> {
+        let mut value = 0.0;
+        if unsafe { ffi::ChannelConversionConvertValue(self.inner, raw, &mut value) } {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    /// Converts an engineering value back to its raw channel value.
+    ///
+    /// Linear and rational conversions are inverted from their parameters,
+    /// other conversions use the inverse conversion if the block has one.
+    /// Returns `None` if the value can't be inverted, for example when a
+    /// rational conversion has quadratic terms and so no unique raw value.
+    pub fn invert(&self, eng: f64) -> Option<f64> {
+        use ffi::ConversionType;
+
+        let p = |index| self.get_parameter_as_double(index);
+        let conversion_type = self.get_type();
+        let raw = if conversion_type == ConversionType::NoConversion as u8 {
+            eng
+        } else if conversion_type == ConversionType::Linear as u8 {
+            (eng - p(0)) / p(1)
+        } else if conversion_type == ConversionType::Rational as u8 {
+            if p(0) - eng * p(3) != 0.0 {
+                return None;
+            }
+            // eng = (p1 * raw + p2) / (p4 * raw + p5) once the quadratic terms cancel
+            (p(2) - eng * p(5)) / (eng * p(4) - p(1))
+        } else {
+            return self.get_inverse()?.convert(eng);
+        };
+        raw.is_finite().then_some(raw)
+    }
+
+    /// Gets the number of text references.
+    ///
+    /// Text conversions such as [`crate::ConversionType::ValueToText`] map their
+    /// parameters to these texts, usually with a default text last.
+    pub fn get_nof_references(&self) -> u16 {
+        unsafe { ffi::ChannelConversionGetNofReferences(self.inner) }
+    }
+
+    /// Gets a text reference as a string.
+    pub fn get_reference_as_string(&self, index: u16) -> String {
+        unsafe {
+            let mut len =
+                ffi::ChannelConversionGetReference(self.inner, index, std::ptr::null_mut(), 0);
+            if len == 0 {
+                return String::new();
+            }
+            len += 1; // For null terminator
+            let mut buf = vec![0 as c_char; len as usize];
+            ffi::ChannelConversionGetReference(self.inner, index, buf.as_mut_ptr(), len);
+            CStr::from_ptr(buf.as_ptr()).to_string_lossy().into_owned()
+        }
+    }
+
+    /// Gets the inverse conversion (physical to raw value), if any.
+    pub fn get_inverse(&self) -> Option<ChannelConversionRef<'a>> {
+        unsafe {
+            let inverse = ffi::ChannelConversionGetInverse(self.inner);
+            if inverse.is_null() {
+                None
+            } else {
+                Some(ChannelConversionRef::new(inverse))
+            }
+        }
+    }
+
     /// Gets the metadata.
     pub fn get_metadata(&self) -> Option<MetaDataRef<'a>> {
         unsafe {
@@ -248,6 +328,61 @@ impl<'a> ChannelConversion<'a> {
         }
     }
 
+    /// Sets a text reference.
+    pub fn set_reference(&mut self, index: u16, text: &str) -> Result<()> {
+        let c_text = CString::new(text)?;
+        unsafe {
+            ffi::ChannelConversionSetReference(self.inner, index, c_text.as_ptr());
+        }
+        Ok(())
+    }
+
+    /// Copies the name, description, unit, type, range, formula, parameters
+    /// and text references of another conversion into this one.
+    pub fn copy_from(&mut self, other: &ChannelConversionRef) -> Result<()> {
+        self.set_name(&other.get_name())?;
+        self.set_description(&other.get_description())?;
+        self.set_unit(&other.get_unit())?;
+        self.set_type(other.get_type());
+        if other.is_range_used() {
+            self.set_range(other.get_range_min(), other.get_range_max());
+        }
+        let formula = other.get_formula();
+        if !formula.is_empty() {
+            self.set_formula(&formula)?;
+        }
+        for index in 0..other.get_nof_parameters() {
+            self.set_parameter_as_double(index, other.get_parameter_as_double(index));
+        }
+        for index in 0..other.get_nof_references() {
+            self.set_reference(index, &other.get_reference_as_string(index))?;
+        }
+        Ok(())
+    }
+
+    /// Creates the inverse conversion (physical to raw value).
+    ///
+    /// MDF4 only; returns the existing inverse if there is one.
+    pub fn create_inverse(&mut self) -> Option<ChannelConversion<'a>> {
+        unsafe {
+            let inverse = ffi::ChannelConversionCreateInverse(self.inner);
+            if inverse.is_null() {
+                None
+            } else {
+                Some(ChannelConversion::new(inverse))
+            }
+        }
+    }
+
+    /// Sets the inverse conversion to a copy of `other`.
+    ///
+    /// mdflib owns the inverse block, so it is created with
+    /// [`ChannelConversion::create_inverse`] and configured from `other`.
+    pub fn set_inverse(&mut self, other: &ChannelConversionRef) -> Result<()> {
+        let mut inverse = self.create_inverse().ok_or(MdfError::NullPointer)?;
+        inverse.copy_from(other)
+    }
+
     /// Creates metadata for the channel conversion.
     pub fn create_metadata(&mut self) -> Option<MetaData<'a>> {
         unsafe {
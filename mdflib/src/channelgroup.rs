@@ -10,8 +10,38 @@ use std::ops::Deref;
 use std::os::raw::c_char;
 
 use crate::channel::{Channel, ChannelRef};
+use crate::channelobserver::create_channel_observer;
+use crate::error::{MdfError, Result};
 use crate::metadata::{MetaData, MetaDataRef};
+use crate::reader::MdfReader;
 use crate::sourceinformation::{SourceInformation, SourceInformationRef};
+use crate::writer::MdfWriter;
+
+/// Approximate size of the identification, header and file history blocks
+const FILE_OVERHEAD_BYTES: u64 = 1024;
+/// Approximate size of the data group, channel group and data blocks of a group
+const GROUP_OVERHEAD_BYTES: u64 = 256;
+/// Approximate size of a channel block with its name
+const CHANNEL_OVERHEAD_BYTES: u64 = 256;
+
+/// Estimates the size of an uncompressed MDF4 file in bytes.
+///
+/// `groups` lists each channel group with the number of samples expected to
+/// be written to it. The samples take
+/// [`ChannelGroupRef::estimated_bytes_per_sample`] each and a fixed
+/// approximation is added for the blocks describing the file, groups and
+/// channels. Attachments, metadata and variable length data aren't included.
+pub fn estimate_file_size(groups: &[(ChannelGroupRef, u64)]) -> u64 {
+    groups
+        .iter()
+        .map(|(group, samples)| {
+            GROUP_OVERHEAD_BYTES
+                + group.get_channel_count() as u64 * CHANNEL_OVERHEAD_BYTES
+                + group.estimated_bytes_per_sample() * samples
+        })
+        .sum::<u64>()
+        + FILE_OVERHEAD_BYTES
+}
 
 /// Represents an immutable reference to a channel group in an MDF file.
 ///
@@ -54,6 +84,15 @@ impl ChannelGroupRef {
         unsafe { ffi::ChannelGroupGetIndex(self.inner) }
     }
 
+    /// Gets the byte offset of the channel group block in the file.
+    ///
+    /// mdflib identifies blocks by their file position, so this is the same
+    /// value as [`Self::get_index`]. It is 0 for a block that has not been
+    /// read from or written to a file yet.
+    pub fn get_file_position(&self) -> u64 {
+        self.get_index()
+    }
+
     /// Gets the name of the channel group.
     pub fn get_name(&self) -> String {
         unsafe {
@@ -87,6 +126,81 @@ impl ChannelGroupRef {
         unsafe { ffi::ChannelGroupGetNofSamples(self.inner) }
     }
 
+    /// Gets the record ID of the channel group.
+    ///
+    /// The record ID identifies the records of this group in a data group
+    /// holding several channel groups (unsorted data).
+    pub fn get_record_id(&self) -> u64 {
+        unsafe { ffi::ChannelGroupGetRecordId(self.inner) }
+    }
+
+    /// Gets the flags of the channel group (`CgFlag_*`).
+    pub fn get_flags(&self) -> u16 {
+        unsafe { ffi::ChannelGroupGetFlags(self.inner) }
+    }
+
+    /// Checks if the channel group holds variable length signal data.
+    ///
+    /// These groups store the values of a variable length channel in another
+    /// group of the same data group, for example the data bytes of bus logger
+    /// frames with [`ffi::MdfStorageType::VlsdStorage`], and have no signals
+    /// of their own.
+    pub fn is_vlsd(&self) -> bool {
+        self.get_flags() & ffi::CgFlag_VlsdChannel != 0
+    }
+
+    /// Checks if the channel group holds bus events, such as the frame groups
+    /// made by [`crate::MdfWriter::create_bus_log_configuration`].
+    pub fn is_bus_event(&self) -> bool {
+        self.get_flags() & ffi::CgFlag_BusEvent != 0
+    }
+
+    /// Gets the number of data bytes in each record of the channel group.
+    ///
+    /// Computed from the channel layout as the sum of the data bytes of all
+    /// channels stored in the record. Virtual channels don't occupy record
+    /// bytes and are skipped. The record ID and invalidation bytes aren't
+    /// included.
+    pub fn get_record_byte_count(&self) -> u64 {
+        self.get_channels()
+            .iter()
+            .filter(|cn| {
+                let channel_type = cn.get_type();
+                channel_type != ffi::ChannelType::VirtualMaster as u8
+                    && channel_type != ffi::ChannelType::VirtualData as u8
+            })
+            .map(|cn| cn.get_data_bytes())
+            .sum()
+    }
+
+    /// Estimates the number of bytes each sample takes in the file.
+    ///
+    /// This is [`ChannelGroupRef::get_record_byte_count`] plus one invalidation
+    /// bit for every stored channel other than the master, rounded up to whole
+    /// bytes. Record ID bytes of unsorted data groups aren't included.
+    pub fn estimated_bytes_per_sample(&self) -> u64 {
+        let invalid_bits = self
+            .get_channels()
+            .iter()
+            .filter(|cn| {
+                let channel_type = cn.get_type();
+                channel_type != ffi::ChannelType::Master as u8
+                    && channel_type != ffi::ChannelType::VirtualMaster as u8
+                    && channel_type != ffi::ChannelType::VirtualData as u8
+            })
+            .count() as u64;
+        self.get_record_byte_count() + invalid_bits.div_ceil(8)
+    }
+
+    /// Gets the size in bytes of the group's sample buffer, the record layout
+    /// used by [`ChannelGroup::write_block`].
+    ///
+    /// The writer sizes the buffer when the measurement is initialized, before
+    /// that this is zero.
+    pub fn get_sample_buffer_size(&self) -> usize {
+        unsafe { ffi::ChannelGroupGetSampleBufferSize(self.inner) }
+    }
+
     /// Gets the number of channels in the channel group.
     pub fn get_channel_count(&self) -> usize {
         unsafe { ffi::ChannelGroupGetChannelCount(self.inner) }
@@ -153,10 +267,109 @@ impl ChannelGroupRef {
         }
     }
 
+    /// Gets the name of the channel group's acquisition source.
+    ///
+    /// Returns `None` if the channel group has no source information.
+    pub fn get_source_name(&self) -> Option<String> {
+        self.get_source_information().map(|si| si.get_name())
+    }
+
+    /// Gets the path of the channel group's acquisition source, e.g. "CAN1".
+    ///
+    /// Returns `None` if the channel group has no source information.
+    pub fn get_source_path(&self) -> Option<String> {
+        self.get_source_information().map(|si| si.get_path())
+    }
+
     /// Gets the bus type of the channel group.
     pub fn get_bus_type(&self) -> u8 {
         unsafe { ffi::ChannelGroupGetBusType(self.inner) }
     }
+
+    /// Counts the samples of this group actually stored in the file.
+    ///
+    /// [`ChannelGroupRef::get_nof_samples`] is the count declared in the
+    /// channel group block, which may differ from the number of records in
+    /// the data blocks, for example after an interrupted or buffer limited
+    /// write. This reads the parent data group's data, counts the records
+    /// belonging to this group and clears the data group's data again, so
+    /// the samples of other observers of the data group are cleared as well.
+    /// The read isn't added to [`MdfReader::records_read`]. The file
+    /// structure must have been read first with
+    /// [`MdfReader::read_everything_but_data`].
+    ///
+    /// Returns [`MdfError::NotFound`] if the group isn't part of the reader's
+    /// file and [`MdfError::DataRead`] if the data can't be read completely.
+    pub fn get_stored_sample_count(&self, reader: &MdfReader) -> Result<usize> {
+        let file = reader.get_file().ok_or(MdfError::NullPointer)?;
+        let mut dg = file
+            .get_data_groups()
+            .into_iter()
+            .find(|dg| {
+                dg.get_channel_groups()
+                    .iter()
+                    .any(|cg| cg.as_ptr() == self.inner)
+            })
+            .ok_or_else(|| MdfError::NotFound(self.get_name()))?;
+
+        unsafe {
+            let counter = ffi::CreateSampleCounter(dg.as_ptr(), self.inner);
+            if counter.is_null() {
+                return Err(MdfError::NullPointer);
+            }
+            let read = reader.read_data_untracked(&mut dg);
+            let count = ffi::SampleCounterGetCount(counter);
+            ffi::SampleCounterUnInit(counter);
+            dg.clear_data();
+            read?;
+            Ok(count as usize)
+        }
+    }
+
+    /// Reads the engineering value of every channel at one sample.
+    ///
+    /// Finds the parent data group in the reader's file, observes all channels
+    /// of this group and reads the data group's data. Returns `(name, value)`
+    /// pairs in channel order, with `None` for invalid values. The file
+    /// structure must have been read first with
+    /// [`MdfReader::read_everything_but_data`].
+    pub fn read_record(
+        &self,
+        reader: &mut MdfReader,
+        sample: usize,
+    ) -> Result<Vec<(String, Option<f64>)>> {
+        if sample as u64 >= self.get_nof_samples() {
+            return Err(MdfError::IndexOutOfBounds(sample));
+        }
+
+        let file = reader.get_file().ok_or(MdfError::NullPointer)?;
+        let mut dg = file
+            .get_data_groups()
+            .into_iter()
+            .find(|dg| {
+                dg.get_channel_groups()
+                    .iter()
+                    .any(|cg| cg.as_ptr() == self.inner)
+            })
+            .ok_or_else(|| MdfError::NotFound(self.get_name()))?;
+
+        let channels = self.get_channels();
+        let mut observers = Vec::with_capacity(channels.len());
+        for channel in &channels {
+            // Safety: all pointers come from the reader's file which outlives the observers
+            let observer =
+                unsafe { create_channel_observer(dg.as_ptr(), self.inner, channel.as_ptr())? };
+            observers.push(observer);
+        }
+
+        reader.read_data(&mut dg)?;
+
+        Ok(channels
+            .iter()
+            .zip(&observers)
+            .map(|(channel, observer)| (channel.get_name(), observer.get_eng_value(sample)))
+            .collect())
+    }
 }
 
 /// Represents a mutable reference to a channel group in an MDF file.
@@ -191,12 +404,190 @@ impl ChannelGroup {
     }
 
     /// Sets the number of samples in the channel group.
+    ///
+    /// The writer counts the samples it saves and stores that number when the
+    /// measurement is finalized, replacing this value. It is only kept for
+    /// files whose data is written by other means than [`MdfWriter`].
     pub fn set_nof_samples(&mut self, samples: u64) {
         unsafe {
             ffi::ChannelGroupSetNofSamples(self.inner, samples);
         }
     }
 
+    /// Sets the record ID of the channel group.
+    ///
+    /// Records saved with [`crate::MdfWriter::save_sample`] are prefixed with
+    /// this ID when the data group holds several channel groups, so it must be
+    /// unique within the data group. The channel groups made by
+    /// [`crate::MdfWriter::create_bus_log_configuration`] get their record IDs
+    /// from mdflib and shouldn't be changed, set them only on groups created
+    /// manually before the measurement is initialized.
+    pub fn set_record_id(&mut self, record_id: u64) {
+        unsafe {
+            ffi::ChannelGroupSetRecordId(self.inner, record_id);
+        }
+    }
+
+    /// Lays out the values of all channels of the group in the record.
+    ///
+    /// The channels are placed back to back in channel order, each starting at
+    /// the byte after the previous one. Within its bytes a channel keeps its
+    /// bit offset, with whole bytes of an offset of 8 or more skipped before
+    /// the value. A data bytes of 0 is derived from the bit offset and count
+    /// and a bit count of 0 is filled with the bits of the data bytes. Virtual
+    /// channels have no bytes in the record and are skipped.
+    ///
+    /// Returns the record size in bytes, without record ID and invalidation
+    /// bytes, which mdflib derives from the channel layout when the
+    /// measurement is initialized. Returns [`MdfError::InvalidLayout`] if the
+    /// bits of a channel don't fit within its data bytes, which would corrupt
+    /// the neighbouring values, or if the record gets too large.
+    pub fn finalize_layout(&mut self) -> Result<usize> {
+        let mut record_size = 0u64;
+        for mut channel in self.get_channels_mut() {
+            let channel_type = channel.get_type();
+            if channel_type == ffi::ChannelType::VirtualMaster as u8
+                || channel_type == ffi::ChannelType::VirtualData as u8
+            {
+                continue;
+            }
+
+            // Lay out the channel relative to its own bytes first
+            channel.set_byte_offset(0);
+            channel.finalize_layout().map_err(|e| match e {
+                MdfError::InvalidLayout(reason) => {
+                    MdfError::InvalidLayout(format!("channel '{}': {reason}", channel.get_name()))
+                }
+                e => e,
+            })?;
+            let byte_offset = record_size + u64::from(channel.get_byte_offset());
+            channel.set_byte_offset(u32::try_from(byte_offset).map_err(|_| {
+                MdfError::InvalidLayout(format!("byte offset {byte_offset} too large"))
+            })?);
+            record_size = byte_offset + channel.get_data_bytes();
+        }
+
+        usize::try_from(record_size)
+            .map_err(|_| MdfError::InvalidLayout(format!("record size {record_size} too large")))
+    }
+
+    /// Saves `nof_samples` pre-assembled records of this group.
+    ///
+    /// `data` holds the records back to back in the layout of the group's
+    /// sample buffer: the channel values at their byte offsets followed by the
+    /// invalidation bytes, without a record ID. The record size is fixed when
+    /// the measurement is initialized, so this must be called after
+    /// [`MdfWriter::init_measurement`]. Bytes after the last record are
+    /// ignored.
+    ///
+    /// mdflib sets the master value from the sample time, so the time of each
+    /// record is taken from its float time master channel in seconds, relative
+    /// to the start time of the writer. Returns [`MdfError::InvalidLayout`] if
+    /// the group has no 4 or 8 byte float master or the size of `nof_samples`
+    /// records doesn't fit in memory, and [`MdfError::InvalidFormat`] if a
+    /// time is negative or not finite. The times are checked before any
+    /// record is saved.
+    pub fn write_block(
+        &mut self,
+        writer: &mut MdfWriter,
+        data: &[u8],
+        nof_samples: u64,
+    ) -> Result<()> {
+        writer.check_saving()?;
+        let record_size = self.get_sample_buffer_size();
+        if record_size == 0 {
+            return Err(MdfError::InvalidWriterState("measurement not initialized"));
+        }
+        let needed = usize::try_from(nof_samples)
+            .ok()
+            .and_then(|nof_samples| record_size.checked_mul(nof_samples))
+            .ok_or_else(|| {
+                MdfError::InvalidLayout(format!(
+                    "{nof_samples} records of {record_size} bytes overflow the address space"
+                ))
+            })?;
+        if data.len() < needed {
+            return Err(MdfError::BufferTooSmall {
+                needed,
+                actual: data.len(),
+            });
+        }
+
+        let (offset, data_type, bytes) = self
+            .get_channels()
+            .into_iter()
+            .find(|cn| cn.get_type() == ffi::ChannelType::Master as u8)
+            .and_then(|cn| {
+                let offset = cn.get_byte_offset() as usize;
+                let bytes = cn.get_data_bytes() as usize;
+                match (ffi::ChannelDataType::try_from(cn.get_data_type()), bytes) {
+                    (
+                        Ok(
+                            data_type @ (ffi::ChannelDataType::FloatLe
+                            | ffi::ChannelDataType::FloatBe),
+                        ),
+                        4 | 8,
+                    ) if offset + bytes <= record_size => Some((offset, data_type, bytes)),
+                    _ => None,
+                }
+            })
+            .ok_or_else(|| {
+                MdfError::InvalidLayout(format!(
+                    "channel group '{}' has no 4 or 8 byte float master",
+                    self.get_name()
+                ))
+            })?;
+        let start_time = writer.get_start_time();
+
+        let records = data[..needed].chunks_exact(record_size);
+        let times = records
+            .clone()
+            .map(|record| {
+                let raw = &record[offset..offset + bytes];
+                let seconds = match (data_type, bytes) {
+                    (ffi::ChannelDataType::FloatLe, 8) => {
+                        f64::from_le_bytes(raw.try_into().unwrap())
+                    }
+                    (ffi::ChannelDataType::FloatBe, 8) => {
+                        f64::from_be_bytes(raw.try_into().unwrap())
+                    }
+                    (ffi::ChannelDataType::FloatLe, _) => {
+                        f32::from_le_bytes(raw.try_into().unwrap()) as f64
+                    }
+                    _ => f32::from_be_bytes(raw.try_into().unwrap()) as f64,
+                };
+                if !seconds.is_finite() || seconds < 0.0 {
+                    return Err(MdfError::InvalidFormat);
+                }
+                Ok(start_time.saturating_add((seconds * 1e9) as u64))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let group = **self;
+        for (record, time) in records.zip(times) {
+            if !unsafe {
+                ffi::ChannelGroupSetSampleBuffer(self.inner, record.as_ptr(), record.len())
+            } {
+                return Err(MdfError::NullPointer);
+            }
+            writer.save_sample(&group, time);
+        }
+        Ok(())
+    }
+
+    /// Gets mutable handles to all channels of the channel group.
+    ///
+    /// The handles borrow the group, so several channels can be set at once,
+    /// e.g. for [`crate::MdfWriter::save_record`].
+    pub fn get_channels_mut(&mut self) -> Vec<Channel<'_>> {
+        (0..self.get_channel_count())
+            .filter_map(|i| unsafe {
+                let ch = ffi::ChannelGroupGetMutableChannel(self.inner, i);
+                (!ch.is_null()).then(|| Channel::new(ch))
+            })
+            .collect()
+    }
+
     /// Creates a new channel in the channel group.
     pub fn create_channel(&mut self) -> Option<Channel<'_>> {
         unsafe {
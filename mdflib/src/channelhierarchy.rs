@@ -0,0 +1,219 @@
+//! Channel hierarchy wrapper for mdflib IChannelHierarchy
+//!
+//! Channel hierarchy (CH) blocks describe a logical structure of the channels
+//! in an MDF4 file, for example the functions and structures of an ECU. Each
+//! block can have child blocks and references elements, typically channels.
+
+use crate::channel::ChannelRef;
+use crate::channelgroup::ChannelGroupRef;
+use crate::datagroup::DataGroupRef;
+use crate::error::{MdfError, Result};
+use mdflib_sys as ffi;
+use mdflib_sys::ChType;
+use std::ffi::{CStr, CString};
+use std::marker::PhantomData;
+use std::ops::Deref;
+use std::os::raw::c_char;
+
+/// Represents an immutable reference to a channel hierarchy block.
+#[derive(Debug, Clone, Copy)]
+pub struct ChannelHierarchyRef<'a> {
+    pub(crate) inner: *const ffi::IChannelHierarchy,
+    _marker: PhantomData<&'a ()>,
+}
+
+impl std::fmt::Display for ChannelHierarchyRef<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "ChannelHierarchy {{ index: {}, name: {}, type: {}, elements: {} }}",
+            self.get_index(),
+            self.get_name(),
+            self.get_type(),
+            self.get_element_count()
+        )
+    }
+}
+
+impl<'a> ChannelHierarchyRef<'a> {
+    pub(crate) fn new(inner: *const ffi::IChannelHierarchy) -> Self {
+        Self {
+            inner,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Gets the index (file position) of the block.
+    pub fn get_index(&self) -> u64 {
+        unsafe { ffi::ChannelHierarchyGetIndex(self.inner) }
+    }
+
+    /// Gets the name of the block.
+    pub fn get_name(&self) -> String {
+        unsafe {
+            let mut len = ffi::ChannelHierarchyGetName(self.inner, std::ptr::null_mut(), 0);
+            if len == 0 {
+                return String::new();
+            }
+            len += 1; // For null terminator
+            let mut buf = vec![0 as c_char; len];
+            ffi::ChannelHierarchyGetName(self.inner, buf.as_mut_ptr(), len);
+            CStr::from_ptr(buf.as_ptr()).to_string_lossy().into_owned()
+        }
+    }
+
+    /// Gets the hierarchy type of the block.
+    pub fn get_type(&self) -> u8 {
+        unsafe { ffi::ChannelHierarchyGetType(self.inner) }
+    }
+
+    /// Gets the hierarchy type of the block as [`ChType`].
+    pub fn get_ch_type(&self) -> Result<ChType> {
+        let value = self.get_type();
+        ChType::try_from(value).map_err(|_| MdfError::InvalidEnumValue("ChType", value))
+    }
+
+    /// Gets the direct child blocks.
+    pub fn get_children(&self) -> Vec<ChannelHierarchyRef<'a>> {
+        const MAX_CHILDREN: usize = 1000;
+        let mut children: Vec<*const ffi::IChannelHierarchy> = vec![std::ptr::null(); MAX_CHILDREN];
+        let count = unsafe {
+            ffi::ChannelHierarchyGetChildren(self.inner, children.as_mut_ptr(), MAX_CHILDREN)
+        };
+
+        children.truncate(count);
+        children
+            .into_iter()
+            .filter(|&ptr| !ptr.is_null())
+            .map(ChannelHierarchyRef::new)
+            .collect()
+    }
+
+    /// Iterates over all blocks below this one, depth first with each parent
+    /// before its children. The block itself is not included.
+    pub fn iter_descendants(&self) -> impl Iterator<Item = ChannelHierarchyRef<'a>> {
+        let mut stack: Vec<ChannelHierarchyRef<'a>> = self.get_children();
+        stack.reverse();
+        std::iter::from_fn(move || {
+            let next = stack.pop()?;
+            stack.extend(next.get_children().into_iter().rev());
+            Some(next)
+        })
+    }
+
+    /// Gets the number of elements referenced by the block.
+    pub fn get_element_count(&self) -> usize {
+        unsafe { ffi::ChannelHierarchyGetElementCount(self.inner) }
+    }
+
+    /// Gets the data group, channel group and channel of an element.
+    ///
+    /// Parts the element doesn't reference are `None`.
+    pub fn get_element(
+        &self,
+        index: usize,
+    ) -> Option<(
+        Option<DataGroupRef>,
+        Option<ChannelGroupRef>,
+        Option<ChannelRef<'a>>,
+    )> {
+        let mut data_group: *const ffi::IDataGroup = std::ptr::null();
+        let mut channel_group: *const ffi::IChannelGroup = std::ptr::null();
+        let mut channel: *const ffi::IChannel = std::ptr::null();
+        let found = unsafe {
+            ffi::ChannelHierarchyGetElement(
+                self.inner,
+                index,
+                &mut data_group,
+                &mut channel_group,
+                &mut channel,
+            )
+        };
+        if !found {
+            return None;
+        }
+        Some((
+            (!data_group.is_null()).then(|| DataGroupRef::new(data_group)),
+            (!channel_group.is_null()).then(|| ChannelGroupRef::new(channel_group)),
+            (!channel.is_null()).then(|| ChannelRef::new(channel)),
+        ))
+    }
+
+    /// Gets the channels referenced by the elements of the block.
+    ///
+    /// Elements that reference only a data group or channel group are skipped.
+    pub fn get_channels(&self) -> Vec<ChannelRef<'a>> {
+        (0..self.get_element_count())
+            .filter_map(|index| self.get_element(index))
+            .filter_map(|(_, _, channel)| channel)
+            .collect()
+    }
+}
+
+/// Represents a mutable reference to a channel hierarchy block.
+#[derive(Debug)]
+pub struct ChannelHierarchy<'a> {
+    pub(crate) inner: *mut ffi::IChannelHierarchy,
+    inner_ref: ChannelHierarchyRef<'a>,
+}
+
+impl<'a> ChannelHierarchy<'a> {
+    pub(crate) fn new(inner: *mut ffi::IChannelHierarchy) -> Self {
+        Self {
+            inner,
+            inner_ref: ChannelHierarchyRef::new(inner),
+        }
+    }
+
+    /// Sets the name of the block.
+    pub fn set_name(&mut self, name: &str) -> Result<()> {
+        let c_name = CString::new(name)?;
+        unsafe {
+            ffi::ChannelHierarchySetName(self.inner, c_name.as_ptr());
+        }
+        Ok(())
+    }
+
+    /// Sets the hierarchy type of the block.
+    pub fn set_type(&mut self, ch_type: ChType) {
+        unsafe { ffi::ChannelHierarchySetType(self.inner, ch_type as u8) }
+    }
+
+    /// Creates a child block.
+    pub fn create_child(&mut self) -> Option<ChannelHierarchy<'_>> {
+        unsafe {
+            let child = ffi::ChannelHierarchyCreateChild(self.inner);
+            if child.is_null() {
+                None
+            } else {
+                Some(ChannelHierarchy::new(child))
+            }
+        }
+    }
+
+    /// Adds an element referencing `channel` of `channel_group` in
+    /// `data_group`.
+    pub fn add_channel(
+        &mut self,
+        data_group: &DataGroupRef,
+        channel_group: &ChannelGroupRef,
+        channel: &ChannelRef,
+    ) {
+        unsafe {
+            ffi::ChannelHierarchyAddElement(
+                self.inner,
+                data_group.inner,
+                channel_group.inner,
+                channel.inner,
+            )
+        }
+    }
+}
+
+impl<'a> Deref for ChannelHierarchy<'a> {
+    type Target = ChannelHierarchyRef<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner_ref
+    }
+}
@@ -3,9 +3,75 @@
 //! A channel observer is used to read the data from a channel. It provides
 //! access to the raw channel values as well as the converted engineering values.
 
+use crate::channel::ChannelRef;
 use crate::error::Result;
+use crate::util::read_cstring;
 use mdflib_sys as ffi;
 use std::marker::PhantomData;
+use std::sync::Arc;
+
+/// Owned copy of the samples held by a channel observer.
+///
+/// Unlike the observer, the buffer does not borrow the reader's data group so it
+/// can be kept after the reader and file have been dropped.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SampleBuffer {
+    /// Master (time) value of each sample, `None` if the master channel
+    /// wasn't observed
+    pub times: Option<Vec<f64>>,
+    /// Engineering value of each sample
+    pub values: Vec<f64>,
+    /// Whether each sample is valid
+    pub valid: Vec<bool>,
+}
+
+impl SampleBuffer {
+    /// Gets the number of samples in the buffer.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Checks if the buffer holds no samples.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+/// Samples of a channel in the native type of its data type.
+///
+/// Returned by [`ChannelObserverRef::read_typed`]. Unlike the `f64` getters, the
+/// integer variants hold the full 64-bit values.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypedSamples {
+    /// Unsigned integer, CANopen date and CANopen time channels
+    U64(Vec<u64>),
+    /// Signed integer channels
+    I64(Vec<i64>),
+    /// Floating point and complex channels
+    F64(Vec<f64>),
+    /// Text channels
+    String(Vec<String>),
+    /// Byte array and MIME channels
+    Bytes(Vec<Vec<u8>>),
+}
+
+impl TypedSamples {
+    /// Gets the number of samples.
+    pub fn len(&self) -> usize {
+        match self {
+            TypedSamples::U64(values) => values.len(),
+            TypedSamples::I64(values) => values.len(),
+            TypedSamples::F64(values) => values.len(),
+            TypedSamples::String(values) => values.len(),
+            TypedSamples::Bytes(values) => values.len(),
+        }
+    }
+
+    /// Checks if there are no samples.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
 
 /// Represents an immutable reference to a channel observer in an MDF file.
 ///
@@ -14,6 +80,10 @@ use std::marker::PhantomData;
 #[derive(Debug, Clone, Copy)]
 pub struct ChannelObserverRef<'a> {
     pub(crate) inner: *const ffi::IChannelObserver,
+    /// Observer of the channel group's master channel, null if not observed
+    pub(crate) master: *const ffi::IChannelObserver,
+    /// Observer keeping the channel's record bytes, null if not kept
+    pub(crate) records: *const ffi::RecordObserver,
     _marker: PhantomData<&'a ()>,
 }
 
@@ -32,6 +102,8 @@ impl<'a> ChannelObserverRef<'a> {
     pub(crate) fn new(inner: *const ffi::IChannelObserver) -> Self {
         Self {
             inner,
+            master: std::ptr::null(),
+            records: std::ptr::null(),
             _marker: PhantomData,
         }
     }
@@ -75,6 +147,23 @@ impl<'a> ChannelObserverRef<'a> {
         }
     }
 
+    /// Gets a string channel value for a specific sample.
+    ///
+    /// The value ends at the first NUL, so the zero padding of fixed and
+    /// maximum length storage is not included.
+    ///
+    /// # Returns
+    /// Returns `Some(value)` if the sample is valid, `None` otherwise.
+    pub fn get_value_as_string(&self, sample: usize) -> Option<String> {
+        if !self.is_valid(sample) {
+            return None;
+        }
+        let value = read_cstring(64, |buf, len| unsafe {
+            ffi::ChannelObserverGetChannelValueString(self.inner, sample, buf, len)
+        });
+        Some(value)
+    }
+
     /// Checks if a specific sample is valid.
     ///
     /// # Arguments
@@ -86,6 +175,21 @@ impl<'a> ChannelObserverRef<'a> {
         unsafe { ffi::ChannelObserverGetValid(self.inner, sample) }
     }
 
+    /// Gets the validity of all samples as a packed bitset.
+    ///
+    /// Bit `sample % 64` of word `sample / 64` is set if the sample is valid.
+    /// Bits past the last sample are zero.
+    pub fn validity_bitset(&self) -> Vec<u64> {
+        let nof_samples = self.get_nof_samples();
+        let mut bits = vec![0u64; nof_samples.div_ceil(64)];
+        for sample in 0..nof_samples {
+            if unsafe { ffi::ChannelObserverGetValid(self.inner, sample) } {
+                bits[sample / 64] |= 1 << (sample % 64);
+            }
+        }
+        bits
+    }
+
     /// Gets all channel values (raw, unscaled) for all samples.
     ///
     /// # Returns
@@ -111,26 +215,320 @@ impl<'a> ChannelObserverRef<'a> {
         }
         values
     }
+
+    /// Gets the name of the observed channel.
+    pub fn get_name(&self) -> String {
+        read_cstring(64, |buf, len| unsafe {
+            ffi::ChannelObserverGetName(self.inner, buf, len)
+        })
+    }
+
+    /// Gets the observed channel.
+    pub fn get_channel(&self) -> Option<ChannelRef<'a>> {
+        unsafe {
+            let channel = ffi::ChannelObserverGetChannel(self.inner);
+            if channel.is_null() {
+                None
+            } else {
+                Some(ChannelRef::new(channel))
+            }
+        }
+    }
+
+    /// Gets the unit of the engineering values.
+    ///
+    /// A unit set on the channel's conversion overrules the channel unit, so
+    /// it is returned if present, otherwise the channel unit.
+    pub fn get_eng_unit(&self) -> String {
+        let Some(channel) = self.get_channel() else {
+            return String::new();
+        };
+        channel
+            .get_channel_conversion()
+            .map(|conversion| conversion.get_unit())
+            .filter(|unit| !unit.is_empty())
+            .unwrap_or_else(|| channel.get_unit())
+    }
+
+    /// Gets the data type of the observed channel.
+    pub fn get_data_type(&self) -> u8 {
+        unsafe { ffi::ChannelObserverGetDataType(self.inner) }
+    }
+
+    /// Reads all channel values in the native type of the channel's data type.
+    ///
+    /// The values are the raw channel values, which equal the engineering
+    /// values unless the channel has a conversion; use
+    /// [`ChannelObserverRef::get_all_eng_values`] for scaled values. Invalid
+    /// samples are included with the value mdflib returns for them, check
+    /// [`ChannelObserverRef::is_valid`] if needed.
+    pub fn read_typed(&self) -> TypedSamples {
+        let nof_samples = self.get_nof_samples();
+        let data_type = ffi::ChannelDataType::try_from(self.get_data_type())
+            .unwrap_or(ffi::ChannelDataType::FloatLe);
+
+        match data_type {
+            ffi::ChannelDataType::UnsignedIntegerLe
+            | ffi::ChannelDataType::UnsignedIntegerBe
+            | ffi::ChannelDataType::CanOpenDate
+            | ffi::ChannelDataType::CanOpenTime => TypedSamples::U64(
+                (0..nof_samples)
+                    .map(|sample| {
+                        let mut value = 0u64;
+                        unsafe {
+                            ffi::ChannelObserverGetChannelValueUnsigned(
+                                self.inner, sample, &mut value,
+                            )
+                        };
+                        value
+                    })
+                    .collect(),
+            ),
+            ffi::ChannelDataType::SignedIntegerLe | ffi::ChannelDataType::SignedIntegerBe => {
+                TypedSamples::I64(
+                    (0..nof_samples)
+                        .map(|sample| {
+                            let mut value = 0i64;
+                            unsafe {
+                                ffi::ChannelObserverGetChannelValueSigned(
+                                    self.inner, sample, &mut value,
+                                )
+                            };
+                            value
+                        })
+                        .collect(),
+                )
+            }
+            ffi::ChannelDataType::StringAscii
+            | ffi::ChannelDataType::StringUTF8
+            | ffi::ChannelDataType::StringUTF16Le
+            | ffi::ChannelDataType::StringUTF16Be => TypedSamples::String(
+                (0..nof_samples)
+                    .map(|sample| {
+                        read_cstring(64, |buf, len| unsafe {
+                            ffi::ChannelObserverGetChannelValueString(self.inner, sample, buf, len)
+                        })
+                    })
+                    .collect(),
+            ),
+            ffi::ChannelDataType::ByteArray
+            | ffi::ChannelDataType::MimeSample
+            | ffi::ChannelDataType::MimeStream => TypedSamples::Bytes(
+                (0..nof_samples)
+                    .map(|sample| self.read_bytes(sample))
+                    .collect(),
+            ),
+            _ => TypedSamples::F64(
+                (0..nof_samples)
+                    .map(|sample| self.get_channel_value(sample).unwrap_or_default())
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Gets the bytes of a specific sample as stored in its record.
+    ///
+    /// These are the bytes the channel occupies in the record read from file,
+    /// from its byte offset up to the byte holding its last bit, before any
+    /// conversion. Variable length values hold the offset of the data instead.
+    /// Only observers created by [`create_raw_channel_observer`] keep the
+    /// records, so this returns `None` for other observers and for a sample
+    /// out of range.
+    pub fn get_raw_bytes(&self, sample: usize) -> Option<Vec<u8>> {
+        if self.records.is_null()
+            || sample >= unsafe { ffi::RecordObserverGetNofRecords(self.records) }
+        {
+            return None;
+        }
+        unsafe {
+            let len = ffi::RecordObserverGetBytes(self.records, sample, std::ptr::null_mut(), 0);
+            let mut buf = vec![0u8; len];
+            ffi::RecordObserverGetBytes(self.records, sample, buf.as_mut_ptr(), len);
+            Some(buf)
+        }
+    }
+
+    /// Reads a byte array value of a sample
+    fn read_bytes(&self, sample: usize) -> Vec<u8> {
+        unsafe {
+            let len = ffi::ChannelObserverGetChannelValueBytes(
+                self.inner,
+                sample,
+                std::ptr::null_mut(),
+                0,
+            );
+            let mut buf = vec![0u8; len];
+            ffi::ChannelObserverGetChannelValueBytes(self.inner, sample, buf.as_mut_ptr(), len);
+            buf
+        }
+    }
+
+    /// Copies all samples into an owned [`SampleBuffer`].
+    ///
+    /// Times are taken from the channel group's master channel if the observer
+    /// was created with [`create_channel_group_observers`] or
+    /// [`create_timed_channel_observer`]. Otherwise, or if the group has no
+    /// master channel, `times` is `None`. Invalid samples keep their
+    /// (unreliable) value and are flagged in `valid`.
+    pub fn take_samples(&self) -> SampleBuffer {
+        let nof_samples = self.get_nof_samples();
+        let mut times = (!self.master.is_null()).then(|| Vec::with_capacity(nof_samples));
+        let mut buffer = SampleBuffer {
+            times: None,
+            values: Vec::with_capacity(nof_samples),
+            valid: Vec::with_capacity(nof_samples),
+        };
+
+        for sample in 0..nof_samples {
+            let mut value = 0.0;
+            let valid = unsafe { ffi::ChannelObserverGetEngValue(self.inner, sample, &mut value) };
+
+            if let Some(times) = times.as_mut() {
+                let mut time = 0.0;
+                unsafe { ffi::ChannelObserverGetEngValue(self.master, sample, &mut time) };
+                times.push(time);
+            }
+
+            buffer.values.push(value);
+            buffer.valid.push(valid);
+        }
+
+        buffer.times = times;
+        buffer
+    }
+
+    /// Gets the master (time) values of the first and last sample.
+    ///
+    /// Samples with an invalid master value are skipped. Returns `None` if
+    /// there are no samples with a valid master value or if the master
+    /// channel isn't observed, see [`create_timed_channel_observer`].
+    pub fn time_range(&self) -> Option<(f64, f64)> {
+        if self.master.is_null() {
+            return None;
+        }
+
+        let nof_samples = self.get_nof_samples();
+
+        let time = |sample: usize| {
+            let mut time = 0.0;
+            unsafe { ffi::ChannelObserverGetEngValue(self.master, sample, &mut time) }
+                .then_some(time)
+        };
+        let first = (0..nof_samples).find_map(time)?;
+        let last = (0..nof_samples).rev().find_map(time)?;
+        Some((first, last))
+    }
+
+    /// Gets `(time, value)` pairs of the valid samples in one pass.
+    ///
+    /// The time is the master channel value as in
+    /// [`ChannelObserverRef::take_samples`] and the value is the engineering
+    /// value. Invalid samples are skipped. Returns `None` if the master channel
+    /// isn't observed, see [`create_timed_channel_observer`].
+    pub fn time_value_pairs(&self) -> Option<Vec<(f64, f64)>> {
+        if self.master.is_null() {
+            return None;
+        }
+
+        let nof_samples = self.get_nof_samples();
+        let mut pairs = Vec::with_capacity(nof_samples);
+        for sample in 0..nof_samples {
+            let mut value = 0.0;
+            if !unsafe { ffi::ChannelObserverGetEngValue(self.inner, sample, &mut value) } {
+                continue;
+            }
+
+            let mut time = 0.0;
+            unsafe { ffi::ChannelObserverGetEngValue(self.master, sample, &mut time) };
+            pairs.push((time, value));
+        }
+
+        Some(pairs)
+    }
+
+    /// Gets `(time, value)` pairs of the valid samples sorted by time.
+    ///
+    /// Loggers may save samples out of order and mdflib stores them as saved,
+    /// so [`ChannelObserverRef::time_value_pairs`] isn't necessarily
+    /// monotonic. This copies the pairs and sorts them ascending by master
+    /// value, keeping the stored order of samples with the same time. Returns
+    /// `None` if the master channel isn't observed.
+    pub fn sorted_by_time(&self) -> Option<Vec<(f64, f64)>> {
+        let mut pairs = self.time_value_pairs()?;
+        pairs.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Some(pairs)
+    }
+}
+
+/// Owns an mdflib channel observer, shared when it also provides the times of
+/// the other observers of its channel group
+#[derive(Debug)]
+struct ObserverHandle(*mut ffi::IChannelObserver);
+
+impl Drop for ObserverHandle {
+    fn drop(&mut self) {
+        if !self.0.is_null() {
+            unsafe { ffi::ChannelObserverUnInit(self.0) }
+        }
+    }
+}
+
+// Safety: the handle only uninitializes the observer once the last owner drops it
+unsafe impl Send for ObserverHandle {}
+unsafe impl Sync for ObserverHandle {}
+
+/// Owns the record observer of a channel
+#[derive(Debug)]
+struct RecordHandle(*mut ffi::RecordObserver);
+
+impl Drop for RecordHandle {
+    fn drop(&mut self) {
+        if !self.0.is_null() {
+            unsafe { ffi::RecordObserverUnInit(self.0) }
+        }
+    }
 }
 
 /// Represents a mutable channel observer in an MDF file.
 ///
 /// This wrapper provides ownership of the underlying IChannelObserver and automatically
-/// cleans up resources when dropped.
+/// cleans up resources when dropped. An observer of the group's master channel
+/// used for the times is shared and kept alive until all observers using it
+/// are dropped.
 #[derive(Debug)]
 pub struct ChannelObserver<'a> {
-    pub(crate) inner: *mut ffi::IChannelObserver,
-    _marker: PhantomData<&'a ()>,
+    _handle: Arc<ObserverHandle>,
+    _master: Option<Arc<ObserverHandle>>,
+    _records: Option<RecordHandle>,
+    inner_ref: ChannelObserverRef<'a>,
 }
 
 impl<'a> ChannelObserver<'a> {
     #[allow(dead_code)]
     pub(crate) fn new(inner: *mut ffi::IChannelObserver) -> Self {
+        Self::with_handles(Arc::new(ObserverHandle(inner)), None)
+    }
+
+    fn with_handles(handle: Arc<ObserverHandle>, master: Option<Arc<ObserverHandle>>) -> Self {
+        let inner = handle.0;
         Self {
-            inner,
-            _marker: PhantomData,
+            inner_ref: ChannelObserverRef {
+                inner,
+                master: master.as_ref().map_or(std::ptr::null(), |master| master.0),
+                records: std::ptr::null(),
+                _marker: PhantomData,
+            },
+            _handle: handle,
+            _master: master,
+            _records: None,
         }
     }
+
+    fn with_records(mut self, records: RecordHandle) -> Self {
+        self.inner_ref.records = records.0;
+        self._records = Some(records);
+        self
+    }
 }
 
 // Implement Deref to allow using ChannelObserver as ChannelObserverRef
@@ -138,18 +536,7 @@ impl<'a> std::ops::Deref for ChannelObserver<'a> {
     type Target = ChannelObserverRef<'a>;
 
     fn deref(&self) -> &Self::Target {
-        unsafe { &*(self as *const ChannelObserver as *const ChannelObserverRef) }
-    }
-}
-
-// Implement Drop to clean up the observer when it goes out of scope
-impl<'a> Drop for ChannelObserver<'a> {
-    fn drop(&mut self) {
-        if !self.inner.is_null() {
-            unsafe {
-                ffi::ChannelObserverUnInit(self.inner);
-            }
-        }
+        &self.inner_ref
     }
 }
 
@@ -157,6 +544,42 @@ impl<'a> Drop for ChannelObserver<'a> {
 unsafe impl<'a> Send for ChannelObserver<'a> {}
 unsafe impl<'a> Sync for ChannelObserver<'a> {}
 
+/// Finds the master channel of a channel group
+///
+/// # Safety
+///
+/// `channel_group` must be a valid pointer.
+unsafe fn find_master(channel_group: *const ffi::IChannelGroup) -> *const ffi::IChannel {
+    let count = unsafe { ffi::ChannelGroupGetChannelCount(channel_group) };
+    (0..count)
+        .map(|index| unsafe { ffi::ChannelGroupGetChannelByIndex(channel_group, index) })
+        .find(|&channel| {
+            !channel.is_null() && {
+                let channel_type = unsafe { ffi::ChannelGetType(channel) };
+                channel_type == ffi::ChannelType::Master as u8
+                    || channel_type == ffi::ChannelType::VirtualMaster as u8
+            }
+        })
+        .unwrap_or(std::ptr::null())
+}
+
+/// Creates the mdflib observer of a channel
+///
+/// # Safety
+///
+/// As for [`create_channel_observer`].
+unsafe fn create_handle(
+    data_group: *const ffi::IDataGroup,
+    channel_group: *const ffi::IChannelGroup,
+    channel: *const ffi::IChannel,
+) -> Result<Arc<ObserverHandle>> {
+    let observer = unsafe { ffi::CreateChannelObserver(data_group, channel_group, channel) };
+    if observer.is_null() {
+        return Err(crate::error::MdfError::NullPointer);
+    }
+    Ok(Arc::new(ObserverHandle(observer)))
+}
+
 /// Creates a channel observer for a specific channel in a data group.
 ///
 /// This function creates a channel observer that can be used to read sample data
@@ -208,11 +631,90 @@ pub unsafe fn create_channel_observer<'a>(
     channel_group: *const ffi::IChannelGroup,
     channel: *const ffi::IChannel,
 ) -> Result<ChannelObserver<'a>> {
-    let observer = unsafe { ffi::CreateChannelObserver(data_group, channel_group, channel) };
+    let handle = unsafe { create_handle(data_group, channel_group, channel)? };
+    Ok(ChannelObserver::with_handles(handle, None))
+}
 
-    if observer.is_null() {
+/// Creates a channel observer that also observes the group's master channel.
+///
+/// The time based methods such as [`ChannelObserverRef::time_value_pairs`]
+/// then use the master values as times. This costs a second observer for a
+/// channel other than the master, so when observing several channels of a
+/// group prefer [`create_channel_group_observers`], which shares one.
+///
+/// # Safety
+///
+/// As for [`create_channel_observer`].
+pub unsafe fn create_timed_channel_observer<'a>(
+    data_group: *const ffi::IDataGroup,
+    channel_group: *const ffi::IChannelGroup,
+    channel: *const ffi::IChannel,
+) -> Result<ChannelObserver<'a>> {
+    let handle = unsafe { create_handle(data_group, channel_group, channel)? };
+    let master = unsafe { find_master(channel_group) };
+    let master = if master.is_null() {
+        None
+    } else if master == channel {
+        Some(handle.clone())
+    } else {
+        Some(unsafe { create_handle(data_group, channel_group, master)? })
+    };
+    Ok(ChannelObserver::with_handles(handle, master))
+}
+
+/// Creates a channel observer that also keeps the channel's record bytes.
+///
+/// [`ChannelObserverRef::get_raw_bytes`] then returns the bytes of each
+/// sample as stored in the file. The bytes are held in memory in addition to
+/// the values, so only use this for channels whose raw data is needed.
+///
+/// # Safety
+///
+/// As for [`create_channel_observer`].
+pub unsafe fn create_raw_channel_observer<'a>(
+    data_group: *const ffi::IDataGroup,
+    channel_group: *const ffi::IChannelGroup,
+    channel: *const ffi::IChannel,
+) -> Result<ChannelObserver<'a>> {
+    let handle = unsafe { create_handle(data_group, channel_group, channel)? };
+    let records = unsafe { ffi::CreateRecordObserver(data_group, channel_group, channel) };
+    if records.is_null() {
         return Err(crate::error::MdfError::NullPointer);
     }
+    Ok(ChannelObserver::with_handles(handle, None).with_records(RecordHandle(records)))
+}
+
+/// Creates an observer for every channel of a channel group.
+///
+/// The observers are returned in channel order and all use the observer of
+/// the group's master channel for their times, so no extra observer is
+/// created. It is kept alive until the last observer using it is dropped.
+///
+/// # Safety
+///
+/// As for [`create_channel_observer`].
+pub unsafe fn create_channel_group_observers<'a>(
+    data_group: *const ffi::IDataGroup,
+    channel_group: *const ffi::IChannelGroup,
+) -> Result<Vec<ChannelObserver<'a>>> {
+    let master = unsafe { find_master(channel_group) };
+    let count = unsafe { ffi::ChannelGroupGetChannelCount(channel_group) };
+    let mut handles = Vec::with_capacity(count);
+    let mut master_handle = None;
+    for index in 0..count {
+        let channel = unsafe { ffi::ChannelGroupGetChannelByIndex(channel_group, index) };
+        if channel.is_null() {
+            continue;
+        }
+        let handle = unsafe { create_handle(data_group, channel_group, channel)? };
+        if channel == master {
+            master_handle = Some(handle.clone());
+        }
+        handles.push(handle);
+    }
 
-    Ok(ChannelObserver::new(observer))
+    Ok(handles
+        .into_iter()
+        .map(|handle| ChannelObserver::with_handles(handle, master_handle.clone()))
+        .collect())
 }
@@ -9,7 +9,12 @@ use std::ffi::{CStr, CString};
 use std::ops::Deref;
 use std::os::raw::c_char;
 
-use crate::channelgroup::ChannelGroup;
+use crate::channelgroup::{ChannelGroup, ChannelGroupRef};
+use crate::channelobserver::create_channel_observer;
+use crate::error::{MdfError, Result};
+use crate::metadata::{MetaData, MetaDataRef};
+use crate::reader::MdfReader;
+use mdflib_sys::BusType;
 
 /// Represents an immutable reference to a data group in an MDF file.
 #[derive(Debug, Clone, Copy)]
@@ -75,6 +80,50 @@ impl DataGroupRef {
         }
     }
 
+    /// Checks if the data of the data group has been read.
+    ///
+    /// Observers only hold samples after [`crate::MdfReader::read_data`] has
+    /// been called for their data group. The flag is reset by
+    /// [`DataGroup::clear_data`].
+    pub fn is_data_read(&self) -> bool {
+        unsafe { ffi::DataGroupIsRead(self.inner) }
+    }
+
+    /// Gets the metadata of the data group.
+    pub fn get_metadata(&self) -> Option<MetaDataRef<'_>> {
+        unsafe {
+            let metadata = ffi::DataGroupGetMetaData(self.inner);
+            if metadata.is_null() {
+                None
+            } else {
+                Some(MetaDataRef::new(metadata))
+            }
+        }
+    }
+
+    /// Gets the comment of the data group.
+    ///
+    /// The comment is taken from the `TX` element of the data group's metadata
+    /// and falls back to the plain description if there is no metadata.
+    pub fn get_comment(&self) -> String {
+        self.get_metadata()
+            .and_then(|md| md.get_property_as_string("TX").ok())
+            .filter(|comment| !comment.is_empty())
+            .unwrap_or_else(|| self.get_description())
+    }
+
+    /// Gets the channel groups that have a bus type set, e.g. the CAN groups of
+    /// a bus logger file, together with their [`BusType`].
+    pub fn bus_channel_groups(&self) -> Vec<(ChannelGroupRef, BusType)> {
+        self.get_channel_groups()
+            .iter()
+            .filter_map(|cg| match BusType::try_from(cg.get_bus_type()) {
+                Ok(BusType::None) | Err(_) => None,
+                Ok(bus_type) => Some((**cg, bus_type)),
+            })
+            .collect()
+    }
+
     pub fn get_channel_group(&self, name: &str) -> Option<ChannelGroup> {
         let c_name = CString::new(name).unwrap();
         unsafe {
@@ -125,6 +174,85 @@ impl DataGroup {
         }
     }
 
+    /// Creates a channel group with the structure of `src`.
+    ///
+    /// The name, description and source information of the group and all its
+    /// channels are copied with [`Channel::copy_from`](crate::Channel::copy_from),
+    /// in the same order. No samples are copied. `src` may belong to another
+    /// file, for example to write a new file with the layout of an existing one.
+    pub fn copy_channel_group(&mut self, src: &ChannelGroupRef) -> Result<ChannelGroup> {
+        let mut group = self.create_channel_group().ok_or(MdfError::NullPointer)?;
+        group.set_name(&src.get_name());
+        group.set_description(&src.get_description());
+        if let Some(src_si) = src.get_source_information() {
+            group
+                .create_source_information()
+                .ok_or(MdfError::NullPointer)?
+                .copy_from(&src_si)?;
+        }
+        for src_cn in src.get_channels() {
+            group
+                .create_channel()
+                .ok_or(MdfError::NullPointer)?
+                .copy_from(&src_cn)?;
+        }
+        Ok(group)
+    }
+
+    /// Creates metadata for the data group.
+    pub fn create_metadata(&mut self) -> Option<MetaData<'_>> {
+        unsafe {
+            let metadata = ffi::DataGroupCreateMetaData(self.inner);
+            if metadata.is_null() {
+                None
+            } else {
+                Some(MetaData::new(metadata))
+            }
+        }
+    }
+
+    /// Sets the comment of the data group.
+    ///
+    /// The comment is stored as the `TX` element of the data group's metadata,
+    /// which is created if needed.
+    pub fn set_comment(&mut self, comment: &str) -> Result<()> {
+        let mut metadata = self.create_metadata().ok_or(MdfError::NullPointer)?;
+        metadata.set_property_as_string("TX", comment)
+    }
+
+    /// Reads the master (time) values of a channel group in this data group.
+    ///
+    /// Only the master channel of `cg` is observed, but all records of the
+    /// data group are still read. The data is cleared again afterwards, which
+    /// also clears the samples of other observers of the data group. Returns
+    /// [`MdfError::NotFound`] if the group has no master channel. The file
+    /// structure must have been read first with
+    /// [`MdfReader::read_everything_but_data`].
+    pub fn read_master_values(
+        &mut self,
+        reader: &MdfReader,
+        cg: &ChannelGroupRef,
+    ) -> Result<Vec<f64>> {
+        let master = cg
+            .get_channels()
+            .into_iter()
+            .find(|cn| {
+                let channel_type = cn.get_type();
+                channel_type == ffi::ChannelType::Master as u8
+                    || channel_type == ffi::ChannelType::VirtualMaster as u8
+            })
+            .ok_or_else(|| MdfError::NotFound(format!("master channel of {}", cg.get_name())))?;
+
+        // Safety: all pointers come from the reader's file which outlives the observer
+        let observer =
+            unsafe { create_channel_observer(self.as_ptr(), cg.as_ptr(), master.as_ptr())? };
+        let read = reader.read_data(self);
+        let values = observer.take_samples().values;
+        self.clear_data();
+        read?;
+        Ok(values)
+    }
+
     pub fn clear_data(&mut self) {
         unsafe { ffi::DataGroupClearData(self.inner) }
     }
@@ -0,0 +1,66 @@
+//! Owned in-memory copy of a whole MDF file
+//!
+//! [`MdfDocument`] holds the header, structure and samples of a file without
+//! any ties to mdflib, so it can be kept after the reader has been dropped.
+//! It is returned by [`crate::MdfReader::read_all`]; all samples are held in
+//! memory.
+
+use crate::catalog::{ChannelInfo, HeaderInfo};
+use crate::channelobserver::SampleBuffer;
+
+/// Owned channel with its samples.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ChannelData {
+    /// Channel description
+    pub info: ChannelInfo,
+    /// Samples of the channel
+    pub samples: SampleBuffer,
+}
+
+/// Owned channel group with its channels.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ChannelGroupData {
+    /// Channel group name
+    pub name: String,
+    /// Channel group description
+    pub description: String,
+    /// Channels in file order
+    pub channels: Vec<ChannelData>,
+}
+
+impl ChannelGroupData {
+    /// Gets a channel by name.
+    pub fn channel(&self, name: &str) -> Option<&ChannelData> {
+        self.channels
+            .iter()
+            .find(|channel| channel.info.name == name)
+    }
+}
+
+/// Owned data group with its channel groups.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DataGroupData {
+    /// Data group description
+    pub description: String,
+    /// Channel groups in file order
+    pub channel_groups: Vec<ChannelGroupData>,
+}
+
+/// Owned copy of an MDF file.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MdfDocument {
+    /// File header
+    pub header: HeaderInfo,
+    /// Data groups in file order
+    pub data_groups: Vec<DataGroupData>,
+}
+
+impl MdfDocument {
+    /// Gets the first channel with `name` in any channel group.
+    pub fn channel(&self, name: &str) -> Option<&ChannelData> {
+        self.data_groups
+            .iter()
+            .flat_map(|dg| &dg.channel_groups)
+            .find_map(|cg| cg.channel(name))
+    }
+}
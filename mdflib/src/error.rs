@@ -9,6 +9,10 @@ pub enum MdfError {
     #[error("Failed to open file: {0}")]
     FileOpen(String),
 
+    /// Path that can't be passed to mdflib as UTF-8
+    #[error("Invalid path: {}", .0.display())]
+    InvalidPath(std::path::PathBuf),
+
     /// Error reading the header
     #[error("Failed to read header")]
     HeaderRead,
@@ -37,10 +41,18 @@ pub enum MdfError {
     #[error("Null pointer encountered")]
     NullPointer,
 
+    /// Named item not found
+    #[error("Not found: {0}")]
+    NotFound(String),
+
     /// Index out of bounds
     #[error("Index out of bounds: {0}")]
     IndexOutOfBounds(usize),
 
+    /// Operation not possible in the current measurement state of the writer
+    #[error("Invalid writer state: {0}")]
+    InvalidWriterState(&'static str),
+
     /// Invalid channel type
     #[error("Invalid channel type: {0}")]
     InvalidChannelType(u8),
@@ -49,6 +61,15 @@ pub enum MdfError {
     #[error("Invalid data type: {0}")]
     InvalidDataType(u8),
 
+    /// Raw value does not match any variant of the enum
+    #[error("Invalid {0} value: {1}")]
+    InvalidEnumValue(&'static str, u8),
+
+    /// Channel bit count, bit offset and data bytes, a record layout or array
+    /// axis values don't fit together
+    #[error("Invalid channel layout: {0}")]
+    InvalidLayout(String),
+
     /// Buffer too small
     #[error("Buffer too small: needed {needed}, got {actual}")]
     BufferTooSmall { needed: usize, actual: usize },
@@ -203,6 +203,15 @@ impl<'a> ETag<'a> {
         }
     }
 
+    /// Takes ownership of an ETag allocated by the C wrapper, which is freed on drop.
+    pub(crate) fn from_owned_raw(inner: *mut ffi::ETag) -> Self {
+        Self {
+            inner,
+            inner_ref: ETagRef::new(inner),
+            owned: true,
+        }
+    }
+
     /// Sets the name of the ETag.
     pub fn set_name(&mut self, name: &str) -> Result<()> {
         let c_name = CString::new(name)?;
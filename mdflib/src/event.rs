@@ -4,9 +4,10 @@
 //! point in time. This module provides access to the event's metadata, such as
 //! its name, description, and time.
 
-use crate::error::Result;
+use crate::error::{MdfError, Result};
 use crate::metadata::MetaDataRef;
 use mdflib_sys as ffi;
+use mdflib_sys::{EventCause, EventType, RangeType, SyncType};
 use std::ffi::{CStr, CString};
 use std::marker::PhantomData;
 use std::ops::Deref;
@@ -117,6 +118,30 @@ impl<'a> EventRef<'a> {
         unsafe { ffi::EventGetCause(self.inner) }
     }
 
+    /// Gets the type of the event as [`EventType`].
+    pub fn get_event_type(&self) -> Result<EventType> {
+        let value = self.get_type();
+        EventType::try_from(value).map_err(|_| MdfError::InvalidEnumValue("EventType", value))
+    }
+
+    /// Gets the sync type of the event as [`SyncType`].
+    pub fn get_sync_type(&self) -> Result<SyncType> {
+        let value = self.get_sync();
+        SyncType::try_from(value).map_err(|_| MdfError::InvalidEnumValue("SyncType", value))
+    }
+
+    /// Gets the range type of the event as [`RangeType`].
+    pub fn get_range_type(&self) -> Result<RangeType> {
+        let value = self.get_range();
+        RangeType::try_from(value).map_err(|_| MdfError::InvalidEnumValue("RangeType", value))
+    }
+
+    /// Gets the cause of the event as [`EventCause`].
+    pub fn get_event_cause(&self) -> Result<EventCause> {
+        let value = self.get_cause();
+        EventCause::try_from(value).map_err(|_| MdfError::InvalidEnumValue("EventCause", value))
+    }
+
     /// Gets the creator index of the event.
     pub fn get_creator_index(&self) -> u16 {
         unsafe { ffi::EventGetCreatorIndex(self.inner) }
@@ -153,6 +178,20 @@ impl<'a> EventRef<'a> {
             }
         }
     }
+
+    /// Gets the event that begins the range ended by this event.
+    ///
+    /// Only set on [`RangeType::RangeEnd`] events.
+    pub fn get_range_event(&self) -> Option<EventRef<'a>> {
+        unsafe {
+            let event = ffi::EventGetRangeEvent(self.inner);
+            if event.is_null() {
+                None
+            } else {
+                Some(EventRef::new(event))
+            }
+        }
+    }
 }
 
 /// Represents a mutable event in an MDF file.
@@ -226,6 +265,26 @@ impl<'a> Event<'a> {
         }
     }
 
+    /// Sets the type of the event from an [`EventType`].
+    pub fn set_event_type(&mut self, event_type: EventType) {
+        self.set_type(event_type as u8);
+    }
+
+    /// Sets the sync type of the event from a [`SyncType`].
+    pub fn set_sync_type(&mut self, sync_type: SyncType) {
+        self.set_sync(sync_type as u8);
+    }
+
+    /// Sets the range type of the event from a [`RangeType`].
+    pub fn set_range_type(&mut self, range_type: RangeType) {
+        self.set_range(range_type as u8);
+    }
+
+    /// Sets the cause of the event from an [`EventCause`].
+    pub fn set_event_cause(&mut self, cause: EventCause) {
+        self.set_cause(cause as u8);
+    }
+
     /// Sets the creator index of the event.
     pub fn set_creator_index(&mut self, index: u16) {
         unsafe {
@@ -254,6 +313,13 @@ impl<'a> Event<'a> {
         }
     }
 
+    /// Sets the event that begins the range ended by this event.
+    pub fn set_range_event(&mut self, begin: &EventRef) {
+        unsafe {
+            ffi::EventSetRangeEvent(self.inner, begin.inner);
+        }
+    }
+
     /// Sets the post-trigger time of the event.
     pub fn set_post_trig(&mut self, time: f64) {
         unsafe {
@@ -6,12 +6,15 @@
 //! header, data groups, and attachments.
 
 use mdflib_sys as ffi;
-use std::ffi::CStr;
+use std::ffi::{CStr, CString};
 use std::ops::Deref;
 use std::os::raw::c_char;
 
 use crate::attachment::{Attachment, AttachmentRef};
-use crate::{ChannelRef, DataGroup, MdfHeaderRef};
+use crate::channel::ChannelDataType;
+use crate::error::Result;
+use crate::util::read_cstring;
+use crate::{ChannelGroupRef, ChannelRef, DataGroup, DataGroupRef, MdfHeaderRef};
 
 #[derive(Debug, Clone, Copy)]
 pub struct MdfFileRef {
@@ -74,6 +77,25 @@ impl MdfFileRef {
         }
     }
 
+    /// Gets the program identifier stored in the ID block.
+    pub fn get_program_id(&self) -> String {
+        read_cstring(64, |buf, len| unsafe {
+            ffi::MdfFileGetProgramId(self.inner, buf, len)
+        })
+    }
+
+    /// Gets the identification text of the ID block.
+    ///
+    /// This is the file identifier (`MDF`, or `UnFinMF` until the file is
+    /// finalized), the format version and the program identifier, each
+    /// padded with spaces to 8 characters, as in the first 24 bytes of the
+    /// file.
+    pub fn get_id_text(&self) -> String {
+        read_cstring(32, |buf, len| unsafe {
+            ffi::MdfFileGetIdText(self.inner, buf, len)
+        })
+    }
+
     pub fn get_main_version(&self) -> i32 {
         unsafe { ffi::MdfFileGetMainVersion(self.inner) }
     }
@@ -133,6 +155,28 @@ impl MdfFileRef {
         }
     }
 
+    /// Finds every channel named `name` with its data group and channel group.
+    ///
+    /// The same signal can be recorded in several groups, e.g. from redundant
+    /// buses, so all matches are returned in file order. The name must match
+    /// exactly.
+    pub fn find_all_channels(
+        &self,
+        name: &str,
+    ) -> Vec<(DataGroupRef, ChannelGroupRef, ChannelRef<'_>)> {
+        let mut channels = Vec::new();
+        for dg in self.get_data_groups() {
+            for cg in dg.get_channel_groups() {
+                for cn in cg.get_channels() {
+                    if cn.get_name() == name {
+                        channels.push((*dg, *cg, ChannelRef::new(cn.inner)));
+                    }
+                }
+            }
+        }
+        channels
+    }
+
     /// Gets the attachments of the file.
     pub fn get_attachments(&self) -> Vec<AttachmentRef<'_>> {
         const MAX_ATTACHMENTS: usize = 1000;
@@ -149,9 +193,88 @@ impl MdfFileRef {
             .collect()
     }
 
+    /// Gets all channel groups of the file with their data group and number of
+    /// samples, sorted by descending number of samples.
+    ///
+    /// The first entry is the group holding most of the measurement, which is
+    /// useful to select the main group of an unknown file. Groups with the same
+    /// number of samples keep their file order.
+    pub fn channel_groups_by_size(&self) -> Vec<(DataGroupRef, ChannelGroupRef, u64)> {
+        let mut groups: Vec<_> = self
+            .get_data_groups()
+            .iter()
+            .flat_map(|dg| {
+                dg.get_channel_groups()
+                    .into_iter()
+                    .map(|cg| (**dg, *cg, cg.get_nof_samples()))
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        groups.sort_by_key(|(_, _, samples)| std::cmp::Reverse(*samples));
+        groups
+    }
+
+    /// Gets the number of channels in all channel groups of the file.
+    pub fn total_channel_count(&self) -> usize {
+        self.get_data_groups()
+            .iter()
+            .flat_map(|dg| dg.get_channel_groups())
+            .map(|cg| cg.get_channel_count())
+            .sum()
+    }
+
+    /// Gets the path, data type and unit of every channel in the file.
+    ///
+    /// The path is `<data group index>/<channel group name>/<channel name>`.
+    /// Channels with a data type unknown to the library are left out.
+    pub fn schema_summary(&self) -> Vec<(String, ChannelDataType, String)> {
+        let mut summary = Vec::new();
+        for (dg_index, dg) in self.get_data_groups().iter().enumerate() {
+            for cg in dg.get_channel_groups() {
+                let cg_name = cg.get_name();
+                for cn in cg.get_channels() {
+                    if let Ok(data_type) = cn.get_channel_data_type() {
+                        summary.push((
+                            format!("{dg_index}/{cg_name}/{}", cn.get_name()),
+                            data_type,
+                            cn.get_unit(),
+                        ));
+                    }
+                }
+            }
+        }
+        summary
+    }
+
+    /// Returns true if the file holds no samples.
+    ///
+    /// This is the case for a file with only a header and no data groups, or
+    /// when none of its channel groups has any samples. The accessors of such
+    /// a file return empty lists rather than errors.
+    pub fn is_empty(&self) -> bool {
+        self.get_data_groups()
+            .iter()
+            .flat_map(|dg| dg.get_channel_groups())
+            .all(|cg| cg.get_nof_samples() == 0)
+    }
+
     pub fn is_finalized_done(&self) -> bool {
         unsafe { ffi::MdfFileIsFinalizedDone(self.inner) }
     }
+
+    /// Gets the number of record bytes written by the measurement.
+    ///
+    /// The sum over all channel groups of the number of samples times
+    /// [`crate::ChannelGroupRef::get_record_byte_count`]. Block headers, record
+    /// IDs and invalidation bytes aren't included, so this is an estimate of
+    /// the payload size rather than the file size.
+    pub fn get_measurement_byte_size(&self) -> u64 {
+        self.get_data_groups()
+            .iter()
+            .flat_map(|dg| dg.get_channel_groups())
+            .map(|cg| cg.get_nof_samples() * cg.get_record_byte_count())
+            .sum()
+    }
 }
 
 #[derive(Debug)]
@@ -168,6 +291,16 @@ impl MdfFile {
         }
     }
 
+    /// Sets the program identifier stored in the ID block, at most 8
+    /// characters are written.
+    pub fn set_program_id(&mut self, program_id: &str) -> Result<()> {
+        let c_program_id = CString::new(program_id)?;
+        unsafe {
+            ffi::MdfFileSetProgramId(self.inner, c_program_id.as_ptr());
+        }
+        Ok(())
+    }
+
     pub fn create_data_group(&mut self) -> DataGroup {
         unsafe { DataGroup::new(ffi::MdfFileCreateDataGroup(self.inner)) }
     }
@@ -54,6 +54,16 @@ impl<'a> FileHistoryRef<'a> {
         unsafe { ffi::FileHistoryGetTime(self.inner) }
     }
 
+    /// Gets the time zone offset of the file history time in minutes.
+    pub fn get_timezone_offset_min(&self) -> i16 {
+        unsafe { ffi::FileHistoryGetTimezoneOffsetMin(self.inner) }
+    }
+
+    /// Gets the daylight saving time offset of the file history time in minutes.
+    pub fn get_dst_offset_min(&self) -> i16 {
+        unsafe { ffi::FileHistoryGetDstOffsetMin(self.inner) }
+    }
+
     /// Gets the description of the file history.
     pub fn get_description(&self) -> String {
         unsafe {
@@ -160,6 +170,27 @@ impl<'a> FileHistory<'a> {
         }
     }
 
+    /// Sets the time zone offset of the file history time in minutes.
+    ///
+    /// The time itself stays in UTC. Setting the time with
+    /// [`FileHistory::set_time`] clears the offsets, so set them afterwards.
+    pub fn set_timezone_offset_min(&mut self, offset: i16) {
+        let dst = self.get_dst_offset_min();
+        unsafe {
+            ffi::FileHistorySetTimezone(self.inner, offset, dst);
+        }
+    }
+
+    /// Sets the daylight saving time offset of the file history time in minutes.
+    ///
+    /// See [`FileHistory::set_timezone_offset_min`].
+    pub fn set_dst_offset_min(&mut self, offset: i16) {
+        let timezone = self.get_timezone_offset_min();
+        unsafe {
+            ffi::FileHistorySetTimezone(self.inner, timezone, offset);
+        }
+    }
+
     /// Sets the description of the file history.
     pub fn set_description(&mut self, description: &str) -> Result<()> {
         let c_description = CString::new(description)?;
@@ -204,6 +235,33 @@ impl<'a> FileHistory<'a> {
         }
         Ok(())
     }
+
+    /// Applies all fields of a [`FileHistoryEntry`].
+    pub fn apply(&mut self, entry: &FileHistoryEntry) -> Result<()> {
+        let time = entry.time.unwrap_or_else(|| {
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_nanos() as u64)
+                .unwrap_or(0)
+        });
+        self.set_time(time);
+        if let Some(description) = &entry.description {
+            self.set_description(description)?;
+        }
+        if let Some(tool_name) = &entry.tool_name {
+            self.set_tool_name(tool_name)?;
+        }
+        if let Some(tool_vendor) = &entry.tool_vendor {
+            self.set_tool_vendor(tool_vendor)?;
+        }
+        if let Some(tool_version) = &entry.tool_version {
+            self.set_tool_version(tool_version)?;
+        }
+        if let Some(user_name) = &entry.user_name {
+            self.set_user_name(user_name)?;
+        }
+        Ok(())
+    }
 }
 
 impl<'a> Deref for FileHistory<'a> {
@@ -213,3 +271,22 @@ impl<'a> Deref for FileHistory<'a> {
         &self.inner_ref
     }
 }
+
+/// Values for a new file history entry, see [`crate::MdfWriter::add_file_history`].
+///
+/// Fields left as `None` are not set. The time defaults to the current time.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FileHistoryEntry {
+    /// Time in nanoseconds since 1970-01-01
+    pub time: Option<u64>,
+    /// Description of the change
+    pub description: Option<String>,
+    /// Name of the tool
+    pub tool_name: Option<String>,
+    /// Vendor of the tool
+    pub tool_vendor: Option<String>,
+    /// Version of the tool
+    pub tool_version: Option<String>,
+    /// Name of the user
+    pub user_name: Option<String>,
+}
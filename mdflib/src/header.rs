@@ -6,12 +6,15 @@
 //! and file history.
 
 use mdflib_sys as ffi;
+use mdflib_sys::{RangeType, SyncType};
 use std::ffi::{CStr, CString};
 use std::ops::Deref;
 use std::os::raw::c_char;
 
 use crate::attachment::{Attachment, AttachmentRef};
+use crate::channelhierarchy::{ChannelHierarchy, ChannelHierarchyRef};
 use crate::datagroup::{DataGroup, DataGroupRef};
+use crate::error::{MdfError, Result};
 use crate::event::{Event, EventRef};
 use crate::filehistory::{FileHistory, FileHistoryRef};
 use crate::metadata::{MetaData, MetaDataRef};
@@ -192,6 +195,15 @@ impl MdfHeaderRef {
         }
     }
 
+    /// Gets the XML of the header comment (the `HDcomment` element).
+    ///
+    /// Returns an empty string if the header has no metadata.
+    pub fn get_comment_xml(&self) -> String {
+        self.get_metadata()
+            .map(|md| md.get_xml_snippet())
+            .unwrap_or_default()
+    }
+
     /// Gets the attachments of the header.
     pub fn get_attachments(&self) -> Vec<AttachmentRef<'_>> {
         const MAX_ATTACHMENTS: usize = 1000;
@@ -238,6 +250,91 @@ impl MdfHeaderRef {
             .collect()
     }
 
+    /// Gets the top level channel hierarchy blocks of the header.
+    ///
+    /// See [`ChannelHierarchyRef::iter_descendants`] to walk the whole tree.
+    pub fn get_channel_hierarchies(&self) -> Vec<ChannelHierarchyRef<'_>> {
+        const MAX_HIERARCHIES: usize = 1000;
+        let mut hierarchies: Vec<*const ffi::IChannelHierarchy> =
+            vec![std::ptr::null(); MAX_HIERARCHIES];
+        let count = unsafe {
+            ffi::IHeaderGetChannelHierarchies(self.inner, hierarchies.as_mut_ptr(), MAX_HIERARCHIES)
+        };
+
+        hierarchies.truncate(count);
+        hierarchies
+            .into_iter()
+            .filter(|&ptr| !ptr.is_null())
+            .map(ChannelHierarchyRef::new)
+            .collect()
+    }
+
+    /// Gets the number of events.
+    pub fn get_event_count(&self) -> usize {
+        unsafe { ffi::IHeaderGetEventCount(self.inner) }
+    }
+
+    /// Gets an event by its index.
+    pub fn get_event(&self, index: usize) -> Option<EventRef<'_>> {
+        unsafe {
+            let event = ffi::IHeaderGetEventByIndex(self.inner, index);
+            if event.is_null() {
+                None
+            } else {
+                Some(EventRef::new(event))
+            }
+        }
+    }
+
+    /// Iterates over the events without collecting them into a `Vec`.
+    pub fn events_iter(&self) -> impl Iterator<Item = EventRef<'_>> + '_ {
+        (0..self.get_event_count()).filter_map(move |index| self.get_event(index))
+    }
+
+    /// Gets the events whose time lies within `start_ns..=end_ns`.
+    ///
+    /// The absolute time of an event is the start time of the measurement
+    /// plus its sync value times sync factor in seconds. Events synchronized
+    /// to anything else than time are skipped. Times are in nanoseconds since
+    /// the epoch as for [`MdfHeaderRef::get_start_time`].
+    pub fn events_in_range(&self, start_ns: u64, end_ns: u64) -> Vec<EventRef<'_>> {
+        let start_time = self.get_start_time() as i128;
+        self.events_iter()
+            .filter(|event| matches!(event.get_sync_type(), Ok(SyncType::SyncTime)))
+            .filter(|event| {
+                let offset = event.get_sync_value() as f64 * event.get_sync_factor();
+                let time = start_time + (offset * 1e9).round() as i128;
+                (start_ns as i128..=end_ns as i128).contains(&time)
+            })
+            .collect()
+    }
+
+    /// Gets the ranges of the header events as pairs of begin and end events.
+    ///
+    /// An end event is matched to the begin event it links to. End events
+    /// without a link are matched to the last begin event before them that
+    /// isn't closed yet. Begin events without an end are not returned.
+    pub fn get_event_ranges(&self) -> Vec<(EventRef<'_>, EventRef<'_>)> {
+        let mut open: Vec<EventRef<'_>> = Vec::new();
+        let mut ranges = Vec::new();
+        for event in self.get_events() {
+            match event.get_range_type() {
+                Ok(RangeType::RangeStart) => open.push(event),
+                Ok(RangeType::RangeEnd) => {
+                    let begin = match event.get_range_event() {
+                        Some(begin) => open.iter().position(|open| open.inner == begin.inner),
+                        None => open.len().checked_sub(1),
+                    };
+                    if let Some(begin) = begin {
+                        ranges.push((open.remove(begin), event));
+                    }
+                }
+                _ => {}
+            }
+        }
+        ranges
+    }
+
     /// Gets the data group count.
     pub fn get_data_group_count(&self) -> usize {
         unsafe { ffi::IHeaderGetDataGroupCount(self.inner) as usize }
@@ -343,6 +440,52 @@ impl MdfHeader {
         }
     }
 
+    /// Copies the header information of another file into this header.
+    ///
+    /// The identifiers, descriptive fields, start time, start angle and
+    /// distance, the metadata XML and all file histories of `src` are copied.
+    /// The file histories are appended after any already in this header.
+    /// Attachments, events and data groups are not copied.
+    pub fn copy_from(&mut self, src: &MdfHeaderRef) -> Result<()> {
+        self.set_measurement_id(&src.get_measurement_id());
+        self.set_recorder_id(&src.get_recorder_id());
+        self.set_recorder_index(src.get_recorder_index());
+        self.set_author(&src.get_author());
+        self.set_department(&src.get_department());
+        self.set_project(&src.get_project());
+        self.set_subject(&src.get_subject());
+        self.set_description(&src.get_description());
+        self.set_start_time(src.get_start_time());
+        if let Some(angle) = src.get_start_angle() {
+            self.set_start_angle(angle);
+        }
+        if let Some(distance) = src.get_start_distance() {
+            self.set_start_distance(distance);
+        }
+
+        if let Some(src_metadata) = src.get_metadata() {
+            let xml = src_metadata.get_xml_snippet();
+            if !xml.is_empty() {
+                let mut metadata = self.create_metadata().ok_or(MdfError::NullPointer)?;
+                metadata.set_xml_snippet(&xml)?;
+            }
+        }
+
+        for src_history in src.get_file_histories() {
+            let mut history = self.create_file_history().ok_or(MdfError::NullPointer)?;
+            history.set_time(src_history.get_time());
+            history.set_timezone_offset_min(src_history.get_timezone_offset_min());
+            history.set_dst_offset_min(src_history.get_dst_offset_min());
+            history.set_description(&src_history.get_description())?;
+            history.set_tool_name(&src_history.get_tool_name())?;
+            history.set_tool_vendor(&src_history.get_tool_vendor())?;
+            history.set_tool_version(&src_history.get_tool_version())?;
+            history.set_user_name(&src_history.get_user_name())?;
+        }
+
+        Ok(())
+    }
+
     /// Creates metadata for the header.
     pub fn create_metadata(&mut self) -> Option<MetaData<'_>> {
         unsafe {
@@ -355,6 +498,16 @@ impl MdfHeader {
         }
     }
 
+    /// Sets the XML of the header comment.
+    ///
+    /// mdflib stores the snippet as given without validating it, so malformed
+    /// XML is only reported by readers of the file. Fails only if `xml`
+    /// contains a NUL byte.
+    pub fn set_comment_xml(&mut self, xml: &str) -> Result<()> {
+        let mut metadata = self.create_metadata().ok_or(MdfError::NullPointer)?;
+        metadata.set_xml_snippet(xml)
+    }
+
     /// Creates an attachment for the header.
     pub fn create_attachment(&mut self) -> Option<Attachment<'_>> {
         unsafe {
@@ -391,6 +544,41 @@ impl MdfHeader {
         }
     }
 
+    /// Creates a top level channel hierarchy block.
+    pub fn create_channel_hierarchy(&mut self) -> Option<ChannelHierarchy<'_>> {
+        unsafe {
+            let hierarchy = ffi::IHeaderCreateChannelHierarchy(self.inner);
+            if hierarchy.is_null() {
+                None
+            } else {
+                Some(ChannelHierarchy::new(hierarchy))
+            }
+        }
+    }
+
+    /// Creates a pair of events marking the begin and end of a range.
+    ///
+    /// The events have their range type set and the end event links to the
+    /// begin event, see [`MdfHeaderRef::get_event_ranges`].
+    pub fn create_event_range(&mut self) -> Option<(Event<'_>, Event<'_>)> {
+        unsafe {
+            let begin = ffi::IHeaderCreateEvent(self.inner);
+            if begin.is_null() {
+                return None;
+            }
+            let end = ffi::IHeaderCreateEvent(self.inner);
+            if end.is_null() {
+                return None;
+            }
+            let mut begin = Event::new(begin);
+            let mut end = Event::new(end);
+            begin.set_range_type(RangeType::RangeStart);
+            end.set_range_type(RangeType::RangeEnd);
+            end.set_range_event(&begin);
+            Some((begin, end))
+        }
+    }
+
     /// Gets all data groups from the header.
     pub fn get_data_groups(&self) -> Vec<DataGroupRef> {
         const MAX_DATA_GROUPS: usize = 1000;
@@ -10,27 +10,45 @@
 //! *   Read and write channel data.
 //! *   `bundled` (default): Compiles and statically links the `mdflib` C++ library.
 //! *   `system`: Links against a system-installed version of `mdflib`.
+//! *   `serde`: Implements `Serialize` and `Deserialize` for the plain [`Frame`],
+//!     [`ChannelInfo`] and [`HeaderInfo`] types.
+//! *   `tracing`: Adds [`log::set_tracing_callback`] to emit mdflib log messages
+//!     as `tracing` events.
+//! *   `encoding`: Decodes MDF3 text that is not valid UTF-8 as Windows-1252
+//!     (a superset of Latin-1) using `encoding_rs` instead of replacing the
+//!     invalid characters in the reader aware getters such as
+//!     [`ChannelRef::decode_name`]. Other getters always read text as UTF-8.
+//! *   `md5`: Adds `AttachmentRef::check_md5` to check embedded attachments
+//!     against their stored MD5 checksum, also used by [`MdfReader::verify`].
 //!
 //! See [`crate::MdfReader`] and [`crate::MdfWriter`] docs for examples of how
 //! to use the library. The 'examples/read_mdf.rs' and workspace binary
 //! 'mf4_candump' provide additional usage examples.
 
+pub mod builder;
 pub mod canmessage;
+pub mod catalog;
 pub mod channel;
 pub mod channelgroup;
 pub mod datagroup;
+pub mod document;
 pub mod error;
 pub mod file;
 pub mod header;
 pub mod reader;
+pub mod transcode;
+pub mod version;
 pub mod writer;
 
 pub mod log;
 
+mod util;
+
 // Re-export binding enums
 pub use mdflib_sys::{
-    ArrayStorage, ArrayType, BusType, CanErrorType, ChannelType, ConversionType, ETagDataType,
-    EventCause, EventType, MdfBusType, MessageType, RangeType, SourceType, SyncType,
+    ArrayStorage, ArrayType, BusType, CanErrorType, ChType, ChannelSyncType, ChannelType,
+    ConversionType, ETagDataType, EventCause, EventType, MdfBusType, MdfStorageType, MessageType,
+    RangeType, SourceType, SyncType,
 };
 
 // New MDF object modules
@@ -38,6 +56,7 @@ pub mod attachment;
 pub mod canbusobserver;
 pub mod channelarray;
 pub mod channelconversion;
+pub mod channelhierarchy;
 pub mod channelobserver;
 pub mod etag;
 pub mod event;
@@ -45,25 +64,37 @@ pub mod filehistory;
 pub mod metadata;
 pub mod sourceinformation;
 
-pub use canmessage::{CanMessage, CanMessageRef};
-pub use channel::{Channel, ChannelRef};
-pub use channelgroup::{ChannelGroup, ChannelGroupRef};
+pub use builder::{ChannelSpec, DataGroupBuilder};
+pub use canmessage::{dlc_to_length, CanMessage, CanMessageRef, Frame, OwnedCanFrame};
+pub use catalog::{ChannelInfo, HeaderInfo};
+pub use channel::{
+    data_type_byte_order, data_type_default_bytes, ByteOrder, Channel, ChannelDataType, ChannelRef,
+    SampleValue,
+};
+pub use channelgroup::{estimate_file_size, ChannelGroup, ChannelGroupRef};
 pub use datagroup::{DataGroup, DataGroupRef};
+pub use document::{ChannelData, ChannelGroupData, DataGroupData, MdfDocument};
 pub use error::{MdfError, Result};
 pub use file::{MdfFile, MdfFileRef};
 pub use header::{MdfHeader, MdfHeaderRef};
-pub use reader::MdfReader;
-pub use writer::{MdfWriter, MdfWriterType};
+pub use reader::{MdfReader, SkippedDataGroup, VerifyReport};
+pub use transcode::{merge_files, transcode};
+pub use version::mdflib_version;
+pub use writer::{MdfWriter, MdfWriterType, MeasurementGuard};
 
 // Re-export new MDF object types
-pub use attachment::{Attachment, AttachmentRef};
+pub use attachment::{Attachment, AttachmentFileType, AttachmentRef};
 pub use canbusobserver::{create_can_bus_observer, CanBusObserver, CanBusObserverRef};
 pub use channelarray::{ChannelArray, ChannelArrayRef};
 pub use channelconversion::{ChannelConversion, ChannelConversionRef};
-pub use channelobserver::{create_channel_observer, ChannelObserver, ChannelObserverRef};
+pub use channelhierarchy::{ChannelHierarchy, ChannelHierarchyRef};
+pub use channelobserver::{
+    create_channel_group_observers, create_channel_observer, create_raw_channel_observer,
+    create_timed_channel_observer, ChannelObserver, ChannelObserverRef, SampleBuffer, TypedSamples,
+};
 pub use etag::{ETag, ETagRef};
 pub use event::{Event, EventRef};
-pub use filehistory::{FileHistory, FileHistoryRef};
+pub use filehistory::{FileHistory, FileHistoryEntry, FileHistoryRef};
 pub use log::{log_callback, set_log_callback_1};
 pub use metadata::{MetaData, MetaDataRef};
 pub use sourceinformation::{SourceInformation, SourceInformationRef};
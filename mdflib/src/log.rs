@@ -4,11 +4,12 @@
 //! underlying `mdflib` C++ library. It allows users to set a custom logging
 //! callback function to handle log messages from the library.
 
-use crate::error::{MdfError, Result};
+use crate::error::Result;
 use mdflib_sys as ffi;
 use std::ffi::CStr;
 use std::os::raw::c_char;
-use std::sync::Mutex;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Arc, Mutex, PoisonError};
 
 /// Re-export of the MdfLogSeverity enum for use in the logging callback.
 pub use ffi::MdfLogSeverity;
@@ -18,19 +19,44 @@ pub type LogCallback1 = extern "C" fn(severity: MdfLogSeverity, text: *const u8)
 pub type LogCallback2 =
     extern "C" fn(severity: MdfLogSeverity, function: *const u8, text: *const u8);
 
+/// Type alias for a closure logging callback, see [`set_log_closure`].
+pub type LogClosure = Arc<dyn Fn(MdfLogSeverity, &str) + Send + Sync>;
+
+/// A user-defined logging callback without function name.
+#[derive(Clone)]
+enum LogHandler1 {
+    Extern(LogCallback1),
+    Closure(LogClosure),
+}
+
 /// A static variable to hold the user-defined logging callback.
-static LOG_CALLBACK_1: Mutex<Option<LogCallback1>> = Mutex::new(None);
+static LOG_CALLBACK_1: Mutex<Option<LogHandler1>> = Mutex::new(None);
 static LOG_CALLBACK_2: Mutex<Option<LogCallback2>> = Mutex::new(None);
 
 /// The C-compatible callback function that will be passed to the C++ library.
+///
+/// The callback is cloned out of [`LOG_CALLBACK_1`] and called without holding
+/// the lock, so it can set the logging function or log itself. A panic must
+/// not unwind into mdflib, so it is caught and the message dropped.
 extern "C" fn log_callback_wrapper_1(severity: MdfLogSeverity, text: *const c_char) {
-    unsafe {
-        if let Some(callback) = LOG_CALLBACK_1.lock().unwrap().as_ref() {
-            let rust_text = CStr::from_ptr(text).to_string_lossy();
-            let bytes = rust_text.as_bytes();
-            callback(severity, bytes.as_ptr());
+    let handler = LOG_CALLBACK_1
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone();
+    let Some(handler) = handler else {
+        return;
+    };
+
+    let _ = panic::catch_unwind(AssertUnwindSafe(|| unsafe {
+        let rust_text = CStr::from_ptr(text).to_string_lossy();
+        match handler {
+            LogHandler1::Extern(callback) => {
+                let bytes = rust_text.as_bytes();
+                callback(severity, bytes.as_ptr());
+            }
+            LogHandler1::Closure(callback) => callback(severity, &rust_text),
         }
-    }
+    }));
 }
 
 extern "C" fn log_callback_wrapper_2(
@@ -38,8 +64,11 @@ extern "C" fn log_callback_wrapper_2(
     function: *const c_char,
     text: *const c_char,
 ) {
+    let callback = *LOG_CALLBACK_2
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
     unsafe {
-        if let Some(callback) = LOG_CALLBACK_2.lock().unwrap().as_ref() {
+        if let Some(callback) = callback {
             let rust_function = CStr::from_ptr(function).to_string_lossy();
             let rust_text = CStr::from_ptr(text).to_string_lossy();
             let function_bytes = rust_function.as_bytes();
@@ -51,6 +80,9 @@ extern "C" fn log_callback_wrapper_2(
 
 /// Sets a custom logging function.
 ///
+/// Replaces any logging function set before, including a closure set with
+/// [`set_log_closure`]. `None` removes the logging function.
+///
 /// # Example
 ///
 /// ```
@@ -67,16 +99,14 @@ extern "C" fn log_callback_wrapper_2(
 /// ```
 pub fn set_log_callback_1(callback: Option<LogCallback1>) -> Result<()> {
     unsafe {
+        let mut handler = LOG_CALLBACK_1
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
         if let Some(callback) = callback {
-            if LOG_CALLBACK_1.lock().unwrap().is_some() {
-                return Err(MdfError::CallbackError(
-                    "Failed to set log callback, already set".to_string(),
-                ));
-            }
-            LOG_CALLBACK_1.lock().unwrap().replace(callback);
+            handler.replace(LogHandler1::Extern(callback));
             ffi::MdfSetLogFunction1(Some(log_callback_wrapper_1));
         } else {
-            LOG_CALLBACK_1.lock().unwrap().take();
+            handler.take();
             ffi::MdfSetLogFunction1(None);
         }
     }
@@ -84,8 +114,37 @@ pub fn set_log_callback_1(callback: Option<LogCallback1>) -> Result<()> {
     Ok(())
 }
 
+/// Sets a closure as the logging function.
+///
+/// Unlike [`set_log_callback_1`] the closure can capture its environment. It
+/// replaces any logging function set before, set `None` with
+/// [`set_log_callback_1`] to remove it. The closure may set the logging
+/// function itself. If it panics, the panic is caught and the message is
+/// dropped, as it can't unwind into mdflib.
+///
+/// # Example
+///
+/// ```
+/// use mdflib::log::set_log_closure;
+///
+/// let prefix = String::from("mdflib");
+/// set_log_closure(move |severity, text| println!("{prefix} [{severity:?}] {text}"));
+/// ```
+pub fn set_log_closure(callback: impl Fn(MdfLogSeverity, &str) + Send + Sync + 'static) {
+    LOG_CALLBACK_1
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .replace(LogHandler1::Closure(Arc::new(callback)));
+    unsafe {
+        ffi::MdfSetLogFunction1(Some(log_callback_wrapper_1));
+    }
+}
+
 /// Sets a custom logging function with function name.
 ///
+/// Replaces any logging function with function name set before. `None`
+/// removes the logging function.
+///
 /// # Example
 ///
 /// ```
@@ -104,11 +163,6 @@ pub fn set_log_callback_1(callback: Option<LogCallback1>) -> Result<()> {
 pub fn set_log_callback_2(callback: Option<LogCallback2>) -> Result<()> {
     unsafe {
         if let Some(callback) = callback {
-            if LOG_CALLBACK_2.lock().unwrap().is_some() {
-                return Err(MdfError::CallbackError(
-                    "Failed to set log callback, already set".to_string(),
-                ));
-            }
             LOG_CALLBACK_2.lock().unwrap().replace(callback);
             ffi::MdfSetLogFunction2(Some(log_callback_wrapper_2));
         } else {
@@ -150,3 +204,113 @@ pub extern "C" fn log_callback_with_function(
         _ => log::warn!("[{function}] [{severity:?}]: {text}"),
     }
 }
+
+/// A C-compatible logging callback function that emits messages as `tracing` events.
+///
+/// Events use the `mdflib` target. Warnings map to [`tracing::Level::WARN`]
+/// and the error severities and above to [`tracing::Level::ERROR`].
+#[cfg(feature = "tracing")]
+pub extern "C" fn tracing_callback(severity: MdfLogSeverity, text: *const u8) {
+    let text = unsafe { CStr::from_ptr(text as *const c_char).to_string_lossy() };
+    match severity {
+        MdfLogSeverity::kTrace => tracing::trace!(target: "mdflib", ?severity, "{text}"),
+        MdfLogSeverity::kDebug => tracing::debug!(target: "mdflib", ?severity, "{text}"),
+        MdfLogSeverity::kInfo | MdfLogSeverity::kNotice => {
+            tracing::info!(target: "mdflib", ?severity, "{text}")
+        }
+        MdfLogSeverity::kWarning => tracing::warn!(target: "mdflib", ?severity, "{text}"),
+        _ => tracing::error!(target: "mdflib", ?severity, "{text}"),
+    }
+}
+
+/// Sets [`tracing_callback`] as the logging function, emitting mdflib log
+/// messages as `tracing` events.
+///
+/// Replaces any logging function set before.
+#[cfg(feature = "tracing")]
+pub fn set_tracing_callback() -> Result<()> {
+    set_log_callback_1(Some(tracing_callback))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Serializes the tests as they share the global logging function
+    static TEST_LOCK: Mutex<()> = Mutex::new(());
+    static FIRST_CALLS: AtomicUsize = AtomicUsize::new(0);
+    static SECOND_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+    extern "C" fn first_callback(_severity: MdfLogSeverity, _text: *const u8) {
+        FIRST_CALLS.fetch_add(1, Ordering::SeqCst);
+    }
+
+    extern "C" fn second_callback(_severity: MdfLogSeverity, _text: *const u8) {
+        SECOND_CALLS.fetch_add(1, Ordering::SeqCst);
+    }
+
+    #[test]
+    fn test_log_callback_replace_and_clear() {
+        let _guard = TEST_LOCK.lock().unwrap();
+        set_log_callback_1(Some(first_callback)).unwrap();
+        log_callback_wrapper_1(MdfLogSeverity::kInfo, c"First".as_ptr());
+
+        set_log_callback_1(Some(second_callback)).unwrap();
+        log_callback_wrapper_1(MdfLogSeverity::kInfo, c"Second".as_ptr());
+
+        set_log_callback_1(None).unwrap();
+        log_callback_wrapper_1(MdfLogSeverity::kInfo, c"None".as_ptr());
+
+        assert_eq!(FIRST_CALLS.load(Ordering::SeqCst), 1);
+        assert_eq!(SECOND_CALLS.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_log_closure_receives_messages() {
+        let _guard = TEST_LOCK.lock().unwrap();
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let sink = received.clone();
+        set_log_closure(move |severity, text| {
+            sink.lock().unwrap().push((severity, text.to_string()));
+        });
+
+        log_callback_wrapper_1(MdfLogSeverity::kInfo, c"Opened file".as_ptr());
+        log_callback_wrapper_1(MdfLogSeverity::kError, c"Read failed".as_ptr());
+        set_log_callback_1(None).unwrap();
+        log_callback_wrapper_1(MdfLogSeverity::kInfo, c"Not received".as_ptr());
+
+        let received = received.lock().unwrap();
+        assert_eq!(
+            *received,
+            [
+                (MdfLogSeverity::kInfo, "Opened file".to_string()),
+                (MdfLogSeverity::kError, "Read failed".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_log_closure_reentrant_and_panicking() {
+        let _guard = TEST_LOCK.lock().unwrap();
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        // Replaces itself from within the callback
+        set_log_closure(move |_, _| {
+            let counter = counter.clone();
+            set_log_closure(move |_, text| {
+                counter.fetch_add(1, Ordering::SeqCst);
+                if text == "Panic" {
+                    panic!("closure panicked");
+                }
+            });
+        });
+
+        log_callback_wrapper_1(MdfLogSeverity::kInfo, c"Replace".as_ptr());
+        log_callback_wrapper_1(MdfLogSeverity::kInfo, c"Panic".as_ptr());
+        log_callback_wrapper_1(MdfLogSeverity::kInfo, c"After panic".as_ptr());
+        set_log_callback_1(None).unwrap();
+
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+}
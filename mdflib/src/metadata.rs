@@ -86,11 +86,11 @@ impl<'a> MetaDataRef<'a> {
             ffi::MetaDataGetProperties(self.inner, properties.as_mut_ptr(), MAX_PROPERTIES)
         };
 
-        properties.truncate(count);
+        properties.truncate(count.min(MAX_PROPERTIES));
         properties
             .into_iter()
             .filter(|&ptr| !ptr.is_null())
-            .map(ETag::from_raw)
+            .map(ETag::from_owned_raw)
             .collect()
     }
 
@@ -102,11 +102,11 @@ impl<'a> MetaDataRef<'a> {
             ffi::MetaDataGetCommonProperties(self.inner, properties.as_mut_ptr(), MAX_PROPERTIES)
         };
 
-        properties.truncate(count);
+        properties.truncate(count.min(MAX_PROPERTIES));
         properties
             .into_iter()
             .filter(|&ptr| !ptr.is_null())
-            .map(ETag::from_raw)
+            .map(ETag::from_owned_raw)
             .collect()
     }
 }
@@ -146,6 +146,23 @@ impl<'a> MetaData<'a> {
         Ok(())
     }
 
+    /// Merges string properties into the metadata.
+    ///
+    /// Only the given keys are added or updated, any other existing properties
+    /// are kept. No property is changed if a key or value contains a null byte.
+    pub fn merge_properties(&mut self, props: &[(String, String)]) -> Result<()> {
+        let c_props = props
+            .iter()
+            .map(|(key, value)| Ok((CString::new(key.as_str())?, CString::new(value.as_str())?)))
+            .collect::<Result<Vec<_>>>()?;
+        for (c_key, c_value) in &c_props {
+            unsafe {
+                ffi::MetaDataSetPropertyAsString(self.inner, c_key.as_ptr(), c_value.as_ptr());
+            }
+        }
+        Ok(())
+    }
+
     /// Sets the XML snippet.
     pub fn set_xml_snippet(&mut self, xml: &str) -> Result<()> {
         let c_xml = CString::new(xml)?;
@@ -45,33 +45,94 @@
 //! }
 //! ```
 use crate::{
+    attachment::AttachmentRef,
+    canbusobserver::create_can_bus_observer,
+    canmessage::Frame,
+    catalog::{ChannelInfo, HeaderInfo},
+    channel::ChannelRef,
+    channelobserver::{create_channel_group_observers, create_channel_observer, ChannelObserver},
     datagroup::{DataGroup, DataGroupRef},
+    document::{ChannelData, ChannelGroupData, DataGroupData, MdfDocument},
     error::{MdfError, Result},
     header::MdfHeaderRef,
     MdfFileRef,
 };
 use mdflib_sys::*;
+use std::cell::Cell;
+use std::collections::HashMap;
 use std::ffi::CString;
 use std::path::Path;
 
+/// Report returned by [`MdfReader::verify`]
+///
+/// Each entry in `issues` describes one structural inconsistency found in the
+/// file. An empty list means the file passed all checks.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VerifyReport {
+    /// Whether the file is marked as finalized
+    pub finalized: bool,
+    /// Number of data groups checked
+    pub data_group_count: usize,
+    /// Number of embedded attachments whose MD5 checksum was checked, always
+    /// 0 without the `md5` feature
+    pub attachments_checked: usize,
+    /// Inconsistencies found in the file
+    pub issues: Vec<String>,
+}
+
+impl VerifyReport {
+    /// Returns true if no issues were found
+    pub fn is_ok(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+/// Data group skipped by [`MdfReader::read_all_data`] in tolerant mode
+///
+/// mdflib reads the data of a data group in one pass, so a failure can't be
+/// isolated to a single channel group and all channel groups of the data
+/// group are dropped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkippedDataGroup {
+    /// Index of the data group in the file
+    pub index: usize,
+    /// Names of the channel groups whose data was dropped
+    pub channel_groups: Vec<String>,
+    /// Reason the data couldn't be read
+    pub error: String,
+}
+
 /// Safe wrapper around mdflib's MdfReader
+///
+/// The data group list is read lazily: after only [`MdfReader::read_header`],
+/// [`MdfReader::get_data_group_count`] and [`MdfReader::get_data_group`] read
+/// the measurement info (data, channel group and channel blocks) on first use.
+/// mdflib reads this information for all groups in one pass.
 pub struct MdfReader {
     inner: *mut mdflib_sys::MdfReader,
+    measurement_info_read: Cell<bool>,
+    tolerant: bool,
+    records_read: Cell<u64>,
 }
 
 impl MdfReader {
     /// Create a new MDF reader for the specified file
     pub fn new<P: AsRef<Path>>(path: P) -> Result<Self> {
-        let path_str = path.as_ref().to_str().unwrap();
-        let c_path = CString::new(path_str)?;
+        let path_str = crate::util::path_to_utf8(path.as_ref())?;
+        let c_path = CString::new(path_str.as_str())?;
 
         unsafe {
             let reader = MdfReaderInit(c_path.as_ptr());
             if reader.is_null() {
-                return Err(MdfError::FileOpen(path_str.to_string()));
+                return Err(MdfError::FileOpen(path_str));
             }
 
-            Ok(MdfReader { inner: reader })
+            Ok(MdfReader {
+                inner: reader,
+                measurement_info_read: Cell::new(false),
+                tolerant: false,
+                records_read: Cell::new(0),
+            })
         }
     }
 
@@ -85,8 +146,20 @@ impl MdfReader {
         unsafe { MdfReaderIsFinalized(self.inner) }
     }
 
+    /// Check if the file is currently held open by the reader
+    pub fn is_open(&self) -> bool {
+        unsafe { MdfReaderIsOpen(self.inner) }
+    }
+
     /// Open the MDF file for reading
+    ///
+    /// The `read_*` methods open the file themselves when needed, so an
+    /// explicit open is only required to keep the file open across several
+    /// reads. Opening an already open file does nothing.
     pub fn open(&mut self) -> Result<()> {
+        if self.is_open() {
+            return Ok(());
+        }
         unsafe {
             if MdfReaderOpen(self.inner) {
                 Ok(())
@@ -97,9 +170,14 @@ impl MdfReader {
     }
 
     /// Close the MDF file
+    ///
+    /// Closing a file that is not open does nothing. The structure that has
+    /// already been read remains available.
     pub fn close(&mut self) {
-        unsafe {
-            MdfReaderClose(self.inner);
+        if self.is_open() {
+            unsafe {
+                MdfReaderClose(self.inner);
+            }
         }
     }
 
@@ -130,6 +208,7 @@ impl MdfReader {
     pub fn read_measurement_info(&mut self) -> Result<()> {
         unsafe {
             if MdfReaderReadMeasurementInfo(self.inner) {
+                self.measurement_info_read.set(true);
                 Ok(())
             } else {
                 Err(MdfError::MeasurementInfo)
@@ -153,6 +232,7 @@ impl MdfReader {
     pub fn read_everything_but_data(&mut self) -> Result<()> {
         unsafe {
             if MdfReaderReadEverythingButData(self.inner) {
+                self.measurement_info_read.set(true);
                 Ok(())
             } else {
                 Err(MdfError::DataRead)
@@ -160,13 +240,27 @@ impl MdfReader {
         }
     }
 
+    /// Reads the measurement info if it hasn't been read yet.
+    fn ensure_measurement_info(&self) {
+        if !self.measurement_info_read.get() && unsafe { MdfReaderReadMeasurementInfo(self.inner) }
+        {
+            self.measurement_info_read.set(true);
+        }
+    }
+
     /// Gets the number of data groups in the file.
+    ///
+    /// Reads the measurement info on first use if only the header has been read.
     pub fn get_data_group_count(&self) -> usize {
+        self.ensure_measurement_info();
         unsafe { MdfReaderGetDataGroupCount(self.inner) }
     }
 
     /// Gets a data group by its index.
+    ///
+    /// Reads the measurement info on first use if only the header has been read.
     pub fn get_data_group(&self, index: usize) -> Option<DataGroupRef> {
+        self.ensure_measurement_info();
         unsafe {
             let dg = MdfReaderGetDataGroup(self.inner, index);
             if dg.is_null() {
@@ -177,16 +271,545 @@ impl MdfReader {
         }
     }
 
+    /// Groups all channels of the file by the bus type of their source
+    ///
+    /// The bus type comes from the channel's own source information, or else
+    /// from that of its channel group. Channels without either, or with an
+    /// unknown bus type, are listed under [`BusType::None`]. Channels keep
+    /// their file order within each bus type.
+    ///
+    /// Reads the measurement info on first use if only the header has been read.
+    pub fn channels_by_bus(&self) -> HashMap<BusType, Vec<ChannelRef<'_>>> {
+        let mut channels: HashMap<BusType, Vec<ChannelRef<'_>>> = HashMap::new();
+        for dg in (0..self.get_data_group_count()).filter_map(|i| self.get_data_group(i)) {
+            for cg in dg.get_channel_groups() {
+                let group_bus = cg.get_source_information().map(|si| si.get_bus());
+                for cn in cg.get_channels() {
+                    let bus = cn
+                        .get_source_information()
+                        .map(|si| si.get_bus())
+                        .or(group_bus)
+                        .and_then(|bus| BusType::try_from(bus).ok())
+                        .unwrap_or(BusType::None);
+                    channels
+                        .entry(bus)
+                        .or_default()
+                        .push(ChannelRef::new(cn.inner));
+                }
+            }
+        }
+        channels
+    }
+
     /// Read data from a data group
-    pub fn read_data(&mut self, group: &mut DataGroup) -> Result<()> {
+    ///
+    /// After a successful read the records read for the group's channel
+    /// groups are added to [`MdfReader::records_read`]. A data group without
+    /// channel groups has no records, so nothing is read for it.
+    ///
+    /// Only a shared reference to the reader is needed, so observers borrowing
+    /// the reader, e.g. from [`create_can_bus_observer`], can be filled.
+    pub fn read_data(&self, group: &mut DataGroup) -> Result<()> {
+        if group.get_channel_groups().is_empty() {
+            return Ok(());
+        }
+
+        let inner = group.inner;
+        self.counted_read(group, || unsafe { MdfReaderReadData(self.inner, inner) })
+    }
+
+    /// Reads data from a data group without adding to [`MdfReader::records_read`]
+    pub(crate) fn read_data_untracked(&self, group: &mut DataGroup) -> Result<()> {
+        if !unsafe { MdfReaderReadData(self.inner, group.inner) } {
+            return Err(MdfError::DataRead);
+        }
+        Ok(())
+    }
+
+    /// Runs a read of a data group's data, counting the records passed to its
+    /// channel groups' observers and adding them to [`MdfReader::records_read`]
+    /// if the read succeeds
+    fn counted_read(&self, group: &DataGroup, read: impl FnOnce() -> bool) -> Result<()> {
+        let mut counters = Vec::new();
+        for cg in group.get_channel_groups() {
+            let counter = unsafe { CreateSampleCounter(group.as_ptr(), cg.as_ptr()) };
+            if counter.is_null() {
+                counters
+                    .into_iter()
+                    .for_each(|counter| unsafe { SampleCounterUnInit(counter) });
+                return Err(MdfError::NullPointer);
+            }
+            counters.push(counter);
+        }
+
+        let ok = read();
+        let records: u64 = counters
+            .into_iter()
+            .map(|counter| unsafe {
+                let count = SampleCounterGetCount(counter);
+                SampleCounterUnInit(counter);
+                count
+            })
+            .sum();
+        if !ok {
+            return Err(MdfError::DataRead);
+        }
+        self.records_read.set(self.records_read.get() + records);
+        Ok(())
+    }
+
+    /// Gets the number of records read so far by this reader.
+    ///
+    /// Counts the records read into the channel groups by successful reads
+    /// of [`MdfReader::read_data`] and the methods built on it, including the
+    /// partial reads of [`MdfReader::peek_first_samples`] and
+    /// [`MdfReader::read_single_value`], so it can be used for sample based
+    /// progress when reading the data groups one at a time. Reading a data
+    /// group again counts its records again, a failed read counts nothing.
+    pub fn records_read(&self) -> u64 {
+        self.records_read.get()
+    }
+
+    /// Gets the total number of samples of all channel groups in the file.
+    ///
+    /// Uses the sample counts stored in the channel groups, so no data has to
+    /// be read. Reads the measurement info on first use if only the header has
+    /// been read.
+    pub fn total_sample_count(&self) -> u64 {
+        (0..self.get_data_group_count())
+            .filter_map(|i| self.get_data_group(i))
+            .flat_map(|dg| dg.get_channel_groups())
+            .map(|cg| cg.get_nof_samples())
+            .sum()
+    }
+
+    /// Enable or disable tolerant reading
+    ///
+    /// In tolerant mode [`MdfReader::read_all_data`] logs and skips data groups
+    /// whose data can't be read, e.g. because of unknown or corrupt blocks,
+    /// instead of failing the whole read. The whole data group is skipped, not
+    /// just the channel group that couldn't be read.
+    pub fn set_tolerant(&mut self, tolerant: bool) {
+        self.tolerant = tolerant;
+    }
+
+    /// Check if tolerant reading is enabled
+    pub fn is_tolerant(&self) -> bool {
+        self.tolerant
+    }
+
+    /// Read the data of all data groups
+    ///
+    /// Returns the data groups that were skipped because their data couldn't be
+    /// read. Skipping a data group drops all of its channel groups, which are
+    /// listed in the [`SkippedDataGroup`], so their observers remain empty.
+    /// Unless tolerant mode is enabled with [`MdfReader::set_tolerant`], the
+    /// first failure is returned as [`MdfError::DataRead`] instead.
+    pub fn read_all_data(&mut self) -> Result<Vec<SkippedDataGroup>> {
+        let file = self.get_file().ok_or(MdfError::NullPointer)?;
+        let mut skipped = Vec::new();
+
+        for (index, mut dg) in file.get_data_groups().into_iter().enumerate() {
+            if let Err(e) = self.read_data(&mut dg) {
+                if !self.tolerant {
+                    return Err(e);
+                }
+                let names: Vec<String> = dg
+                    .get_channel_groups()
+                    .iter()
+                    .map(|cg| cg.get_name())
+                    .collect();
+                ::log::warn!(
+                    "Skipping unreadable data group {index} (channel groups: {names:?}): {e}"
+                );
+                dg.clear_data();
+                skipped.push(SkippedDataGroup {
+                    index,
+                    channel_groups: names,
+                    error: e.to_string(),
+                });
+            }
+        }
+
+        Ok(skipped)
+    }
+
+    /// Read the data groups one at a time, clearing each after use
+    ///
+    /// For each data group an observer is created for every channel of its
+    /// channel groups with [`create_channel_group_observers`], so their times
+    /// come from the master channel, the data is read and `f` is called with
+    /// the group and the observers (in channel group then channel order). The observers are
+    /// dropped and the group's data cleared before moving on to the next group,
+    /// so only one group's samples are held in memory at a time. The file
+    /// structure must have been read first with
+    /// [`MdfReader::read_everything_but_data`].
+    pub fn for_each_data_group<F>(&mut self, mut f: F) -> Result<()>
+    where
+        F: FnMut(&mut DataGroup, &[ChannelObserver]) -> Result<()>,
+    {
+        let file = self.get_file().ok_or(MdfError::NullPointer)?;
+
+        for mut dg in file.get_data_groups() {
+            let mut observers = Vec::new();
+            for cg in dg.get_channel_groups() {
+                // Safety: the pointers come from the reader's file which outlives the observers
+                observers
+                    .extend(unsafe { create_channel_group_observers(dg.as_ptr(), cg.as_ptr())? });
+            }
+
+            let result = self.read_data(&mut dg).and_then(|_| f(&mut dg, &observers));
+            drop(observers);
+            dg.clear_data();
+            result?;
+        }
+
+        Ok(())
+    }
+
+    /// Reads the first sample of every channel for a quick file preview
+    ///
+    /// Each data group is read with a one sample range read, so only the start
+    /// of its data is loaded, and cleared again afterwards. Returns
+    /// `(name, value)` pairs with the engineering value in data group, channel
+    /// group then channel order, with `None` for channels without a valid
+    /// first sample. The file structure must have been read first with
+    /// [`MdfReader::read_everything_but_data`].
+    pub fn peek_first_samples(&mut self) -> Result<Vec<(String, Option<f64>)>> {
+        let file = self.get_file().ok_or(MdfError::NullPointer)?;
+        let mut samples = Vec::new();
+
+        for mut dg in file.get_data_groups() {
+            let mut observers = Vec::new();
+            for cg in dg.get_channel_groups() {
+                for cn in cg.get_channels() {
+                    // Safety: the pointers come from the reader's file which outlives the observers
+                    let observer =
+                        unsafe { create_channel_observer(dg.as_ptr(), cg.as_ptr(), cn.as_ptr())? };
+                    observers.push((cn.get_name(), observer));
+                }
+            }
+            if observers.is_empty() {
+                continue;
+            }
+
+            let inner = dg.inner;
+            let read = self.counted_read(&dg, || unsafe {
+                MdfReaderReadPartialData(self.inner, inner, 0, 0)
+            });
+            samples.extend(
+                observers
+                    .iter()
+                    .map(|(name, observer)| (name.clone(), observer.get_eng_value(0))),
+            );
+            drop(observers);
+            dg.clear_data();
+            read?;
+        }
+
+        Ok(samples)
+    }
+
+    /// Reads the engineering value of one sample of a channel
+    ///
+    /// Only the record of `sample` is read from the channel's data group,
+    /// which is cleared again afterwards, so this suits sparse lookups where
+    /// creating an observer and reading the whole group would be wasteful.
+    /// Returns `None` if the sample is invalid, [`MdfError::NotFound`] if the
+    /// channel isn't in the file and [`MdfError::IndexOutOfBounds`] past the
+    /// last sample. The file structure must have been read first with
+    /// [`MdfReader::read_everything_but_data`].
+    pub fn read_single_value(
+        &mut self,
+        channel: &ChannelRef,
+        sample: usize,
+    ) -> Result<Option<f64>> {
+        let file = self.get_file().ok_or(MdfError::NullPointer)?;
+        let not_found = || MdfError::NotFound(channel.get_name());
+        let mut dg = file.find_parent_data_group(channel).ok_or_else(not_found)?;
+        let cg = dg
+            .get_channel_groups()
+            .into_iter()
+            .find(|cg| {
+                cg.get_channels()
+                    .iter()
+                    .any(|cn| cn.as_ptr() == channel.as_ptr())
+            })
+            .ok_or_else(not_found)?;
+        if sample as u64 >= cg.get_nof_samples() {
+            return Err(MdfError::IndexOutOfBounds(sample));
+        }
+
+        // Safety: the pointers come from the reader's file which outlives the observer
+        let observer =
+            unsafe { create_channel_observer(dg.as_ptr(), cg.as_ptr(), channel.as_ptr())? };
+        let inner = dg.inner;
+        let read = self.counted_read(&dg, || unsafe {
+            MdfReaderReadPartialData(self.inner, inner, sample, sample)
+        });
+        let value = observer.get_eng_value(sample);
+        drop(observer);
+        dg.clear_data();
+        read?;
+        Ok(value)
+    }
+
+    /// Reads the CAN messages of every CAN channel group in the file
+    ///
+    /// Each data group with CAN channel groups is read in turn with a
+    /// [`crate::CanBusObserver`] per group, and cleared again once its messages
+    /// are copied. Returns the messages as owned [`Frame`]s with the name of
+    /// their channel group, in data group then channel group order. The file
+    /// structure must have been read first with
+    /// [`MdfReader::read_everything_but_data`].
+    pub fn read_all_can_messages(&mut self) -> Result<Vec<(String, Vec<Frame>)>> {
+        let file = self.get_file().ok_or(MdfError::NullPointer)?;
+        let mut messages = Vec::new();
+
+        for mut dg in file.get_data_groups() {
+            let mut observers = Vec::new();
+            for cg in dg.get_channel_groups() {
+                if cg.get_bus_type() == BusType::Can as u8 {
+                    observers.push((cg.get_name(), create_can_bus_observer(self, &dg, &cg)?));
+                }
+            }
+            if observers.is_empty() {
+                continue;
+            }
+
+            let read = self.read_data(&mut dg);
+            if read.is_ok() {
+                messages.extend(observers.iter().map(|(name, observer)| {
+                    let frames = observer
+                        .get_all_can_messages()
+                        .iter()
+                        .flatten()
+                        .map(Frame::from)
+                        .collect();
+                    (name.clone(), frames)
+                }));
+            }
+            drop(observers);
+            dg.clear_data();
+            read?;
+        }
+
+        Ok(messages)
+    }
+
+    /// Read the whole file into an owned [`MdfDocument`]
+    ///
+    /// Reads each data group in turn as in [`MdfReader::for_each_data_group`],
+    /// copying the header, the channel descriptions and all samples. The
+    /// document doesn't borrow the reader so it can be kept after the reader is
+    /// dropped, at the cost of holding every sample of the file in memory. The
+    /// file structure must have been read first with
+    /// [`MdfReader::read_everything_but_data`].
+    pub fn read_all(&mut self) -> Result<MdfDocument> {
+        let header = self.get_header().ok_or(MdfError::NullPointer)?;
+        let mut document = MdfDocument {
+            header: HeaderInfo::from(&header),
+            data_groups: Vec::new(),
+        };
+
+        self.for_each_data_group(|dg, observers| {
+            let mut observers = observers.iter();
+            let channel_groups = dg
+                .get_channel_groups()
+                .iter()
+                .map(|cg| ChannelGroupData {
+                    name: cg.get_name(),
+                    description: cg.get_description(),
+                    channels: cg
+                        .get_channels()
+                        .iter()
+                        .zip(observers.by_ref())
+                        .map(|(cn, observer)| ChannelData {
+                            info: ChannelInfo::from(cn),
+                            samples: observer.take_samples(),
+                        })
+                        .collect(),
+                })
+                .collect();
+            document.data_groups.push(DataGroupData {
+                description: dg.get_description(),
+                channel_groups,
+            });
+            Ok(())
+        })?;
+
+        Ok(document)
+    }
+
+    /// Read the data of a single data group selected by name
+    ///
+    /// The data group is matched on its description or on the name, or source
+    /// name, of one of its channel groups, e.g. `CAN_DataFrame` in a bus logger
+    /// file. Only this data group's data is read so observers attached to other
+    /// groups remain empty. The file structure must have been read first with
+    /// [`MdfReader::read_everything_but_data`].
+    pub fn read_data_group_by_name(&mut self, name: &str) -> Result<DataGroup> {
+        let file = self.get_file().ok_or(MdfError::NullPointer)?;
+        let mut group = file
+            .get_data_groups()
+            .into_iter()
+            .find(|dg| {
+                dg.get_description() == name
+                    || dg.get_channel_groups().iter().any(|cg| {
+                        cg.get_name() == name
+                            || cg
+                                .get_source_information()
+                                .is_some_and(|si| si.get_name() == name)
+                    })
+            })
+            .ok_or_else(|| MdfError::NotFound(name.to_string()))?;
+
+        self.read_data(&mut group)?;
+        Ok(group)
+    }
+
+    /// Gets all attachments of the file and its header.
+    ///
+    /// Merges [`MdfFileRef::get_attachments`] and
+    /// [`MdfHeaderRef::get_attachments`], listing each attachment once by its
+    /// index (file position).
+    pub fn all_attachments(&self) -> Vec<AttachmentRef<'_>> {
+        let mut attachments: Vec<AttachmentRef<'_>> = Vec::new();
+        let mut add = |list: Vec<AttachmentRef<'_>>| {
+            for attachment in list {
+                if !attachments
+                    .iter()
+                    .any(|existing| existing.get_index() == attachment.get_index())
+                {
+                    attachments.push(AttachmentRef::new(attachment.inner));
+                }
+            }
+        };
+
+        if let Some(file) = self.get_file() {
+            add(file.get_attachments());
+        }
+        if let Some(header) = self.get_header() {
+            add(header.get_attachments());
+        }
+        attachments
+    }
+
+    /// Export the data of an attachment to the destination file
+    ///
+    /// Embedded attachments are extracted (and decompressed) by mdflib, which
+    /// also checks the stored MD5 checksum if one is present.
+    pub fn export_attachment_data<P: AsRef<Path>>(
+        &mut self,
+        attachment: &AttachmentRef,
+        dest_file: P,
+    ) -> Result<()> {
+        let dest_str = crate::util::path_to_utf8(dest_file.as_ref())?;
+        let c_dest = CString::new(dest_str)?;
+
         unsafe {
-            if MdfReaderReadData(self.inner, group.inner) {
+            if MdfReaderExportAttachmentData(self.inner, attachment.inner, c_dest.as_ptr()) {
                 Ok(())
             } else {
                 Err(MdfError::DataRead)
             }
         }
     }
+
+    /// Verify the structural integrity of the file
+    ///
+    /// Reads the file structure, checks the finalization flag, follows the
+    /// data group, channel group and channel links, reads the data blocks of
+    /// each data group and, with the `md5` feature, validates the MD5 checksum
+    /// of embedded attachments. Inconsistencies are collected in the returned [`VerifyReport`] rather than
+    /// returned as errors. Data read during verification is cleared again.
+    pub fn verify(&mut self) -> Result<VerifyReport> {
+        let mut report = VerifyReport::default();
+
+        if !self.is_ok() {
+            report
+                .issues
+                .push("Reader is not in a valid state".to_string());
+            return Ok(report);
+        }
+
+        if let Err(e) = self.read_everything_but_data() {
+            report
+                .issues
+                .push(format!("Failed to read file structure: {e}"));
+            return Ok(report);
+        }
+
+        report.finalized = self.is_finalized();
+        if !report.finalized {
+            report.issues.push("File is not finalized".to_string());
+        }
+
+        let file = match self.get_file() {
+            Some(file) => file,
+            None => {
+                report.issues.push("File object is missing".to_string());
+                return Ok(report);
+            }
+        };
+
+        let mut data_groups = file.get_data_groups();
+        report.data_group_count = data_groups.len();
+        if report.data_group_count != file.get_data_group_count() {
+            report.issues.push(format!(
+                "Data group list has {} valid links, expected {}",
+                report.data_group_count,
+                file.get_data_group_count()
+            ));
+        }
+
+        for (dg_index, dg) in data_groups.iter_mut().enumerate() {
+            for cg_index in 0..dg.get_channel_group_count() {
+                let Some(cg) = dg.get_channel_group_by_index(cg_index) else {
+                    report.issues.push(format!(
+                        "DG {dg_index}: channel group {cg_index} link is broken"
+                    ));
+                    continue;
+                };
+                for cn_index in 0..cg.get_channel_count() {
+                    if cg.get_channel_by_index(cn_index).is_none() {
+                        report.issues.push(format!(
+                            "DG {dg_index}, CG {cg_index}: channel {cn_index} link is broken"
+                        ));
+                    }
+                }
+            }
+
+            if self.read_data(dg).is_err() {
+                report
+                    .issues
+                    .push(format!("DG {dg_index}: failed to read data blocks"));
+            }
+            dg.clear_data();
+        }
+
+        #[cfg(feature = "md5")]
+        for attachment in file.get_attachments() {
+            if !attachment.get_embedded() || attachment.get_md5().is_none() {
+                continue;
+            }
+            report.attachments_checked += 1;
+
+            match attachment.check_md5(self) {
+                Ok(true) => {}
+                Ok(false) => report.issues.push(format!(
+                    "Attachment '{}': MD5 checksum mismatch",
+                    attachment.get_filename()
+                )),
+                Err(e) => report.issues.push(format!(
+                    "Attachment '{}': failed to read data: {e}",
+                    attachment.get_filename()
+                )),
+            }
+        }
+
+        Ok(report)
+    }
 }
 
 impl Drop for MdfReader {
@@ -4,9 +4,10 @@
 //! provides access to the source information's metadata, such as its name,
 //! description, and path.
 
-use crate::error::Result;
+use crate::error::{MdfError, Result};
 use crate::metadata::{MetaData, MetaDataRef};
 use mdflib_sys as ffi;
+use mdflib_sys::{BusType, SourceType};
 use std::ffi::{CStr, CString};
 use std::marker::PhantomData;
 use std::ops::Deref;
@@ -101,6 +102,18 @@ impl<'a> SourceInformationRef<'a> {
         unsafe { ffi::SourceInformationGetBus(self.inner) }
     }
 
+    /// Gets the type of the source information as [`SourceType`].
+    pub fn get_type_enum(&self) -> Result<SourceType> {
+        let value = self.get_type();
+        SourceType::try_from(value).map_err(|_| MdfError::InvalidEnumValue("SourceType", value))
+    }
+
+    /// Gets the bus type of the source information as [`BusType`].
+    pub fn get_bus_enum(&self) -> Result<BusType> {
+        let value = self.get_bus();
+        BusType::try_from(value).map_err(|_| MdfError::InvalidEnumValue("BusType", value))
+    }
+
     /// Gets the flags of the source information.
     pub fn get_flags(&self) -> u8 {
         unsafe { ffi::SourceInformationGetFlags(self.inner) }
@@ -176,6 +189,28 @@ impl<'a> SourceInformation<'a> {
         }
     }
 
+    /// Sets the type of the source information from a [`SourceType`].
+    pub fn set_type_enum(&mut self, source_type: SourceType) {
+        self.set_type(source_type as u8);
+    }
+
+    /// Sets the bus type of the source information from a [`BusType`].
+    pub fn set_bus_enum(&mut self, bus: BusType) {
+        self.set_bus(bus as u8);
+    }
+
+    /// Configures the source information as a CAN bus source.
+    ///
+    /// Sets the name and path (e.g. "CAN1"), the type to [`SourceType::Bus`] and
+    /// the bus to [`BusType::Can`], as used by bus logger channel groups.
+    pub fn configure_can(&mut self, name: &str, path: &str) -> Result<()> {
+        self.set_name(name)?;
+        self.set_path(path)?;
+        self.set_type_enum(SourceType::Bus);
+        self.set_bus_enum(BusType::Can);
+        Ok(())
+    }
+
     /// Sets the flags of the source information.
     pub fn set_flags(&mut self, flags: u8) {
         unsafe {
@@ -183,6 +218,17 @@ impl<'a> SourceInformation<'a> {
         }
     }
 
+    /// Copies the name, description, path, type, bus and flags of `src`.
+    pub fn copy_from(&mut self, src: &SourceInformationRef) -> Result<()> {
+        self.set_name(&src.get_name())?;
+        self.set_description(&src.get_description())?;
+        self.set_path(&src.get_path())?;
+        self.set_type(src.get_type());
+        self.set_bus(src.get_bus());
+        self.set_flags(src.get_flags());
+        Ok(())
+    }
+
     /// Creates metadata for the source information.
     pub fn create_metadata(&mut self) -> Option<MetaData<'a>> {
         unsafe {
@@ -0,0 +1,203 @@
+//! Copying MDF files into a new file
+//!
+//! [`transcode`] reads the structure and samples of a file and writes them
+//! through a writer of the target type, for example to convert MDF3 files to
+//! MDF4 or back. [`merge_files`] combines several files into one.
+
+use crate::{
+    channel::Channel,
+    channelgroup::ChannelGroup,
+    channelobserver::{create_channel_group_observers, ChannelObserver, TypedSamples},
+    error::{MdfError, Result},
+    file::MdfFileRef,
+    reader::MdfReader,
+    writer::{MdfWriter, MdfWriterType},
+};
+use mdflib_sys as ffi;
+use std::path::Path;
+
+/// Samples of one source channel group and the channels they are written to
+struct GroupCopy {
+    target: ChannelGroup,
+    channels: Vec<(*mut ffi::IChannel, TypedSamples, Vec<bool>)>,
+    /// Absolute sample times in nanoseconds since the epoch
+    times: Vec<u64>,
+}
+
+/// Copies an MDF file into a new file written by a writer of type `target`.
+///
+/// The header information and file histories, data groups, channel groups,
+/// channels with their conversions and source information are copied and all
+/// samples are rewritten in time order, with the raw channel values so the
+/// copied conversions apply as in the source. Attachments, events and bus
+/// logging configurations are not copied. All samples of the source are held in
+/// memory while writing.
+pub fn transcode<P: AsRef<Path>>(src: P, dst: P, target: MdfWriterType) -> Result<()> {
+    let mut reader = MdfReader::new(src)?;
+    reader.read_everything_but_data()?;
+    let file = reader.get_file().ok_or(MdfError::NullPointer)?;
+    let src_header = file.get_header();
+
+    let mut writer = MdfWriter::new(target, dst)?;
+    {
+        let mut header = writer.get_header().ok_or(MdfError::NullPointer)?;
+        header.copy_from(&src_header)?;
+    }
+
+    let start_time = src_header.get_start_time().max(1);
+    let groups = copy_groups(&mut reader, &file, &mut writer, start_time)?;
+    write_samples(&mut writer, &groups, start_time)
+}
+
+/// Merges several MDF files into a new MDF4 file.
+///
+/// The data groups of every input are appended to the output in order, with
+/// the header of the first input. Sample times keep their absolute time so
+/// the measurements share the earliest start time of the inputs as a common
+/// base. What is copied from each input is the same as for [`transcode`].
+pub fn merge_files<P: AsRef<Path>>(inputs: &[P], output: P) -> Result<()> {
+    if inputs.is_empty() {
+        return Err(MdfError::NotFound("input files to merge".to_string()));
+    }
+
+    let mut writer = MdfWriter::new(MdfWriterType::Mdf4Basic, output)?;
+    let mut groups = Vec::new();
+    let mut start_time = u64::MAX;
+
+    for (index, input) in inputs.iter().enumerate() {
+        let mut reader = MdfReader::new(input)?;
+        reader.read_everything_but_data()?;
+        let file = reader.get_file().ok_or(MdfError::NullPointer)?;
+        let src_header = file.get_header();
+        if index == 0 {
+            let mut header = writer.get_header().ok_or(MdfError::NullPointer)?;
+            header.copy_from(&src_header)?;
+        }
+
+        let input_start = src_header.get_start_time().max(1);
+        start_time = start_time.min(input_start);
+        groups.extend(copy_groups(&mut reader, &file, &mut writer, input_start)?);
+    }
+
+    write_samples(&mut writer, &groups, start_time)
+}
+
+/// Creates the data groups of `file` in `writer` and reads their samples
+///
+/// Sample times are made absolute with `start_time`, the start time of the
+/// source measurement.
+fn copy_groups(
+    reader: &mut MdfReader,
+    file: &MdfFileRef,
+    writer: &mut MdfWriter,
+    start_time: u64,
+) -> Result<Vec<GroupCopy>> {
+    let mut groups = Vec::new();
+    for mut src_dg in file.get_data_groups() {
+        let mut dst_dg = writer.create_data_group().ok_or(MdfError::NullPointer)?;
+        dst_dg.set_description(&src_dg.get_description());
+
+        let mut observed: Vec<(ChannelGroup, Vec<(*mut ffi::IChannel, ChannelObserver)>)> =
+            Vec::new();
+        for src_cg in src_dg.get_channel_groups() {
+            let dst_cg = dst_dg.copy_channel_group(&src_cg)?;
+
+            // Safety: the pointers come from the reader's file which outlives the observers
+            let observers =
+                unsafe { create_channel_group_observers(src_dg.as_ptr(), src_cg.as_ptr())? };
+            let mut channels = Vec::new();
+            for (index, observer) in observers.into_iter().enumerate() {
+                let dst_cn = dst_cg
+                    .get_channel_by_index(index)
+                    .ok_or(MdfError::IndexOutOfBounds(index))?;
+                channels.push((dst_cn.inner as *mut ffi::IChannel, observer));
+            }
+            observed.push((dst_cg, channels));
+        }
+
+        reader.read_data(&mut src_dg)?;
+
+        for (target, channels) in observed {
+            let times = channels
+                .first()
+                .map(|(_, observer)| {
+                    // Groups without a master keep their order, one sample per second
+                    let nof_samples = observer.get_nof_samples();
+                    observer
+                        .take_samples()
+                        .times
+                        .unwrap_or_else(|| (0..nof_samples).map(|sample| sample as f64).collect())
+                })
+                .unwrap_or_default()
+                .into_iter()
+                .map(|time| start_time + (time.max(0.0) * 1e9) as u64)
+                .collect();
+            let channels = channels
+                .into_iter()
+                .map(|(channel, observer)| {
+                    let valid = (0..observer.get_nof_samples())
+                        .map(|sample| observer.is_valid(sample))
+                        .collect();
+                    (channel, observer.read_typed(), valid)
+                })
+                .collect();
+            groups.push(GroupCopy {
+                target,
+                channels,
+                times,
+            });
+        }
+        src_dg.clear_data();
+    }
+    Ok(groups)
+}
+
+/// Writes the samples of all groups interleaved by time and finalizes the file
+fn write_samples(writer: &mut MdfWriter, groups: &[GroupCopy], start_time: u64) -> Result<()> {
+    let mut samples: Vec<(u64, usize, usize)> = groups
+        .iter()
+        .enumerate()
+        .flat_map(|(group, copy)| {
+            copy.times
+                .iter()
+                .enumerate()
+                .map(move |(sample, time)| (*time, group, sample))
+        })
+        .collect();
+    samples.sort_by_key(|sample| sample.0);
+
+    if !writer.init_measurement() {
+        return Err(MdfError::MeasurementInit);
+    }
+    writer.start_measurement(start_time);
+
+    let mut stop_time = start_time;
+    for (time, group, sample) in samples {
+        let copy = &groups[group];
+        for (channel, values, valid) in &copy.channels {
+            let mut channel = Channel::new(*channel);
+            let valid = valid.get(sample).copied().unwrap_or(false);
+            match values {
+                TypedSamples::U64(values) => channel.set_channel_value_u64(values[sample], valid),
+                TypedSamples::I64(values) => channel.set_channel_value_i64(values[sample], valid),
+                TypedSamples::F64(values) => channel.set_channel_value_f64(values[sample], valid),
+                TypedSamples::String(values) => {
+                    channel.set_channel_value_str(&values[sample], valid)?
+                }
+                TypedSamples::Bytes(values) => {
+                    channel.set_channel_value_bytes(&values[sample], valid)
+                }
+            }
+        }
+
+        stop_time = stop_time.max(time);
+        writer.save_sample(&copy.target, time);
+    }
+
+    writer.stop_measurement(stop_time);
+    if writer.finalize_measurement() {
+        Ok(())
+    } else {
+        Err(MdfError::MeasurementFinalize)
+    }
+}
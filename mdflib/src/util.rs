@@ -0,0 +1,235 @@
+//! Internal helpers shared by the wrapper types
+
+use crate::error::{MdfError, Result};
+use std::ffi::CStr;
+#[cfg(feature = "md5")]
+use std::fs::OpenOptions;
+use std::os::raw::c_char;
+use std::path::Path;
+#[cfg(feature = "md5")]
+use std::path::PathBuf;
+#[cfg(feature = "md5")]
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Converts a path to the UTF-8 string mdflib expects for file names.
+///
+/// Paths that aren't valid Unicode, e.g. with unpaired surrogates on Windows,
+/// return [`MdfError::InvalidPath`] instead of panicking.
+pub(crate) fn path_to_utf8(path: &Path) -> Result<String> {
+    path.to_str()
+        .map(str::to_owned)
+        .ok_or_else(|| MdfError::InvalidPath(path.to_path_buf()))
+}
+
+/// Temporary file with a unique name that is removed when dropped.
+///
+/// Used as the destination of data mdflib can only export to a file.
+#[cfg(feature = "md5")]
+pub(crate) struct TempFile {
+    path: PathBuf,
+}
+
+#[cfg(feature = "md5")]
+impl TempFile {
+    /// Creates an empty file in the temporary directory.
+    ///
+    /// The name is made unique within the process by a counter and across
+    /// processes by the process ID, and the file is created exclusively so a
+    /// left over file of the same name is never reused.
+    pub(crate) fn new(prefix: &str) -> Result<Self> {
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+        loop {
+            let path = std::env::temp_dir().join(format!(
+                "{prefix}-{}-{}.bin",
+                std::process::id(),
+                COUNTER.fetch_add(1, Ordering::Relaxed)
+            ));
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(_) => return Ok(Self { path }),
+                Err(err) if err.kind() == std::io::ErrorKind::AlreadyExists => continue,
+                Err(err) => return Err(err.into()),
+            }
+        }
+    }
+
+    /// Gets the path of the file.
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+#[cfg(feature = "md5")]
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// Size of the stack buffer used by [`read_cstring`]
+const STACK_CAPACITY: usize = 256;
+
+/// Reads a string from an mdflib getter using a single FFI call in the common case.
+///
+/// `f` follows the C wrapper convention: it is called with a buffer and its
+/// length, copies at most `length - 1` characters plus a null terminator and
+/// returns the full length of the string. A buffer of `initial_cap` bytes (on
+/// the stack if it fits) is tried first and the getter is only called again
+/// with an exact size buffer if the string was truncated.
+pub(crate) fn read_cstring<F>(initial_cap: usize, f: F) -> String
+where
+    F: FnMut(*mut c_char, usize) -> usize,
+{
+    String::from_utf8_lossy(&read_cbytes(initial_cap, f)).into_owned()
+}
+
+/// Reads the raw bytes of a string from an mdflib getter, see [`read_cstring`]
+pub(crate) fn read_cbytes<F>(initial_cap: usize, mut f: F) -> Vec<u8>
+where
+    F: FnMut(*mut c_char, usize) -> usize,
+{
+    let mut stack = [0 as c_char; STACK_CAPACITY];
+    let mut heap;
+    let buf: &mut [c_char] = if initial_cap <= STACK_CAPACITY {
+        &mut stack[..initial_cap.max(1)]
+    } else {
+        heap = vec![0 as c_char; initial_cap];
+        &mut heap
+    };
+
+    let len = f(buf.as_mut_ptr(), buf.len());
+    if len == 0 {
+        return Vec::new();
+    }
+    if len < buf.len() {
+        return unsafe { CStr::from_ptr(buf.as_ptr()) }.to_bytes().to_vec();
+    }
+
+    let mut buf = vec![0 as c_char; len + 1]; // For null terminator
+    f(buf.as_mut_ptr(), buf.len());
+    unsafe { CStr::from_ptr(buf.as_ptr()) }.to_bytes().to_vec()
+}
+
+/// Decodes text read from an MDF file.
+///
+/// MDF4 text is always UTF-8 but MDF3 files store text in the local code page
+/// of the writing tool, in practice Latin-1 or Windows-1252, which mdflib
+/// passes through unchanged. Text that is valid UTF-8 is returned as is. With
+/// the `encoding` feature other text of an MDF3 file (`mdf3`) is decoded as
+/// Windows-1252, otherwise invalid sequences are replaced with `U+FFFD`.
+#[cfg_attr(not(feature = "encoding"), allow(unused_variables))]
+pub(crate) fn decode_text(bytes: &[u8], mdf3: bool) -> String {
+    match std::str::from_utf8(bytes) {
+        Ok(text) => text.to_owned(),
+        #[cfg(feature = "encoding")]
+        Err(_) if mdf3 => encoding_rs::WINDOWS_1252
+            .decode_without_bom_handling(bytes)
+            .0
+            .into_owned(),
+        Err(_) => String::from_utf8_lossy(bytes).into_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_path_to_utf8() {
+        let path = Path::new("Messung_äöü_测试.mf4");
+        assert_eq!(path_to_utf8(path).unwrap(), "Messung_äöü_测试.mf4");
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::ffi::OsStrExt;
+            let path = Path::new(std::ffi::OsStr::from_bytes(b"bad_\xff.mf4"));
+            assert!(matches!(path_to_utf8(path), Err(MdfError::InvalidPath(_))));
+        }
+    }
+
+    #[cfg(feature = "md5")]
+    #[test]
+    fn test_temp_file_unique() {
+        let first = TempFile::new("mdflib-test").unwrap();
+        let second = TempFile::new("mdflib-test").unwrap();
+        assert_ne!(first.path(), second.path());
+        assert!(first.path().is_file());
+
+        let path = first.path().to_path_buf();
+        drop(first);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_decode_text() {
+        assert_eq!(decode_text(b"EngineSpeed", true), "EngineSpeed");
+        assert_eq!(decode_text("Température".as_bytes(), true), "Température");
+        // MDF4 text is UTF-8, so invalid sequences are never reinterpreted
+        assert_eq!(decode_text(b"Temp\xe9rature", false), "Temp\u{FFFD}rature");
+        #[cfg(feature = "encoding")]
+        {
+            assert_eq!(decode_text(b"Temp\xe9rature", true), "Température");
+            assert_eq!(decode_text(b"\xb0C", true), "°C");
+            assert_eq!(decode_text(b"\x80", true), "€");
+        }
+        #[cfg(not(feature = "encoding"))]
+        assert_eq!(decode_text(b"Temp\xe9rature", true), "Temp\u{FFFD}rature");
+    }
+
+    /// Mimics a C wrapper string getter, counting the number of calls
+    fn getter<'a>(
+        value: &'a str,
+        calls: &'a mut usize,
+    ) -> impl FnMut(*mut c_char, usize) -> usize + 'a {
+        move |dest, max_length| {
+            *calls += 1;
+            if !dest.is_null() && max_length > 0 {
+                let copy_length = value.len().min(max_length - 1);
+                unsafe {
+                    std::ptr::copy_nonoverlapping(value.as_ptr().cast(), dest, copy_length);
+                    *dest.add(copy_length) = 0;
+                }
+            }
+            value.len()
+        }
+    }
+
+    /// Two call length probe used by the getters before `read_cstring`
+    fn read_probe(value: &str) -> String {
+        let mut calls = 0;
+        let mut f = getter(value, &mut calls);
+        let mut len = f(std::ptr::null_mut(), 0);
+        if len == 0 {
+            return String::new();
+        }
+        len += 1;
+        let mut buf = vec![0 as c_char; len];
+        f(buf.as_mut_ptr(), len);
+        unsafe { CStr::from_ptr(buf.as_ptr()) }
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    #[test]
+    fn test_read_cstring_matches_probe() {
+        let long = "x".repeat(1000);
+        for value in ["", "a", "EngineSpeed", "°C", long.as_str()] {
+            let mut calls = 0;
+            let result = read_cstring(64, getter(value, &mut calls));
+            assert_eq!(result, read_probe(value));
+            assert_eq!(calls, if value.len() < 64 { 1 } else { 2 });
+        }
+    }
+
+    #[test]
+    fn test_read_cstring_exact_fit() {
+        // A string of exactly the buffer size needs room for the terminator
+        let value = "y".repeat(16);
+        let mut calls = 0;
+        assert_eq!(read_cstring(16, getter(&value, &mut calls)), value);
+        assert_eq!(calls, 2);
+
+        let mut calls = 0;
+        assert_eq!(read_cstring(1024, getter(&value, &mut calls)), value);
+        assert_eq!(calls, 1);
+    }
+}
@@ -0,0 +1,40 @@
+//! Version of the linked mdflib C++ library
+//!
+//! The crate can either build the bundled mdflib source or link a system
+//! installed library, so the version is detected at build time and exposed here
+//! to help distinguish the two in bug reports.
+
+use crate::util::read_cstring;
+use mdflib_sys as ffi;
+
+/// Gets the version of the linked mdflib library, e.g. "2.3.0".
+///
+/// Returns "unknown" if the version could not be detected at build time, e.g.
+/// when a system library was found without pkg-config and `MDFLIB_VERSION`
+/// was not set in the build environment.
+pub fn mdflib_version() -> String {
+    read_cstring(32, |buf, len| unsafe {
+        ffi::MdfGetLibraryVersion(buf, len)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mdflib_version() {
+        let version = mdflib_version();
+        assert!(!version.is_empty());
+        // Manual discovery of a system library has no version metadata
+        if version == "unknown" {
+            return;
+        }
+
+        let parts: Vec<&str> = version.split('.').collect();
+        assert!(parts.len() >= 2, "Unexpected version: {version}");
+        for part in parts {
+            assert!(part.parse::<u32>().is_ok(), "Unexpected version: {version}");
+        }
+    }
+}
@@ -45,37 +45,61 @@
 //! }
 //! ```
 use crate::{
+    attachment::AttachmentRef,
     canmessage::CanMessageRef,
-    channelgroup::ChannelGroupRef,
+    channel::{Channel, ChannelDataType, SampleValue},
+    channelgroup::{ChannelGroup, ChannelGroupRef},
     datagroup::DataGroup,
     error::{MdfError, Result},
+    event::Event,
     file::MdfFile,
+    filehistory::FileHistoryEntry,
     header::MdfHeader,
+    reader::MdfReader,
 };
 use mdflib_sys::*;
 use std::ffi::CString;
-use std::path::Path;
+use std::io::{BufRead, BufReader, Read};
+use std::ops::{Deref, DerefMut};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 pub use mdflib_sys::MdfWriterType;
 
+/// Measurement state of a writer, tracked as mdflib doesn't expose it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MeasurementState {
+    Created,
+    Initialized,
+    Started,
+    Stopped,
+    Finalized,
+}
+
 /// Safe wrapper around mdflib's MdfWriter
 pub struct MdfWriter {
     inner: *mut mdflib_sys::MdfWriter,
+    state: MeasurementState,
+    path: PathBuf,
 }
 
 impl MdfWriter {
     /// Create a new MDF writer for the specified file
     pub fn new<P: AsRef<Path>>(writer_type: MdfWriterType, path: P) -> Result<Self> {
-        let path_str = path.as_ref().to_str().unwrap();
-        let c_path = CString::new(path_str)?;
+        let path_str = crate::util::path_to_utf8(path.as_ref())?;
+        let c_path = CString::new(path_str.as_str())?;
 
         unsafe {
             let writer = MdfWriterInit(writer_type, c_path.as_ptr());
             if writer.is_null() {
-                return Err(MdfError::FileOpen(path_str.to_string()));
+                return Err(MdfError::FileOpen(path_str));
             }
 
-            Ok(MdfWriter { inner: writer })
+            Ok(MdfWriter {
+                inner: writer,
+                state: MeasurementState::Created,
+                path: path.as_ref().to_path_buf(),
+            })
         }
     }
 
@@ -103,6 +127,15 @@ impl MdfWriter {
         }
     }
 
+    /// Adds a file history entry to the header.
+    ///
+    /// Only the fields set in `entry` are applied, the time defaults to now.
+    pub fn add_file_history(&mut self, entry: FileHistoryEntry) -> Result<()> {
+        let mut header = self.get_header().ok_or(MdfError::NullPointer)?;
+        let mut history = header.create_file_history().ok_or(MdfError::NullPointer)?;
+        history.apply(&entry)
+    }
+
     /// Check if the file is new
     pub fn is_file_new(&self) -> bool {
         unsafe { MdfWriterIsFileNew(self.inner) }
@@ -114,10 +147,18 @@ impl MdfWriter {
     }
 
     /// Set compress data flag
+    ///
+    /// Data is only compressed if [`MdfWriter::compression_available`] returns
+    /// true, otherwise it's silently written uncompressed.
     pub fn set_compress_data(&mut self, compress: bool) {
         unsafe { MdfWriterSetCompressData(self.inner, compress) }
     }
 
+    /// Check if the linked mdflib was built with zlib and can compress data
+    pub fn compression_available() -> bool {
+        unsafe { MdfWriterCompressionAvailable() }
+    }
+
     /// Get pre-trigger time
     pub fn get_pre_trig_time(&self) -> f64 {
         unsafe { MdfWriterGetPreTrigTime(self.inner) }
@@ -148,6 +189,18 @@ impl MdfWriter {
         unsafe { MdfWriterSetBusType(self.inner, bus_type) }
     }
 
+    /// Get the storage type of the bus logging data bytes
+    pub fn get_storage_type(&self) -> MdfStorageType {
+        unsafe { MdfWriterGetStorageType(self.inner) }
+    }
+
+    /// Set the storage type of the bus logging data bytes
+    ///
+    /// Must be set before [`MdfWriter::create_bus_log_configuration`].
+    pub fn set_storage_type(&mut self, storage: MdfStorageType) {
+        unsafe { MdfWriterSetStorageType(self.inner, storage) }
+    }
+
     /// Create bus log configuration
     pub fn create_bus_log_configuration(&mut self) -> bool {
         unsafe { MdfWriterCreateBusLogConfiguration(self.inner) }
@@ -165,9 +218,233 @@ impl MdfWriter {
         }
     }
 
+    /// Embeds a description file, such as a DBC or A2L, in the MDF file.
+    ///
+    /// The attachment is created in the header with `path` as its filename
+    /// and `file_type` as its MIME type, see
+    /// [`crate::AttachmentFileType::as_str`] for common types. The file is read
+    /// and embedded, with its MD5 checksum, when the MDF file is written, so it
+    /// must exist until then. Not supported by MDF3 writers.
+    pub fn attach_description_file<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        file_type: &str,
+    ) -> Result<AttachmentRef<'_>> {
+        let path = path.as_ref();
+        let path_str = crate::util::path_to_utf8(path)?;
+        if !path.is_file() {
+            return Err(MdfError::FileOpen(path_str));
+        }
+
+        let mut header = self.get_header().ok_or(MdfError::NullPointer)?;
+        let mut attachment = header.create_attachment().ok_or(MdfError::NullPointer)?;
+        attachment.set_filename(&path_str)?;
+        attachment.set_file_type(file_type)?;
+        attachment.set_embedded(true);
+        Ok(AttachmentRef::new(attachment.inner))
+    }
+
+    /// Adds an event at `time_ns` during a running measurement.
+    ///
+    /// Time is absolute time in nanoseconds since the epoch, as for
+    /// [`MdfWriter::save_sample`]. The event is created in the header and
+    /// synchronized to time, with its sync value the offset from the start of
+    /// the measurement in nanoseconds, so it reads back with
+    /// [`crate::MdfHeaderRef::events_in_range`]. Returns
+    /// [`MdfError::InvalidWriterState`] unless the measurement is started.
+    pub fn add_event_at(
+        &mut self,
+        time_ns: u64,
+        name: &str,
+        event_type: EventType,
+    ) -> Result<Event<'_>> {
+        if self.state != MeasurementState::Started {
+            return Err(MdfError::InvalidWriterState("measurement not started"));
+        }
+
+        let offset = time_ns as i128 - self.get_start_time() as i128;
+        let mut header = self.get_header().ok_or(MdfError::NullPointer)?;
+        let event = header.create_event().ok_or(MdfError::NullPointer)?;
+        let mut event = Event::new(event.inner);
+        event.set_name(name)?;
+        event.set_event_type(event_type);
+        event.set_sync_type(SyncType::SyncTime);
+        event.set_sync_value(offset as i64);
+        event.set_sync_factor(1e-9);
+        Ok(event)
+    }
+
     /// Initialize measurement
     pub fn init_measurement(&mut self) -> bool {
-        unsafe { MdfWriterInitMeasurement(self.inner) }
+        let ok = unsafe { MdfWriterInitMeasurement(self.inner) };
+        if ok {
+            self.state = MeasurementState::Initialized;
+        }
+        ok
+    }
+
+    /// Start a measurement that is stopped and finalized when the guard drops
+    ///
+    /// Initializes the measurement if needed and starts it at `start_time`
+    /// (nanoseconds since the epoch). Call [`MeasurementGuard::commit`] to stop
+    /// and finalize explicitly and check the result; otherwise the measurement
+    /// is stopped at the last sample time saved through the guard and
+    /// finalized on drop, so the file is complete even on early returns.
+    /// Returns [`MdfError::InvalidWriterState`] if the measurement has already
+    /// been started.
+    pub fn measurement_guard(&mut self, start_time: u64) -> Result<MeasurementGuard<'_>> {
+        match self.state {
+            MeasurementState::Created => {
+                if !self.init_measurement() {
+                    return Err(MdfError::MeasurementInit);
+                }
+            }
+            MeasurementState::Initialized => {}
+            MeasurementState::Started | MeasurementState::Stopped => {
+                return Err(MdfError::InvalidWriterState("measurement already started"));
+            }
+            MeasurementState::Finalized => {
+                return Err(MdfError::InvalidWriterState("measurement finalized"));
+            }
+        }
+        self.start_measurement(start_time);
+        Ok(MeasurementGuard {
+            writer: self,
+            last_time: start_time,
+            committed: false,
+        })
+    }
+
+    /// Configure the time master channel of a channel group
+    ///
+    /// Sets the sync type of the group's master channel to time and its unit
+    /// to `unit`, creating a double precision master channel named "Time"
+    /// first if the group has none. mdflib fills the master channel from the
+    /// sample times passed to [`MdfWriter::save_sample`]. Must be called
+    /// before [`MdfWriter::init_measurement`].
+    pub fn configure_time_master(&mut self, group: &mut ChannelGroup, unit: &str) -> Result<()> {
+        if self.state != MeasurementState::Created {
+            return Err(MdfError::InvalidWriterState(
+                "measurement already initialized",
+            ));
+        }
+
+        let master = group.get_channels().iter().position(|cn| {
+            let channel_type = cn.get_type();
+            channel_type == ChannelType::Master as u8
+                || channel_type == ChannelType::VirtualMaster as u8
+        });
+        let mut master = match master {
+            Some(index) => group.get_channels_mut().swap_remove(index),
+            None => {
+                let mut master = group.create_channel().ok_or(MdfError::NullPointer)?;
+                master.set_name("Time");
+                master.set_type(ChannelType::Master as u8);
+                master.set_data_type(ChannelDataType::FloatLe as u8);
+                master.set_data_bytes(8);
+                master
+            }
+        };
+        master.set_sync(ChannelSyncType::Time as u8);
+        master.set_unit(unit);
+        Ok(())
+    }
+
+    /// Write a CSV file as a new data group in a complete measurement
+    ///
+    /// The first row holds the channel names. `time_column` becomes the master
+    /// channel and holds the time of each row in seconds from the start of the
+    /// measurement; every other column becomes a double precision channel.
+    /// Empty or non-numeric cells are stored as invalid samples. The
+    /// measurement is started now and run from
+    /// [`MdfWriter::init_measurement`] to [`MdfWriter::finalize_measurement`],
+    /// so the writer must not have been initialized before.
+    ///
+    /// All rows are read and checked before anything is written. Returns
+    /// [`MdfError::InvalidFormat`] if a time is missing, not a number,
+    /// negative or not finite, leaving the writer unchanged.
+    pub fn import_csv<R: Read>(&mut self, reader: R, time_column: &str) -> Result<()> {
+        if self.state != MeasurementState::Created {
+            return Err(MdfError::InvalidWriterState(
+                "measurement already initialized",
+            ));
+        }
+
+        let mut lines = BufReader::new(reader).lines();
+        let header = lines.next().ok_or(MdfError::InvalidFormat)??;
+        let names = split_csv_line(&header);
+        let time_index = names
+            .iter()
+            .position(|name| name == time_column)
+            .ok_or_else(|| MdfError::NotFound(time_column.to_string()))?;
+
+        let mut rows = Vec::new();
+        for line in lines {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let cells = split_csv_line(&line);
+            let time = cells
+                .get(time_index)
+                .and_then(|cell| cell.parse::<f64>().ok())
+                .filter(|time| time.is_finite() && *time >= 0.0)
+                .ok_or(MdfError::InvalidFormat)?;
+            let values: Vec<Option<f64>> = (0..names.len())
+                .map(|index| cells.get(index).and_then(|cell| cell.parse().ok()))
+                .collect();
+            rows.push((time, values));
+        }
+
+        let mut data_group = self.create_data_group().ok_or(MdfError::NullPointer)?;
+        let mut channel_group = data_group
+            .create_channel_group()
+            .ok_or(MdfError::NullPointer)?;
+        let mut channels = Vec::with_capacity(names.len());
+        for (index, name) in names.iter().enumerate() {
+            let mut channel = channel_group
+                .create_channel()
+                .ok_or(MdfError::NullPointer)?;
+            channel.set_name(name);
+            channel.set_data_type(ChannelDataType::FloatLe as u8);
+            channel.set_data_bytes(8);
+            if index == time_index {
+                channel.set_type(ChannelType::Master as u8);
+                channel.set_sync(ChannelSyncType::Time as u8);
+                channel.set_unit("s");
+            }
+            channels.push(channel.inner);
+        }
+
+        if !self.init_measurement() {
+            return Err(MdfError::MeasurementInit);
+        }
+        let start_time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or(1);
+        self.start_measurement(start_time);
+
+        let mut stop_time = start_time;
+        for (time, values) in rows {
+            for (channel, value) in channels.iter().zip(values) {
+                let mut channel = Channel::new(*channel);
+                match value {
+                    Some(value) => channel.set_channel_value_f64(value, true),
+                    None => channel.set_channel_value_f64(0.0, false),
+                }
+            }
+
+            stop_time = start_time.saturating_add((time * 1e9) as u64);
+            self.save_sample(&channel_group, stop_time);
+        }
+
+        self.stop_measurement(stop_time);
+        if self.finalize_measurement() {
+            Ok(())
+        } else {
+            Err(MdfError::MeasurementFinalize)
+        }
     }
 
     /// Save a sample
@@ -177,6 +454,75 @@ impl MdfWriter {
         unsafe { MdfWriterSaveSample(self.inner, group.inner, time) }
     }
 
+    /// Save a sample, failing if the writer can't save it
+    ///
+    /// mdflib silently drops samples saved before the measurement is
+    /// initialized or after it is stopped, so this returns
+    /// [`MdfError::InvalidWriterState`] in those cases instead.
+    pub fn try_save_sample(&mut self, group: &ChannelGroupRef, time: u64) -> Result<()> {
+        self.check_saving()?;
+        self.save_sample(group, time);
+        Ok(())
+    }
+
+    /// Save a CAN message, failing if the writer can't save it
+    ///
+    /// See [`MdfWriter::try_save_sample`].
+    pub fn try_save_can_message(
+        &mut self,
+        group: &ChannelGroupRef,
+        time: u64,
+        message: &CanMessageRef,
+    ) -> Result<()> {
+        self.check_saving()?;
+        self.save_can_message(group, time, message);
+        Ok(())
+    }
+
+    /// Set the values of channels in a group and save them as one sample
+    ///
+    /// All channels must belong to `group`, otherwise
+    /// [`MdfError::NotFound`] is returned and nothing is written. Channels of
+    /// the group not listed keep their last value. Mutable channel handles for
+    /// several channels of a group are available from
+    /// [`crate::ChannelGroup::get_channels_mut`]. Fails like
+    /// [`MdfWriter::try_save_sample`] if the writer can't save samples.
+    pub fn save_record(
+        &mut self,
+        group: &ChannelGroupRef,
+        time: u64,
+        values: &mut [(&mut Channel<'_>, SampleValue)],
+    ) -> Result<()> {
+        self.check_saving()?;
+        let channels = group.get_channels();
+        if let Some((channel, _)) = values
+            .iter()
+            .find(|(channel, _)| !channels.iter().any(|c| c.inner == channel.inner))
+        {
+            return Err(MdfError::NotFound(channel.get_name()));
+        }
+
+        for (channel, value) in values.iter_mut() {
+            channel.set_channel_value_typed(value)?;
+        }
+        self.save_sample(group, time);
+        Ok(())
+    }
+
+    /// Checks that samples are saved in the current measurement state
+    pub(crate) fn check_saving(&self) -> Result<()> {
+        match self.state {
+            MeasurementState::Initialized | MeasurementState::Started => Ok(()),
+            MeasurementState::Created => {
+                Err(MdfError::InvalidWriterState("measurement not initialized"))
+            }
+            MeasurementState::Stopped => Err(MdfError::InvalidWriterState("measurement stopped")),
+            MeasurementState::Finalized => {
+                Err(MdfError::InvalidWriterState("measurement finalized"))
+            }
+        }
+    }
+
     /// Save a CAN message
     ///
     /// Time is absolute time in nanoseconds since the epoch (1970-01-01T00:00:00Z).
@@ -194,6 +540,7 @@ impl MdfWriter {
     /// Time is absolute time in nanoseconds since the epoch (1970-01-01T00:00:00Z). **Should be > 0 otherwise samples will not be saved.**
     pub fn start_measurement(&mut self, start_time: u64) {
         unsafe { MdfWriterStartMeasurement(self.inner, start_time) }
+        self.state = MeasurementState::Started;
     }
 
     /// Stop measurement
@@ -201,16 +548,194 @@ impl MdfWriter {
     /// Time is absolute time in nanoseconds since the epoch (1970-01-01T00:00:00Z). Should be greater than or equal to the start time.
     pub fn stop_measurement(&mut self, stop_time: u64) {
         unsafe { MdfWriterStopMeasurement(self.inner, stop_time) }
+        self.state = MeasurementState::Stopped;
     }
 
     /// Finalize measurement
     ///
     /// Unloads worker queue, joins threads, and writes the final data to the file.
     pub fn finalize_measurement(&mut self) -> bool {
-        unsafe { MdfWriterFinalizeMeasurement(self.inner) }
+        let ok = unsafe { MdfWriterFinalizeMeasurement(self.inner) };
+        if ok {
+            self.state = MeasurementState::Finalized;
+        }
+        ok
+    }
+
+    /// Finalizes the measurement if needed and opens the file for reading
+    ///
+    /// A measurement that is still running is stopped at the current time, a
+    /// writer that was never initialized writes a file without samples. The
+    /// writer is closed before the file is opened, the returned reader has
+    /// only opened it so read it as usual, e.g. with
+    /// [`MdfReader::read_everything_but_data`].
+    pub fn into_reader(mut self) -> Result<MdfReader> {
+        if self.state == MeasurementState::Created && !self.init_measurement() {
+            return Err(MdfError::MeasurementInit);
+        }
+        if matches!(
+            self.state,
+            MeasurementState::Initialized | MeasurementState::Started
+        ) {
+            let stop_time = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_nanos() as u64)
+                .unwrap_or(1)
+                .max(self.get_start_time());
+            self.stop_measurement(stop_time);
+        }
+        if self.state != MeasurementState::Finalized && !self.finalize_measurement() {
+            return Err(MdfError::MeasurementFinalize);
+        }
+
+        let path = self.path.clone();
+        drop(self);
+        MdfReader::new(path)
+    }
+
+    /// Gets the number of samples of `group` written to the file
+    ///
+    /// Samples saved before [`MdfWriter::start_measurement`] are only written
+    /// if they are within the pre-trigger time (see
+    /// [`MdfWriter::set_pre_trig_time`]), so this can be less than the number
+    /// of samples saved. The finalized file is read back and the records of
+    /// the group in its data blocks are counted with
+    /// [`ChannelGroupRef::get_stored_sample_count`], so the sample count
+    /// declared in the channel group block isn't trusted.
+    ///
+    /// Returns [`MdfError::InvalidWriterState`] before the measurement is
+    /// finalized and [`MdfError::NotFound`] if `group` isn't part of the
+    /// writer's file.
+    pub fn get_written_sample_count(&self, group: &ChannelGroupRef) -> Result<u64> {
+        if self.state != MeasurementState::Finalized {
+            return Err(MdfError::InvalidWriterState("measurement not finalized"));
+        }
+        let file = self.get_file().ok_or(MdfError::NullPointer)?;
+        let (dg_index, cg_index) = file
+            .get_data_groups()
+            .iter()
+            .enumerate()
+            .find_map(|(dg_index, dg)| {
+                dg.get_channel_groups()
+                    .iter()
+                    .position(|cg| cg.as_ptr() == group.as_ptr())
+                    .map(|cg_index| (dg_index, cg_index))
+            })
+            .ok_or_else(|| MdfError::NotFound(group.get_name()))?;
+
+        let mut reader = MdfReader::new(&self.path)?;
+        reader.read_everything_but_data()?;
+        let cg = reader
+            .get_data_group(dg_index)
+            .and_then(|dg| dg.get_channel_group_by_index(cg_index))
+            .ok_or(MdfError::IndexOutOfBounds(cg_index))?;
+        Ok(cg.get_stored_sample_count(&reader)? as u64)
     }
 }
 
+/// Running measurement started with [`MdfWriter::measurement_guard`]
+///
+/// Dereferences to the writer for configuration and sample values. Samples
+/// should be saved with the guard's own methods so the stop time follows the
+/// last sample.
+pub struct MeasurementGuard<'a> {
+    writer: &'a mut MdfWriter,
+    last_time: u64,
+    committed: bool,
+}
+
+impl MeasurementGuard<'_> {
+    /// Save a sample
+    ///
+    /// Time is absolute time in nanoseconds since the epoch (1970-01-01T00:00:00Z).
+    pub fn save_sample(&mut self, group: &ChannelGroupRef, time: u64) {
+        self.writer.save_sample(group, time);
+        self.last_time = self.last_time.max(time);
+    }
+
+    /// Save a CAN message
+    ///
+    /// Time is absolute time in nanoseconds since the epoch (1970-01-01T00:00:00Z).
+    pub fn save_can_message(
+        &mut self,
+        group: &ChannelGroupRef,
+        time: u64,
+        message: &CanMessageRef,
+    ) {
+        self.writer.save_can_message(group, time, message);
+        self.last_time = self.last_time.max(time);
+    }
+
+    /// Stop the measurement at `stop_time` and finalize it
+    pub fn commit(mut self, stop_time: u64) -> Result<()> {
+        self.committed = true;
+        if self.finish(stop_time) {
+            Ok(())
+        } else {
+            Err(MdfError::MeasurementFinalize)
+        }
+    }
+
+    /// Stops and finalizes the measurement, skipping what was already done
+    /// through the writer
+    fn finish(&mut self, stop_time: u64) -> bool {
+        match self.writer.state {
+            MeasurementState::Finalized => return true,
+            MeasurementState::Stopped => {}
+            _ => self.writer.stop_measurement(stop_time),
+        }
+        self.writer.finalize_measurement()
+    }
+}
+
+impl Deref for MeasurementGuard<'_> {
+    type Target = MdfWriter;
+
+    fn deref(&self) -> &Self::Target {
+        self.writer
+    }
+}
+
+impl DerefMut for MeasurementGuard<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.writer
+    }
+}
+
+impl Drop for MeasurementGuard<'_> {
+    fn drop(&mut self) {
+        if self.committed {
+            return;
+        }
+        if !self.finish(self.last_time) {
+            ::log::warn!("Failed to finalize measurement on drop");
+        }
+    }
+}
+
+/// Splits a CSV row into trimmed cells, handling double quoted cells
+fn split_csv_line(line: &str) -> Vec<String> {
+    let mut cells = Vec::new();
+    let mut cell = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                cell.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => cells.push(std::mem::take(&mut cell).trim().to_string()),
+            _ => cell.push(c),
+        }
+    }
+    cells.push(cell.trim().to_string());
+
+    cells
+}
+
 impl Drop for MdfWriter {
     fn drop(&mut self) {
         if !self.inner.is_null() {
@@ -235,4 +760,26 @@ mod tests {
         let writer = MdfWriter::new(MdfWriterType::Mdf4Basic, temp_file.path());
         assert!(writer.is_ok());
     }
+
+    #[test]
+    fn test_split_csv_line() {
+        assert_eq!(
+            split_csv_line("Time, Speed ,Temp"),
+            ["Time", "Speed", "Temp"]
+        );
+        assert_eq!(split_csv_line("0.1,,21"), ["0.1", "", "21"]);
+        assert_eq!(
+            split_csv_line(r#""Speed, front","say ""hi""""#),
+            ["Speed, front", r#"say "hi""#]
+        );
+        assert_eq!(split_csv_line(""), [""]);
+    }
+
+    #[test]
+    fn test_compression_available() {
+        // The bundled mdflib always links zlib
+        if cfg!(feature = "bundled") {
+            assert!(MdfWriter::compression_available());
+        }
+    }
 }
@@ -0,0 +1,7 @@
+//! Compile-fail tests for the lifetimes of borrowing wrapper types
+
+#[test]
+fn test_compile_fail() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
@@ -179,3 +179,271 @@ fn test_can_bus_writer_types() {
     let _writer_bus = writer::MdfWriter::new(mdflib_sys::MdfWriterType::MdfBusLogger, file_path2)
         .expect("Failed to create MDF bus logger writer");
 }
+
+/// Test that only the bus channel groups of a mixed file are returned
+#[test]
+fn test_bus_channel_groups_mixed_file() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+
+    {
+        let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::MdfBusLogger, file_path)
+            .expect("Failed to create MDF bus logger writer");
+
+        writer.set_bus_type(MdfBusType::CAN as u16);
+        assert!(writer.create_bus_log_configuration());
+
+        // Plain measurement group without a bus type
+        let mut data_group = writer.create_data_group().unwrap();
+        let mut channel_group = data_group.create_channel_group().unwrap();
+        channel_group.set_name("Analog");
+        let mut channel = channel_group.create_channel().unwrap();
+        channel.set_name("Voltage");
+        channel.set_data_type(mdflib_sys::ChannelDataType::FloatLe as u8);
+        channel.set_data_bytes(8);
+
+        writer.init_measurement();
+        writer.start_measurement(1753689305);
+        writer.stop_measurement(1753689306);
+        writer.finalize_measurement();
+    }
+
+    let mut reader = MdfReader::new(file_path).expect("Failed to create MDF reader");
+    reader.read_everything_but_data().unwrap();
+
+    let file = reader.get_file().unwrap();
+    let mut bus_groups = Vec::new();
+    for dg in file.get_data_groups() {
+        bus_groups.extend(dg.bus_channel_groups());
+    }
+
+    assert!(!bus_groups.is_empty(), "Should find the CAN groups");
+    for (cg, bus_type) in &bus_groups {
+        assert_eq!(*bus_type, BusType::Can);
+        assert_ne!(cg.get_name(), "Analog");
+    }
+}
+
+/// Test that the zero allocation data path matches get_data_bytes
+#[test]
+fn test_can_message_data_bytes_into() {
+    let mut msg = CanMessage::new();
+    msg.set_message_id(0x1ABCDEF);
+    msg.set_extended_id(true);
+    msg.set_dlc(8);
+    let data = vec![0x10, 0x20, 0x30, 0x40, 0x50, 0x60, 0x70, 0x80];
+    msg.set_data_bytes(&data);
+
+    let mut buf = [0u8; 64];
+    let len = msg.get_data_bytes_into(&mut buf);
+    assert_eq!(&buf[..len], msg.get_data_bytes().as_slice());
+
+    // A short buffer only receives the first bytes
+    let mut short = [0u8; 4];
+    assert_eq!(msg.get_data_bytes_into(&mut short), 4);
+    assert_eq!(short, [0x10, 0x20, 0x30, 0x40]);
+
+    let frame = msg.to_owned_frame();
+    assert_eq!(frame.id, msg.get_can_id());
+    assert!(frame.extended);
+    assert_eq!(frame.dlc, 8);
+    assert_eq!(frame.data(), data.as_slice());
+}
+
+#[test]
+fn test_can_frame_round_trip() {
+    let frame = Frame {
+        id: 0x18FEF100,
+        extended: true,
+        dlc: 8,
+        data: vec![0xDE, 0xAD, 0xBE, 0xEF, 0x01, 0x02, 0x03, 0x04],
+        timestamp: 1_500_000_000,
+    };
+
+    let msg = CanMessage::from(&frame);
+    assert_eq!(msg.get_can_id(), frame.id);
+    assert!(msg.get_extended_id());
+    assert_eq!(msg.get_dlc(), frame.dlc);
+    assert_eq!(msg.get_data_bytes(), frame.data);
+    assert_eq!(msg.get_timestamp(), 1.5);
+
+    let back = Frame::from(&*msg);
+    assert_eq!(back.id, frame.id);
+    assert_eq!(back.extended, frame.extended);
+    assert_eq!(back.dlc, frame.dlc);
+    assert_eq!(back.data, frame.data);
+    assert_eq!(back.timestamp, frame.timestamp);
+}
+
+#[test]
+fn test_bus_logger_source_name() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+
+    {
+        let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::MdfBusLogger, file_path)
+            .expect("Failed to create MDF bus logger writer");
+        writer.set_bus_type(mdflib_sys::MdfBusType::CAN as u16);
+        assert!(writer.create_bus_log_configuration());
+
+        let header = writer.get_header().unwrap();
+        let last_dg = header.get_last_data_group().unwrap();
+        let mut can_data_group = last_dg.get_channel_group("CAN_DataFrame").unwrap();
+        let mut source = can_data_group.create_source_information().unwrap();
+        source.configure_can("CAN1", "can0").unwrap();
+
+        writer.init_measurement();
+        writer.start_measurement(1000);
+        writer.stop_measurement(2000);
+        writer.finalize_measurement();
+    }
+
+    let mut reader = MdfReader::new(file_path).expect("Failed to create MDF reader");
+    reader.read_everything_but_data().unwrap();
+
+    let dg = reader.get_data_group(0).unwrap();
+    let cg = dg.get_channel_group("CAN_DataFrame").unwrap();
+    assert_eq!(cg.get_source_name().as_deref(), Some("CAN1"));
+    assert_eq!(cg.get_source_path().as_deref(), Some("can0"));
+}
+
+/// Test adding a file history entry to a bus logger file in one call
+#[test]
+fn test_bus_logger_add_file_history() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+
+    {
+        let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::MdfBusLogger, file_path)
+            .expect("Failed to create MDF bus logger writer");
+        writer.set_bus_type(mdflib_sys::MdfBusType::CAN as u16);
+        assert!(writer.create_bus_log_configuration());
+
+        writer
+            .add_file_history(FileHistoryEntry {
+                description: Some("Test MDF4 CAN bus logger".to_string()),
+                tool_name: Some("mdflib-rs".to_string()),
+                tool_version: Some("0.1.0".to_string()),
+                user_name: Some("Test User".to_string()),
+                ..Default::default()
+            })
+            .unwrap();
+
+        writer.init_measurement();
+        writer.start_measurement(1000);
+        writer.stop_measurement(2000);
+        writer.finalize_measurement();
+    }
+
+    let mut reader = MdfReader::new(file_path).expect("Failed to create MDF reader");
+    reader.read_everything_but_data().unwrap();
+
+    let header = reader.get_header().unwrap();
+    let histories = header.get_file_histories();
+    let history = histories
+        .iter()
+        .find(|history| history.get_description() == "Test MDF4 CAN bus logger")
+        .expect("File history not found");
+    assert_eq!(history.get_tool_name(), "mdflib-rs");
+    assert_eq!(history.get_tool_version(), "0.1.0");
+    assert_eq!(history.get_user_name(), "Test User");
+}
+
+/// Test that the storage groups of a VLSD bus logger are flagged
+#[test]
+fn test_bus_logger_vlsd_groups_flagged() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+
+    {
+        let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::MdfBusLogger, file_path)
+            .expect("Failed to create MDF bus logger writer");
+
+        writer.set_bus_type(MdfBusType::CAN as u16);
+        writer.set_storage_type(MdfStorageType::VlsdStorage);
+        assert_eq!(writer.get_storage_type(), MdfStorageType::VlsdStorage);
+        assert!(writer.create_bus_log_configuration());
+
+        let mut data_group = writer.create_data_group().unwrap();
+        let mut channel_group = data_group.create_channel_group().unwrap();
+        channel_group.set_name("Analog");
+        let mut channel = channel_group.create_channel().unwrap();
+        channel.set_name("Voltage");
+        channel.set_data_type(mdflib_sys::ChannelDataType::FloatLe as u8);
+        channel.set_data_bytes(8);
+
+        writer.init_measurement();
+        writer.start_measurement(1753689305);
+        writer.stop_measurement(1753689306);
+        writer.finalize_measurement();
+    }
+
+    let mut reader = MdfReader::new(file_path).expect("Failed to create MDF reader");
+    reader.read_everything_but_data().unwrap();
+
+    let file = reader.get_file().unwrap();
+    let groups: Vec<_> = file
+        .get_data_groups()
+        .iter()
+        .flat_map(|dg| dg.get_channel_groups())
+        .collect();
+
+    assert!(groups.iter().any(|cg| cg.is_vlsd()));
+    assert!(groups.iter().any(|cg| cg.is_bus_event()));
+    for cg in groups.iter().filter(|cg| cg.is_vlsd()) {
+        assert!(!cg.is_bus_event());
+    }
+    let analog = groups.iter().find(|cg| cg.get_name() == "Analog").unwrap();
+    assert!(!analog.is_vlsd());
+    assert!(!analog.is_bus_event());
+}
+
+/// Test grouping the channels of a mixed CAN, LIN and analog file by bus
+#[test]
+fn test_channels_by_bus() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+
+    {
+        let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::MdfBusLogger, file_path)
+            .expect("Failed to create MDF bus logger writer");
+
+        writer.set_bus_type(MdfBusType::CAN as u16 | MdfBusType::LIN as u16);
+        assert!(writer.create_bus_log_configuration());
+
+        let mut data_group = writer.create_data_group().unwrap();
+        let mut channel_group = data_group.create_channel_group().unwrap();
+        channel_group.set_name("Analog");
+        let mut channel = channel_group.create_channel().unwrap();
+        channel.set_name("Voltage");
+        channel.set_data_type(mdflib_sys::ChannelDataType::FloatLe as u8);
+        channel.set_data_bytes(8);
+
+        writer.init_measurement();
+        writer.start_measurement(1753689305);
+        writer.stop_measurement(1753689306);
+        writer.finalize_measurement();
+    }
+
+    let mut reader = MdfReader::new(file_path).expect("Failed to create MDF reader");
+    reader.read_everything_but_data().unwrap();
+
+    let by_bus = reader.channels_by_bus();
+    let names = |bus: BusType| -> Vec<String> {
+        by_bus
+            .get(&bus)
+            .map(|channels| channels.iter().map(|cn| cn.get_name()).collect())
+            .unwrap_or_default()
+    };
+
+    assert_eq!(names(BusType::None), ["Voltage"]);
+    let can = names(BusType::Can);
+    let lin = names(BusType::Lin);
+    assert!(!can.is_empty(), "Should find the CAN channels");
+    assert!(!lin.is_empty(), "Should find the LIN channels");
+    assert!(!can.contains(&"Voltage".to_string()));
+    assert!(!lin.contains(&"Voltage".to_string()));
+
+    let total: usize = by_bus.values().map(Vec::len).sum();
+    assert_eq!(total, reader.get_file().unwrap().total_channel_count());
+}
@@ -58,9 +58,8 @@ fn test_can_bus_observer_basic() {
         for mut dg in file.get_data_groups() {
             for cg in dg.get_channel_groups() {
                 // Only create CAN bus observers for CAN channel groups
-                if cg.get_bus_type() == BusType::Can as u8 {
-                    let observer =
-                        unsafe { create_can_bus_observer(dg.as_ptr(), cg.as_ptr()).unwrap() };
+                if cg.get_bus_type() == BusType::from(MdfBusType::CAN) as u8 {
+                    let observer = create_can_bus_observer(&reader, &dg, &cg).unwrap();
                     reader.read_data(&mut dg).unwrap();
                     let name = observer.get_name();
                     let nof_samples = observer.get_nof_samples();
@@ -155,9 +154,8 @@ fn test_can_bus_observer_multiple() {
                 let cg = dg.get_channel_group_by_index(cg_index).unwrap();
 
                 // Only create CAN bus observers for CAN channel groups
-                if cg.get_bus_type() == BusType::Can as u8 {
-                    let observer =
-                        unsafe { create_can_bus_observer(dg.as_ptr(), cg.as_ptr()).unwrap() };
+                if cg.get_bus_type() == BusType::from(MdfBusType::CAN) as u8 {
+                    let observer = create_can_bus_observer(&reader, &dg, &cg).unwrap();
                     let name = observer.get_name();
                     observers.push((name, observer));
                 }
@@ -199,3 +197,181 @@ fn test_can_bus_observer_multiple() {
         }
     }
 }
+
+/// Test telling classic and CAN FD frames apart on read-back
+#[test]
+fn test_can_fd_detection() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+
+    {
+        let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::MdfBusLogger, file_path)
+            .expect("Failed to create MDF writer");
+        writer.set_bus_type(MdfBusType::CAN as u16);
+        writer.create_bus_log_configuration();
+
+        let header = writer.get_header().unwrap();
+        let last_dg = header.get_last_data_group().unwrap();
+        let channel_group = last_dg.get_channel_group("_DataFrame").unwrap();
+
+        writer.init_measurement();
+        let start_time = 1753689305;
+        writer.start_measurement(start_time);
+
+        let mut classic = CanMessage::new();
+        classic.set_message_id(0x100);
+        classic.set_dlc(8);
+        classic.set_data_bytes(&[0x11; 8]);
+        writer.save_can_message(&channel_group, start_time, &classic);
+
+        let mut fd = CanMessage::new();
+        fd.set_message_id(0x200);
+        fd.set_fd(true);
+        fd.set_dlc(13);
+        fd.set_data_bytes(&[0x22; 32]);
+        writer.save_can_message(&channel_group, start_time + 1, &fd);
+
+        writer.stop_measurement(start_time + 2);
+        writer.finalize_measurement();
+    }
+
+    let mut reader = reader::MdfReader::new(file_path).expect("Failed to create MDF reader");
+    reader.read_everything_but_data().unwrap();
+
+    let file = reader.get_file().unwrap();
+    let mut dg = file.get_data_group(0).unwrap();
+    let cg = dg.get_channel_group("CAN_DataFrame").unwrap();
+    let observer = create_can_bus_observer(&reader, &dg, &cg).unwrap();
+    reader.read_data(&mut dg).unwrap();
+    assert_eq!(observer.get_nof_samples(), 2);
+
+    let classic = observer.get_can_message(0).unwrap();
+    assert!(!classic.is_fd());
+    assert_eq!(classic.get_frame_length(), 8);
+
+    let fd = observer.get_can_message(1).unwrap();
+    assert!(fd.is_fd());
+    assert_eq!(fd.get_frame_length(), 32);
+    assert_eq!(fd.get_data_bytes(), [0x22; 32]);
+}
+
+#[test]
+fn test_can_bus_observer_filter_by_id() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+    let start_time = 1_753_689_305_000_000_000;
+
+    {
+        let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::MdfBusLogger, file_path)
+            .expect("Failed to create MDF writer");
+        writer.set_bus_type(MdfBusType::CAN as u16);
+        writer.create_bus_log_configuration();
+
+        let header = writer.get_header().unwrap();
+        let last_dg = header.get_last_data_group().unwrap();
+        let channel_group = last_dg.get_channel_group("_DataFrame").unwrap();
+
+        writer.init_measurement();
+        writer.start_measurement(start_time);
+
+        let mut message = CanMessage::new();
+        message.set_dlc(1);
+        for i in 0..12u64 {
+            message.set_message_id(if i % 3 == 0 { 0x7DF } else { 0x100 + i as u32 });
+            message.set_data_bytes(&[i as u8]);
+            writer.save_can_message(&channel_group, start_time + i * 10_000_000, &message);
+        }
+
+        writer.stop_measurement(start_time + 120_000_000);
+        writer.finalize_measurement();
+    }
+
+    let mut reader = reader::MdfReader::new(file_path).expect("Failed to create MDF reader");
+    reader.read_everything_but_data().unwrap();
+
+    let file = reader.get_file().unwrap();
+    let mut dg = file.get_data_group(0).unwrap();
+    let cg = dg.get_channel_group("CAN_DataFrame").unwrap();
+    let observer = create_can_bus_observer(&reader, &dg, &cg).unwrap();
+    reader.read_data(&mut dg).unwrap();
+    assert_eq!(observer.get_nof_samples(), 12);
+
+    let matches: Vec<usize> = (0..observer.get_nof_samples())
+        .filter(|&sample| observer.get_can_id(sample) == Some(0x7DF))
+        .collect();
+    assert_eq!(matches, [0, 3, 6, 9]);
+
+    let timestamps: Vec<u64> = matches
+        .iter()
+        .filter_map(|&sample| observer.get_timestamp(sample))
+        .collect();
+    assert_eq!(timestamps, [0, 30_000_000, 60_000_000, 90_000_000]);
+    assert_eq!(observer.get_can_id(12), None);
+}
+
+/// Test reading the messages of all CAN channel groups at once
+#[test]
+fn test_read_all_can_messages() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+
+    {
+        let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::MdfBusLogger, file_path)
+            .expect("Failed to create MDF writer");
+        writer.set_bus_type(MdfBusType::CAN as u16);
+        writer.create_bus_log_configuration();
+        writer.create_data_group();
+        writer.create_bus_log_configuration();
+
+        let file = writer.get_file().unwrap();
+        let can1 = file
+            .get_data_group(0)
+            .unwrap()
+            .get_channel_group("_DataFrame")
+            .unwrap();
+        let can2 = file
+            .get_data_group(1)
+            .unwrap()
+            .get_channel_group("_DataFrame")
+            .unwrap();
+
+        writer.init_measurement();
+        let start_time = 1753689305;
+        writer.start_measurement(start_time);
+
+        let mut can_message = CanMessage::new();
+        can_message.set_message_id(0x123);
+        can_message.set_dlc(2);
+        can_message.set_data_bytes(&[0xAA, 0x55]);
+        for i in 0..5 {
+            writer.save_can_message(&can1, start_time + i, &can_message);
+        }
+        can_message.set_message_id(0x456);
+        for i in 0..3 {
+            writer.save_can_message(&can2, start_time + i, &can_message);
+        }
+
+        writer.stop_measurement(start_time + 1000);
+        writer.finalize_measurement();
+    }
+
+    let mut reader = reader::MdfReader::new(file_path).expect("Failed to create MDF reader");
+    reader.read_everything_but_data().unwrap();
+    let groups = reader.read_all_can_messages().unwrap();
+
+    let total: usize = groups.iter().map(|(_, frames)| frames.len()).sum();
+    assert_eq!(total, 8);
+
+    let data_frames: Vec<&Vec<Frame>> = groups
+        .iter()
+        .filter(|(name, _)| name.contains("DataFrame"))
+        .map(|(_, frames)| frames)
+        .collect();
+    assert_eq!(data_frames.len(), 2);
+    assert_eq!(data_frames[0].len(), 5);
+    assert!(data_frames[0].iter().all(|frame| frame.id == 0x123));
+    assert_eq!(data_frames[1].len(), 3);
+    assert!(data_frames[1]
+        .iter()
+        .all(|frame| frame.id == 0x456 && frame.data == [0xAA, 0x55]));
+}
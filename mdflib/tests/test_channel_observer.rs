@@ -194,3 +194,1207 @@ fn test_channel_observer_multiple_channels() {
         }
     }
 }
+
+/// Test that an owned sample buffer outlives the reader it was taken from
+#[test]
+fn test_channel_observer_take_samples() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+
+    {
+        let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, file_path)
+            .expect("Failed to create MDF writer");
+
+        let mut data_group = writer.create_data_group().unwrap();
+        let mut channel_group = data_group.create_channel_group().unwrap();
+        channel_group.set_name("BufferGroup");
+
+        {
+            let mut master = channel_group.create_channel().unwrap();
+            master.set_name("Time");
+            master.set_unit("s");
+            master.set_type(ChannelType::Master as u8);
+            master.set_data_type(mdflib_sys::ChannelDataType::FloatLe as u8);
+            master.set_data_bytes(8);
+        }
+
+        let mut channel = channel_group.create_channel().unwrap();
+        channel.set_name("Counter");
+        channel.set_data_type(mdflib_sys::ChannelDataType::UnsignedIntegerLe as u8);
+        channel.set_data_bytes(4);
+
+        // Second handle to the same group so samples can be saved while the
+        // channel is borrowed for setting values
+        let group = data_group.get_channel_group_by_index(0).unwrap();
+
+        writer.init_measurement();
+        writer.start_measurement(1000);
+        for i in 0..10 {
+            channel.set_channel_value(i, true);
+            writer.save_sample(&group, 1000 + i as u64 * 1000);
+        }
+        writer.stop_measurement(11000);
+        writer.finalize_measurement();
+    }
+
+    let buffer = {
+        let mut reader = MdfReader::new(file_path).expect("Failed to create MDF reader");
+        reader.read_everything_but_data().unwrap();
+
+        let file = reader.get_file().unwrap();
+        let mut dg = file.get_data_group(0).unwrap();
+        let cg = dg.get_channel_group("BufferGroup").unwrap();
+        let channel = cg.get_channel("Counter").unwrap();
+
+        let observer =
+            unsafe { create_timed_channel_observer(dg.as_ptr(), cg.as_ptr(), channel.as_ptr()) }
+                .expect("Should be able to create channel observer");
+        let untimed =
+            unsafe { create_channel_observer(dg.as_ptr(), cg.as_ptr(), channel.as_ptr()) }
+                .expect("Should be able to create channel observer");
+        reader.read_data(&mut dg).unwrap();
+
+        // Without the master observed there are no times to take
+        let untimed = untimed.take_samples();
+        assert_eq!(untimed.times, None);
+        assert_eq!(untimed.len(), 10);
+
+        observer.take_samples()
+        // Observer, reader and file are dropped here
+    };
+
+    assert_eq!(buffer.len(), 10);
+    assert_eq!(buffer.times.as_ref().map(Vec::len), Some(10));
+    assert!(buffer.valid.iter().all(|&valid| valid));
+    for (i, value) in buffer.values.iter().enumerate() {
+        assert_eq!(*value, i as f64);
+    }
+}
+
+/// Test that reading a single named data group leaves other groups empty
+#[test]
+fn test_read_data_group_by_name() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+
+    {
+        let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, file_path)
+            .expect("Failed to create MDF writer");
+
+        let mut groups = Vec::new();
+        for name in ["Engine", "Gearbox"] {
+            let mut data_group = writer.create_data_group().unwrap();
+            let mut channel_group = data_group.create_channel_group().unwrap();
+            channel_group.set_name(name);
+
+            let mut channel = channel_group.create_channel().unwrap();
+            channel.set_name(&format!("{name}Value"));
+            channel.set_data_type(mdflib_sys::ChannelDataType::UnsignedIntegerLe as u8);
+            channel.set_data_bytes(4);
+
+            groups.push(channel_group);
+        }
+
+        writer.init_measurement();
+        writer.start_measurement(1000);
+        for i in 0..5 {
+            for group in &groups {
+                writer.save_sample(group, 1000 + i * 1000);
+            }
+        }
+        writer.stop_measurement(6000);
+        writer.finalize_measurement();
+    }
+
+    let mut reader = MdfReader::new(file_path).expect("Failed to create MDF reader");
+    reader.read_everything_but_data().unwrap();
+
+    let file = reader.get_file().unwrap();
+    let mut observers = Vec::new();
+    for dg in file.get_data_groups() {
+        for cg in dg.get_channel_groups() {
+            let channel = cg.get_channel_by_index(0).unwrap();
+            let observer =
+                unsafe { create_channel_observer(dg.as_ptr(), cg.as_ptr(), channel.as_ptr()) }
+                    .expect("Should be able to create channel observer");
+            observers.push((cg.get_name(), observer));
+        }
+    }
+
+    let dg = reader
+        .read_data_group_by_name("Gearbox")
+        .expect("Should find the Gearbox group");
+    assert_eq!(dg.get_channel_group_count(), 1);
+    assert!(matches!(
+        reader.read_data_group_by_name("Missing"),
+        Err(MdfError::NotFound(_))
+    ));
+
+    for (name, observer) in &observers {
+        let values = observer.get_all_channel_values();
+        if name == "Gearbox" {
+            assert_eq!(values.len(), 5);
+            assert!(values.iter().all(Option::is_some));
+        } else {
+            // Unread groups hold no valid samples
+            assert!(values.iter().all(Option::is_none));
+        }
+    }
+}
+
+/// Test reading every channel of a channel group at one sample
+#[test]
+fn test_channel_group_read_record() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+
+    {
+        let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, file_path)
+            .expect("Failed to create MDF writer");
+
+        let mut data_group = writer.create_data_group().unwrap();
+        let mut channel_group = data_group.create_channel_group().unwrap();
+        channel_group.set_name("RecordGroup");
+
+        for name in ["Speed", "Torque", "Temperature"] {
+            let mut channel = channel_group.create_channel().unwrap();
+            channel.set_name(name);
+            channel.set_data_type(mdflib_sys::ChannelDataType::UnsignedIntegerLe as u8);
+            channel.set_data_bytes(4);
+        }
+
+        writer.init_measurement();
+        writer.start_measurement(1000);
+        for i in 0..10 {
+            writer.save_sample(&channel_group, 1000 + i * 1000);
+        }
+        writer.stop_measurement(11000);
+        writer.finalize_measurement();
+    }
+
+    let mut reader = MdfReader::new(file_path).expect("Failed to create MDF reader");
+    reader.read_everything_but_data().unwrap();
+
+    let dg = reader.get_data_group(0).unwrap();
+    let cg = dg.get_channel_group("RecordGroup").unwrap();
+
+    let record = cg
+        .read_record(&mut reader, 3)
+        .expect("Failed to read record");
+    let names: Vec<&str> = record.iter().map(|(name, _)| name.as_str()).collect();
+    for name in ["Speed", "Torque", "Temperature"] {
+        assert!(names.contains(&name), "Missing channel {name}");
+    }
+    assert!(record.iter().all(|(_, value)| value.is_some()));
+
+    assert!(matches!(
+        cg.read_record(&mut reader, 10),
+        Err(MdfError::IndexOutOfBounds(10))
+    ));
+}
+
+/// Test that data groups are available after reading only the header
+#[test]
+fn test_data_group_count_after_header_read() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+
+    {
+        let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, file_path)
+            .expect("Failed to create MDF writer");
+
+        for name in ["First", "Second"] {
+            let mut data_group = writer.create_data_group().unwrap();
+            let mut channel_group = data_group.create_channel_group().unwrap();
+            channel_group.set_name(name);
+            let mut channel = channel_group.create_channel().unwrap();
+            channel.set_name(&format!("{name}Value"));
+            channel.set_data_type(mdflib_sys::ChannelDataType::UnsignedIntegerLe as u8);
+            channel.set_data_bytes(4);
+        }
+
+        writer.init_measurement();
+        writer.start_measurement(1000);
+        writer.stop_measurement(2000);
+        writer.finalize_measurement();
+    }
+
+    let mut reader = MdfReader::new(file_path).expect("Failed to create MDF reader");
+    reader.read_header().unwrap();
+
+    assert_eq!(reader.get_data_group_count(), 2);
+    let dg = reader.get_data_group(1).unwrap();
+    assert_eq!(
+        dg.get_channel_group_by_index(0).unwrap().get_name(),
+        "Second"
+    );
+}
+
+/// Test reading a 64-bit integer channel without f64 rounding
+#[test]
+fn test_channel_observer_read_typed() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+    let values = [u64::MAX, u64::MAX - 1, (1 << 53) + 1];
+
+    {
+        let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, file_path)
+            .expect("Failed to create MDF writer");
+
+        let mut data_group = writer.create_data_group().unwrap();
+        let mut channel_group = data_group.create_channel_group().unwrap();
+        channel_group.set_name("TypedGroup");
+
+        let mut channel = channel_group.create_channel().unwrap();
+        channel.set_name("BigCounter");
+        channel.set_data_type(mdflib_sys::ChannelDataType::UnsignedIntegerLe as u8);
+        channel.set_data_bytes(8);
+
+        let group = data_group.get_channel_group_by_index(0).unwrap();
+
+        writer.init_measurement();
+        writer.start_measurement(1000);
+        for (i, value) in values.iter().enumerate() {
+            channel.set_channel_value_u64(*value, true);
+            writer.save_sample(&group, 1000 + i as u64 * 1000);
+        }
+        writer.stop_measurement(5000);
+        writer.finalize_measurement();
+    }
+
+    let mut reader = MdfReader::new(file_path).expect("Failed to create MDF reader");
+    reader.read_everything_but_data().unwrap();
+
+    let file = reader.get_file().unwrap();
+    let mut dg = file.get_data_group(0).unwrap();
+    let cg = dg.get_channel_group("TypedGroup").unwrap();
+    let channel = cg.get_channel("BigCounter").unwrap();
+
+    let observer = unsafe { create_channel_observer(dg.as_ptr(), cg.as_ptr(), channel.as_ptr()) }
+        .expect("Should be able to create channel observer");
+    reader.read_data(&mut dg).unwrap();
+
+    assert_eq!(
+        observer.get_data_type(),
+        mdflib_sys::ChannelDataType::UnsignedIntegerLe as u8
+    );
+    assert_eq!(observer.read_typed(), TypedSamples::U64(values.to_vec()));
+}
+
+/// Test the data read flag of a data group
+#[test]
+fn test_data_group_is_data_read() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+
+    {
+        let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, file_path)
+            .expect("Failed to create MDF writer");
+
+        let mut data_group = writer.create_data_group().unwrap();
+        let mut channel_group = data_group.create_channel_group().unwrap();
+        let mut channel = channel_group.create_channel().unwrap();
+        channel.set_name("Counter");
+        channel.set_data_type(mdflib_sys::ChannelDataType::UnsignedIntegerLe as u8);
+        channel.set_data_bytes(4);
+
+        let group = data_group.get_channel_group_by_index(0).unwrap();
+        writer.init_measurement();
+        writer.start_measurement(1000);
+        for i in 0..5 {
+            writer.save_sample(&group, 1000 + i * 1000);
+        }
+        writer.stop_measurement(6000);
+        writer.finalize_measurement();
+    }
+
+    let mut reader = MdfReader::new(file_path).expect("Failed to create MDF reader");
+    reader.read_everything_but_data().unwrap();
+
+    let file = reader.get_file().unwrap();
+    let mut dg = file.get_data_group(0).unwrap();
+    assert!(!dg.is_data_read());
+
+    reader.read_data(&mut dg).unwrap();
+    assert!(dg.is_data_read());
+
+    dg.clear_data();
+    assert!(!dg.is_data_read());
+}
+
+/// Test reading data groups one at a time with automatic clearing
+#[test]
+fn test_for_each_data_group() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+
+    {
+        let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, file_path)
+            .expect("Failed to create MDF writer");
+
+        for name in ["First", "Second"] {
+            let mut data_group = writer.create_data_group().unwrap();
+            let mut channel_group = data_group.create_channel_group().unwrap();
+            channel_group.set_name(name);
+            let mut channel = channel_group.create_channel().unwrap();
+            channel.set_name(&format!("{name}Counter"));
+            channel.set_data_type(mdflib_sys::ChannelDataType::UnsignedIntegerLe as u8);
+            channel.set_data_bytes(4);
+        }
+
+        writer.init_measurement();
+        writer.start_measurement(1000);
+        for dg_index in 0..2 {
+            let data_group = writer.get_file().unwrap().get_data_group(dg_index).unwrap();
+            let channel_group = data_group.get_channel_group_by_index(0).unwrap();
+            for i in 0..4 {
+                writer.save_sample(&channel_group, 1000 + i * 1000);
+            }
+        }
+        writer.stop_measurement(5000);
+        writer.finalize_measurement();
+    }
+
+    let mut reader = MdfReader::new(file_path).expect("Failed to create MDF reader");
+    reader.read_everything_but_data().unwrap();
+
+    let mut seen = Vec::new();
+    reader
+        .for_each_data_group(|dg, observers| {
+            assert!(dg.is_data_read());
+            assert_eq!(observers.len(), 1);
+            assert_eq!(observers[0].get_nof_samples(), 4);
+            assert!(observers[0].get_eng_value(3).is_some());
+            seen.push(observers[0].get_name());
+            Ok(())
+        })
+        .unwrap();
+    assert_eq!(seen, ["FirstCounter", "SecondCounter"]);
+
+    // The data has been cleared so a new observer holds no valid samples
+    let file = reader.get_file().unwrap();
+    let dg = file.get_data_group(0).unwrap();
+    assert!(!dg.is_data_read());
+    let cg = dg.get_channel_group_by_index(0).unwrap();
+    let cn = cg.get_channel_by_index(0).unwrap();
+    let observer = unsafe { create_channel_observer(dg.as_ptr(), cg.as_ptr(), cn.as_ptr()) }
+        .expect("Should be able to create channel observer");
+    assert!(observer.get_eng_value(3).is_none());
+}
+
+/// Test fixed 64 byte strings in maximum length storage read back without padding
+#[test]
+fn test_mlsd_string_channel() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+    let values = ["idle", "", "a longer status message that still fits"];
+
+    {
+        let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, file_path)
+            .expect("Failed to create MDF writer");
+
+        let mut data_group = writer.create_data_group().unwrap();
+        let mut channel_group = data_group.create_channel_group().unwrap();
+        channel_group.set_name("StringGroup");
+
+        let mut channel = channel_group.create_channel().unwrap();
+        channel.set_name("Status");
+        channel.set_data_type(mdflib_sys::ChannelDataType::StringAscii as u8);
+        channel.set_storage_type(MdfStorageType::MlsdStorage);
+        channel.set_data_bytes(64);
+
+        let group = data_group.get_channel_group_by_index(0).unwrap();
+
+        writer.init_measurement();
+        writer.start_measurement(1000);
+        for (i, value) in values.iter().enumerate() {
+            channel.set_channel_value_str(value, true).unwrap();
+            writer.save_sample(&group, 1000 + i as u64 * 1000);
+        }
+        writer.stop_measurement(5000);
+        writer.finalize_measurement();
+    }
+
+    let mut reader = MdfReader::new(file_path).expect("Failed to create MDF reader");
+    reader.read_everything_but_data().unwrap();
+
+    let file = reader.get_file().unwrap();
+    let mut dg = file.get_data_group(0).unwrap();
+    let cg = dg.get_channel_group("StringGroup").unwrap();
+    let channel = cg.get_channel("Status").unwrap();
+    assert_eq!(channel.get_type(), ChannelType::MaxLength as u8);
+    assert_eq!(channel.get_data_bytes(), 64);
+
+    let observer = unsafe { create_channel_observer(dg.as_ptr(), cg.as_ptr(), channel.as_ptr()) }
+        .expect("Should be able to create channel observer");
+    reader.read_data(&mut dg).unwrap();
+
+    for (sample, value) in values.iter().enumerate() {
+        assert_eq!(
+            observer.get_value_as_string(sample).as_deref(),
+            Some(*value)
+        );
+    }
+}
+
+/// Test (time, value) pairs skip invalid samples and follow the master channel
+#[test]
+fn test_channel_observer_time_value_pairs() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+
+    {
+        let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, file_path)
+            .expect("Failed to create MDF writer");
+
+        let mut data_group = writer.create_data_group().unwrap();
+        let mut channel_group = data_group.create_channel_group().unwrap();
+        channel_group.set_name("PairGroup");
+
+        {
+            let mut master = channel_group.create_channel().unwrap();
+            master.set_name("Time");
+            master.set_unit("s");
+            master.set_type(ChannelType::Master as u8);
+            master.set_data_type(mdflib_sys::ChannelDataType::FloatLe as u8);
+            master.set_data_bytes(8);
+        }
+
+        let mut channel = channel_group.create_channel().unwrap();
+        channel.set_name("Counter");
+        channel.set_data_type(mdflib_sys::ChannelDataType::UnsignedIntegerLe as u8);
+        channel.set_data_bytes(4);
+
+        let group = data_group.get_channel_group_by_index(0).unwrap();
+
+        writer.init_measurement();
+        writer.start_measurement(1000);
+        for i in 0..10 {
+            channel.set_channel_value(i, i % 3 != 0);
+            writer.save_sample(&group, 1000 + i as u64 * 1000);
+        }
+        writer.stop_measurement(11000);
+        writer.finalize_measurement();
+    }
+
+    let mut reader = MdfReader::new(file_path).expect("Failed to create MDF reader");
+    reader.read_everything_but_data().unwrap();
+
+    let file = reader.get_file().unwrap();
+    let mut dg = file.get_data_group(0).unwrap();
+    let cg = dg.get_channel_group("PairGroup").unwrap();
+    let channel = cg.get_channel("Counter").unwrap();
+
+    let observer =
+        unsafe { create_timed_channel_observer(dg.as_ptr(), cg.as_ptr(), channel.as_ptr()) }
+            .expect("Should be able to create channel observer");
+    let untimed = unsafe { create_channel_observer(dg.as_ptr(), cg.as_ptr(), channel.as_ptr()) }
+        .expect("Should be able to create channel observer");
+    reader.read_data(&mut dg).unwrap();
+
+    // Without the master observed there are no times to pair with
+    assert_eq!(untimed.time_value_pairs(), None);
+    assert_eq!(untimed.sorted_by_time(), None);
+
+    let pairs = observer.time_value_pairs().expect("Master not observed");
+    let valid_count = (0..observer.get_nof_samples())
+        .filter(|&sample| observer.is_valid(sample))
+        .count();
+    assert_eq!(pairs.len(), valid_count);
+    assert!(pairs.windows(2).all(|w| w[0].0 < w[1].0));
+}
+
+/// Test samples saved out of time order are returned sorted
+#[test]
+fn test_channel_observer_sorted_by_time() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+
+    {
+        let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, file_path)
+            .expect("Failed to create MDF writer");
+
+        let mut data_group = writer.create_data_group().unwrap();
+        let mut channel_group = data_group.create_channel_group().unwrap();
+        channel_group.set_name("Unordered");
+
+        {
+            let mut master = channel_group.create_channel().unwrap();
+            master.set_name("Time");
+            master.set_unit("s");
+            master.set_type(ChannelType::Master as u8);
+            master.set_data_type(mdflib_sys::ChannelDataType::FloatLe as u8);
+            master.set_data_bytes(8);
+        }
+
+        let mut channel = channel_group.create_channel().unwrap();
+        channel.set_name("Step");
+        channel.set_data_type(mdflib_sys::ChannelDataType::UnsignedIntegerLe as u8);
+        channel.set_data_bytes(4);
+
+        let group = data_group.get_channel_group_by_index(0).unwrap();
+
+        writer.init_measurement();
+        writer.start_measurement(1_000_000_000);
+        for step in [3u64, 1, 4, 0, 2] {
+            channel.set_channel_value(step as u32, true);
+            writer.save_sample(&group, 1_000_000_000 + step * 100_000_000);
+        }
+        writer.stop_measurement(2_000_000_000);
+        writer.finalize_measurement();
+    }
+
+    let mut reader = MdfReader::new(file_path).expect("Failed to create MDF reader");
+    reader.read_everything_but_data().unwrap();
+
+    let file = reader.get_file().unwrap();
+    let mut dg = file.get_data_group(0).unwrap();
+    let cg = dg.get_channel_group("Unordered").unwrap();
+    let channel = cg.get_channel("Step").unwrap();
+
+    let observer =
+        unsafe { create_timed_channel_observer(dg.as_ptr(), cg.as_ptr(), channel.as_ptr()) }
+            .expect("Should be able to create channel observer");
+    reader.read_data(&mut dg).unwrap();
+
+    let stored: Vec<f64> = observer
+        .time_value_pairs()
+        .expect("Master not observed")
+        .iter()
+        .map(|(_, value)| *value)
+        .collect();
+    assert_eq!(stored, [3.0, 1.0, 4.0, 0.0, 2.0]);
+
+    let sorted = observer.sorted_by_time().expect("Master not observed");
+    assert_eq!(sorted.len(), 5);
+    assert!(sorted.windows(2).all(|w| w[0].0 <= w[1].0));
+    for (step, (time, value)) in sorted.iter().enumerate() {
+        assert_eq!(*value, step as f64);
+        assert!((time - step as f64 * 0.1).abs() < 1e-6);
+    }
+}
+
+/// Test the packed validity bitset matches the per-sample validity
+#[test]
+fn test_channel_observer_validity_bitset() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+
+    {
+        let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, file_path)
+            .expect("Failed to create MDF writer");
+
+        let mut data_group = writer.create_data_group().unwrap();
+        let mut channel_group = data_group.create_channel_group().unwrap();
+        channel_group.set_name("ValidityGroup");
+
+        let mut channel = channel_group.create_channel().unwrap();
+        channel.set_name("Counter");
+        channel.set_data_type(mdflib_sys::ChannelDataType::UnsignedIntegerLe as u8);
+        channel.set_data_bytes(4);
+
+        let group = data_group.get_channel_group_by_index(0).unwrap();
+
+        // More than one word of samples
+        writer.init_measurement();
+        writer.start_measurement(1000);
+        for i in 0..100 {
+            channel.set_channel_value(i, i % 7 != 0);
+            writer.save_sample(&group, 1000 + i as u64 * 1000);
+        }
+        writer.stop_measurement(101_000);
+        writer.finalize_measurement();
+    }
+
+    let mut reader = MdfReader::new(file_path).expect("Failed to create MDF reader");
+    reader.read_everything_but_data().unwrap();
+
+    let file = reader.get_file().unwrap();
+    let mut dg = file.get_data_group(0).unwrap();
+    let cg = dg.get_channel_group("ValidityGroup").unwrap();
+    let channel = cg.get_channel("Counter").unwrap();
+
+    let observer = unsafe { create_channel_observer(dg.as_ptr(), cg.as_ptr(), channel.as_ptr()) }
+        .expect("Should be able to create channel observer");
+    reader.read_data(&mut dg).unwrap();
+
+    let nof_samples = observer.get_nof_samples();
+    let bits = observer.validity_bitset();
+    assert_eq!(bits.len(), nof_samples.div_ceil(64));
+    for sample in 0..nof_samples {
+        let bit = bits[sample / 64] & (1 << (sample % 64)) != 0;
+        assert_eq!(bit, observer.is_valid(sample), "sample {sample}");
+    }
+}
+
+/// Test the time range spans the first and last written sample times
+#[test]
+fn test_channel_observer_time_range() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+    let start_time = 1_000_000_000;
+
+    {
+        let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, file_path)
+            .expect("Failed to create MDF writer");
+
+        let mut data_group = writer.create_data_group().unwrap();
+        let mut channel_group = data_group.create_channel_group().unwrap();
+        channel_group.set_name("RangeGroup");
+
+        {
+            let mut master = channel_group.create_channel().unwrap();
+            master.set_name("Time");
+            master.set_unit("s");
+            master.set_type(ChannelType::Master as u8);
+            master.set_sync(ChannelSyncType::Time as u8);
+            master.set_data_type(mdflib_sys::ChannelDataType::FloatLe as u8);
+            master.set_data_bytes(8);
+        }
+
+        let mut channel = channel_group.create_channel().unwrap();
+        channel.set_name("Counter");
+        channel.set_data_type(mdflib_sys::ChannelDataType::UnsignedIntegerLe as u8);
+        channel.set_data_bytes(4);
+
+        let group = data_group.get_channel_group_by_index(0).unwrap();
+
+        // Samples at 0.5 s to 2.5 s after the start
+        writer.init_measurement();
+        writer.start_measurement(start_time);
+        for i in 0..5 {
+            channel.set_channel_value(i, true);
+            writer.save_sample(&group, start_time + 500_000_000 + i as u64 * 500_000_000);
+        }
+        writer.stop_measurement(start_time + 3_000_000_000);
+        writer.finalize_measurement();
+    }
+
+    let mut reader = MdfReader::new(file_path).expect("Failed to create MDF reader");
+    reader.read_everything_but_data().unwrap();
+
+    let file = reader.get_file().unwrap();
+    let mut dg = file.get_data_group(0).unwrap();
+    let cg = dg.get_channel_group("RangeGroup").unwrap();
+    let channel = cg.get_channel("Counter").unwrap();
+
+    let observer =
+        unsafe { create_timed_channel_observer(dg.as_ptr(), cg.as_ptr(), channel.as_ptr()) }
+            .expect("Should be able to create channel observer");
+    let untimed = unsafe { create_channel_observer(dg.as_ptr(), cg.as_ptr(), channel.as_ptr()) }
+        .expect("Should be able to create channel observer");
+    assert_eq!(observer.time_range(), None);
+    reader.read_data(&mut dg).unwrap();
+
+    // Sample indices aren't passed off as times
+    assert_eq!(untimed.time_range(), None);
+    let (first, last) = observer.time_range().expect("No samples");
+    assert!((first - 0.5).abs() < 1e-6, "first {first}");
+    assert!((last - 2.5).abs() < 1e-6, "last {last}");
+}
+
+#[test]
+fn test_data_group_read_master_values() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+    let start_time = 1_000_000_000;
+
+    {
+        let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, file_path)
+            .expect("Failed to create MDF writer");
+
+        let mut data_group = writer.create_data_group().unwrap();
+        let mut channel_group = data_group.create_channel_group().unwrap();
+        channel_group.set_name("MasterGroup");
+
+        {
+            let mut master = channel_group.create_channel().unwrap();
+            master.set_name("Time");
+            master.set_unit("s");
+            master.set_type(ChannelType::Master as u8);
+            master.set_sync(ChannelSyncType::Time as u8);
+            master.set_data_type(mdflib_sys::ChannelDataType::FloatLe as u8);
+            master.set_data_bytes(8);
+        }
+
+        let mut channel = channel_group.create_channel().unwrap();
+        channel.set_name("Counter");
+        channel.set_data_type(mdflib_sys::ChannelDataType::UnsignedIntegerLe as u8);
+        channel.set_data_bytes(4);
+
+        let group = data_group.get_channel_group_by_index(0).unwrap();
+
+        writer.init_measurement();
+        writer.start_measurement(start_time);
+        for i in 0..4 {
+            channel.set_channel_value(i, true);
+            writer.save_sample(&group, start_time + i as u64 * 250_000_000);
+        }
+        writer.stop_measurement(start_time + 1_000_000_000);
+        writer.finalize_measurement();
+    }
+
+    let mut reader = MdfReader::new(file_path).expect("Failed to create MDF reader");
+    reader.read_everything_but_data().unwrap();
+
+    let mut dg = reader.get_file().unwrap().get_data_group(0).unwrap();
+    let cg = dg.get_channel_group("MasterGroup").unwrap();
+    let times = dg.read_master_values(&reader, &cg).unwrap();
+
+    assert_eq!(times.len(), 4);
+    for (i, time) in times.iter().enumerate() {
+        assert!((time - i as f64 * 0.25).abs() < 1e-6, "time {time}");
+    }
+}
+
+#[test]
+fn test_channel_observer_eng_unit() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+
+    {
+        let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, file_path)
+            .expect("Failed to create MDF writer");
+
+        let mut data_group = writer.create_data_group().unwrap();
+        let mut channel_group = data_group.create_channel_group().unwrap();
+        channel_group.set_name("UnitGroup");
+
+        {
+            let mut raw = channel_group.create_channel().unwrap();
+            raw.set_name("Raw");
+            raw.set_unit("V");
+            raw.set_data_type(mdflib_sys::ChannelDataType::UnsignedIntegerLe as u8);
+            raw.set_data_bytes(4);
+        }
+
+        let mut scaled = channel_group.create_channel().unwrap();
+        scaled.set_name("Scaled");
+        scaled.set_unit("V");
+        scaled.set_data_type(mdflib_sys::ChannelDataType::UnsignedIntegerLe as u8);
+        scaled.set_data_bytes(4);
+        let mut conversion = scaled.create_channel_conversion().unwrap();
+        conversion.set_type(ConversionType::Linear as u8);
+        conversion.set_parameter_as_double(0, 0.0);
+        conversion.set_parameter_as_double(1, 1000.0);
+        conversion.set_unit("mV").unwrap();
+
+        let group = data_group.get_channel_group_by_index(0).unwrap();
+        writer.init_measurement();
+        writer.start_measurement(1000);
+        writer.save_sample(&group, 2000);
+        writer.stop_measurement(3000);
+        writer.finalize_measurement();
+    }
+
+    let mut reader = MdfReader::new(file_path).expect("Failed to create MDF reader");
+    reader.read_everything_but_data().unwrap();
+
+    let file = reader.get_file().unwrap();
+    let dg = file.get_data_group(0).unwrap();
+    let cg = dg.get_channel_group("UnitGroup").unwrap();
+    let raw = cg.get_channel("Raw").unwrap();
+    let scaled = cg.get_channel("Scaled").unwrap();
+
+    let raw_observer = unsafe { create_channel_observer(dg.as_ptr(), cg.as_ptr(), raw.as_ptr()) }
+        .expect("Should be able to create channel observer");
+    let scaled_observer =
+        unsafe { create_channel_observer(dg.as_ptr(), cg.as_ptr(), scaled.as_ptr()) }
+            .expect("Should be able to create channel observer");
+
+    assert_eq!(raw_observer.get_eng_unit(), "V");
+    assert_eq!(scaled_observer.get_channel().unwrap().get_unit(), "V");
+    assert_eq!(scaled_observer.get_eng_unit(), "mV");
+}
+
+#[test]
+fn test_unsorted_data_group_record_ids() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+
+    {
+        let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, file_path)
+            .expect("Failed to create MDF writer");
+
+        let mut data_group = writer.create_data_group().unwrap();
+        let mut fast = data_group.create_channel_group().unwrap();
+        fast.set_name("Fast");
+        fast.set_record_id(1);
+        let mut slow = data_group.create_channel_group().unwrap();
+        slow.set_name("Slow");
+        slow.set_record_id(2);
+
+        let mut fast_counter = fast.create_channel().unwrap();
+        fast_counter.set_name("FastCounter");
+        fast_counter.set_data_type(mdflib_sys::ChannelDataType::UnsignedIntegerLe as u8);
+        fast_counter.set_data_bytes(4);
+        let mut slow_counter = slow.create_channel().unwrap();
+        slow_counter.set_name("SlowCounter");
+        slow_counter.set_data_type(mdflib_sys::ChannelDataType::UnsignedIntegerLe as u8);
+        slow_counter.set_data_bytes(4);
+
+        let fast = data_group.get_channel_group("Fast").unwrap();
+        let slow = data_group.get_channel_group("Slow").unwrap();
+
+        writer.init_measurement();
+        writer.start_measurement(1000);
+        for i in 0..6u32 {
+            fast_counter.set_channel_value(i, true);
+            writer.save_sample(&fast, 1000 + i as u64 * 1000);
+            if i % 2 == 0 {
+                slow_counter.set_channel_value(100 + i, true);
+                writer.save_sample(&slow, 1000 + i as u64 * 1000);
+            }
+        }
+        writer.stop_measurement(7000);
+        writer.finalize_measurement();
+    }
+
+    let mut reader = MdfReader::new(file_path).expect("Failed to create MDF reader");
+    reader.read_everything_but_data().unwrap();
+
+    let file = reader.get_file().unwrap();
+    let mut dg = file.get_data_group(0).unwrap();
+    assert_eq!(dg.get_channel_group_count(), 2);
+    let fast = dg.get_channel_group("Fast").unwrap();
+    let slow = dg.get_channel_group("Slow").unwrap();
+    assert_eq!(fast.get_record_id(), 1);
+    assert_eq!(slow.get_record_id(), 2);
+
+    let fast_channel = fast.get_channel("FastCounter").unwrap();
+    let slow_channel = slow.get_channel("SlowCounter").unwrap();
+    let fast_observer =
+        unsafe { create_channel_observer(dg.as_ptr(), fast.as_ptr(), fast_channel.as_ptr()) }
+            .unwrap();
+    let slow_observer =
+        unsafe { create_channel_observer(dg.as_ptr(), slow.as_ptr(), slow_channel.as_ptr()) }
+            .unwrap();
+    reader.read_data(&mut dg).unwrap();
+
+    let fast_values: Vec<_> = fast_observer.get_all_channel_values();
+    let slow_values: Vec<_> = slow_observer.get_all_channel_values();
+    assert_eq!(
+        fast_values,
+        (0..6).map(|i| Some(i as f64)).collect::<Vec<_>>()
+    );
+    assert_eq!(slow_values, [Some(100.0), Some(102.0), Some(104.0)]);
+}
+
+#[test]
+fn test_reader_read_all() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+
+    {
+        let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, file_path)
+            .expect("Failed to create MDF writer");
+        writer.get_header().unwrap().set_author("Document");
+
+        let mut data_group = writer.create_data_group().unwrap();
+        let mut channel_group = data_group.create_channel_group().unwrap();
+        channel_group.set_name("DocumentGroup");
+
+        {
+            let mut master = channel_group.create_channel().unwrap();
+            master.set_name("Time");
+            master.set_unit("s");
+            master.set_type(ChannelType::Master as u8);
+            master.set_sync(ChannelSyncType::Time as u8);
+            master.set_data_type(mdflib_sys::ChannelDataType::FloatLe as u8);
+            master.set_data_bytes(8);
+        }
+
+        let mut channel = channel_group.create_channel().unwrap();
+        channel.set_name("Counter");
+        channel.set_unit("rpm");
+        channel.set_data_type(mdflib_sys::ChannelDataType::UnsignedIntegerLe as u8);
+        channel.set_data_bytes(4);
+
+        let group = data_group.get_channel_group_by_index(0).unwrap();
+        writer.init_measurement();
+        writer.start_measurement(1_000_000_000);
+        for i in 0..3 {
+            channel.set_channel_value(i * 10, true);
+            writer.save_sample(&group, 1_000_000_000 + i as u64 * 100_000_000);
+        }
+        writer.stop_measurement(1_300_000_000);
+        writer.finalize_measurement();
+    }
+
+    let document = {
+        let mut reader = MdfReader::new(file_path).expect("Failed to create MDF reader");
+        reader.read_everything_but_data().unwrap();
+        reader.read_all().unwrap()
+    };
+
+    assert_eq!(document.header.author, "Document");
+    assert_eq!(document.data_groups.len(), 1);
+    let cg = &document.data_groups[0].channel_groups[0];
+    assert_eq!(cg.name, "DocumentGroup");
+    assert_eq!(cg.channels.len(), 2);
+
+    let counter = document.channel("Counter").unwrap();
+    assert_eq!(counter.info.unit, "rpm");
+    assert_eq!(counter.samples.values, [0.0, 10.0, 20.0]);
+    assert_eq!(counter.samples.valid, [true, true, true]);
+    let times = counter.samples.times.as_ref().expect("Master not observed");
+    for (i, time) in times.iter().enumerate() {
+        assert!((time - i as f64 * 0.1).abs() < 1e-6, "time {time}");
+    }
+}
+
+/// Test reading byte array and integer samples encoded as bytes
+#[test]
+fn test_channel_observer_raw_bytes() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+    let payloads = [[0xDE, 0xAD, 0xBE, 0xEF], [0x01, 0x02, 0x03, 0x04]];
+
+    {
+        let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, file_path)
+            .expect("Failed to create MDF writer");
+
+        let mut data_group = writer.create_data_group().unwrap();
+        let mut channel_group = data_group.create_channel_group().unwrap();
+        channel_group.set_name("RawGroup");
+        for (name, data_type, bytes) in [
+            ("Payload", mdflib_sys::ChannelDataType::ByteArray, 4),
+            ("Word", mdflib_sys::ChannelDataType::UnsignedIntegerBe, 2),
+        ] {
+            let mut channel = channel_group.create_channel().unwrap();
+            channel.set_name(name);
+            channel.set_data_type(data_type as u8);
+            channel.set_data_bytes(bytes);
+        }
+
+        let group = data_group.get_channel_group_by_index(0).unwrap();
+        let mut channels = channel_group.get_channels_mut();
+
+        writer.init_measurement();
+        writer.start_measurement(1000);
+        for (i, bytes) in payloads.iter().enumerate() {
+            let [payload, word] = &mut channels[..] else {
+                panic!("Expected two channels");
+            };
+            let mut values = [
+                (payload, SampleValue::Bytes(bytes.to_vec())),
+                (word, SampleValue::U64(0x1234 + i as u64)),
+            ];
+            writer
+                .save_record(&group, 1000 + i as u64 * 1000, &mut values)
+                .unwrap();
+        }
+        writer.stop_measurement(3000);
+        writer.finalize_measurement();
+    }
+
+    let mut reader = MdfReader::new(file_path).expect("Failed to create MDF reader");
+    reader.read_everything_but_data().unwrap();
+
+    let file = reader.get_file().unwrap();
+    let mut dg = file.get_data_group(0).unwrap();
+    let cg = dg.get_channel_group("RawGroup").unwrap();
+    let payload = cg.get_channel("Payload").unwrap();
+    let word = cg.get_channel("Word").unwrap();
+    let payload_observer =
+        unsafe { create_raw_channel_observer(dg.as_ptr(), cg.as_ptr(), payload.as_ptr()) }.unwrap();
+    let word_observer =
+        unsafe { create_raw_channel_observer(dg.as_ptr(), cg.as_ptr(), word.as_ptr()) }.unwrap();
+    let plain_observer =
+        unsafe { create_channel_observer(dg.as_ptr(), cg.as_ptr(), word.as_ptr()) }.unwrap();
+    reader.read_data(&mut dg).unwrap();
+
+    for (sample, bytes) in payloads.iter().enumerate() {
+        assert_eq!(payload_observer.get_raw_bytes(sample), Some(bytes.to_vec()));
+    }
+    assert_eq!(word_observer.get_raw_bytes(0), Some(vec![0x12, 0x34]));
+    assert_eq!(word_observer.get_raw_bytes(1), Some(vec![0x12, 0x35]));
+    assert_eq!(payload_observer.get_raw_bytes(2), None);
+    assert_eq!(plain_observer.get_raw_bytes(0), None);
+}
+
+/// Test previewing the first sample of every channel
+#[test]
+fn test_peek_first_samples() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+
+    {
+        let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, file_path)
+            .expect("Failed to create MDF writer");
+
+        let engine = DataGroupBuilder::new("Engine")
+            .channel(ChannelSpec::new("Speed", ChannelDataType::FloatLe))
+            .channel(ChannelSpec::new("Gear", ChannelDataType::UnsignedIntegerLe))
+            .build(&mut writer)
+            .unwrap();
+        let body = DataGroupBuilder::new("Body")
+            .channel(ChannelSpec::new("Door", ChannelDataType::UnsignedIntegerLe))
+            .build(&mut writer)
+            .unwrap();
+        let engine_group = engine.get_channel_group_by_index(0).unwrap();
+        let body_group = body.get_channel_group_by_index(0).unwrap();
+        let mut engine_channels = engine.get_channel_group_by_index(0).unwrap();
+        let mut engine_channels = engine_channels.get_channels_mut();
+        let mut body_channels = body.get_channel_group_by_index(0).unwrap();
+        let mut body_channels = body_channels.get_channels_mut();
+
+        writer.init_measurement();
+        writer.start_measurement(1_000_000_000);
+        for i in 0..5u64 {
+            let time = 1_000_000_000 + i * 100_000_000;
+            // The builder adds a master channel first
+            let [_, speed, gear] = &mut engine_channels[..] else {
+                panic!("Expected three engine channels");
+            };
+            let [_, door] = &mut body_channels[..] else {
+                panic!("Expected two body channels");
+            };
+            writer
+                .save_record(
+                    &engine_group,
+                    time,
+                    &mut [
+                        (speed, SampleValue::F64(1500.0 + i as f64 * 10.0)),
+                        (gear, SampleValue::U64(i + 1)),
+                    ],
+                )
+                .unwrap();
+            writer
+                .save_record(&body_group, time, &mut [(door, SampleValue::U64(i % 2))])
+                .unwrap();
+        }
+        writer.stop_measurement(2_000_000_000);
+        writer.finalize_measurement();
+    }
+
+    let mut reader = MdfReader::new(file_path).expect("Failed to create MDF reader");
+    reader.read_everything_but_data().unwrap();
+
+    let samples = reader.peek_first_samples().unwrap();
+    assert_eq!(
+        samples,
+        [
+            ("Time".to_string(), Some(0.0)),
+            ("Speed".to_string(), Some(1500.0)),
+            ("Gear".to_string(), Some(1.0)),
+            ("Time".to_string(), Some(0.0)),
+            ("Door".to_string(), Some(0.0)),
+        ]
+    );
+}
+
+#[test]
+fn test_read_single_value() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+
+    {
+        let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, file_path)
+            .expect("Failed to create MDF writer");
+        let ramp = DataGroupBuilder::new("Ramp")
+            .channel(
+                ChannelSpec::new("Level", ChannelDataType::UnsignedIntegerLe)
+                    .data_bytes(1)
+                    .linear(0.0, 0.5),
+            )
+            .build(&mut writer)
+            .unwrap();
+        let group = ramp.get_channel_group_by_index(0).unwrap();
+        let mut channels = ramp.get_channel_group_by_index(0).unwrap();
+        let mut channels = channels.get_channels_mut();
+
+        writer.init_measurement();
+        writer.start_measurement(1_000_000_000);
+        for i in 0..10u64 {
+            let [_, level] = &mut channels[..] else {
+                panic!("Expected two channels");
+            };
+            writer
+                .save_record(
+                    &group,
+                    1_000_000_000 + i * 100_000_000,
+                    &mut [(level, SampleValue::U64(i))],
+                )
+                .unwrap();
+        }
+        writer.stop_measurement(2_000_000_000);
+        writer.finalize_measurement();
+    }
+
+    let mut reader = MdfReader::new(file_path).expect("Failed to create MDF reader");
+    reader.read_everything_but_data().unwrap();
+    let file = reader.get_file().unwrap();
+    let (_, _, level) = file.find_all_channels("Level")[0];
+
+    assert_eq!(reader.read_single_value(&level, 5).unwrap(), Some(2.5));
+    assert_eq!(reader.read_single_value(&level, 0).unwrap(), Some(0.0));
+    assert!(matches!(
+        reader.read_single_value(&level, 10),
+        Err(MdfError::IndexOutOfBounds(10))
+    ));
+}
+
+/// Test the observers of a channel group share the master observer for times
+#[test]
+fn test_channel_group_observers() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+
+    {
+        let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, file_path)
+            .expect("Failed to create MDF writer");
+        let data_group = DataGroupBuilder::new("Shared")
+            .channel(ChannelSpec::new("Speed", ChannelDataType::FloatLe))
+            .channel(ChannelSpec::new("Gear", ChannelDataType::UnsignedIntegerLe))
+            .build(&mut writer)
+            .unwrap();
+        let group = data_group.get_channel_group_by_index(0).unwrap();
+        let mut channels = data_group.get_channel_group_by_index(0).unwrap();
+        let mut channels = channels.get_channels_mut();
+
+        writer.init_measurement();
+        writer.start_measurement(1_000_000_000);
+        for i in 0..4u64 {
+            let [_, speed, gear] = &mut channels[..] else {
+                panic!("Expected three channels");
+            };
+            writer
+                .save_record(
+                    &group,
+                    1_000_000_000 + i * 250_000_000,
+                    &mut [
+                        (speed, SampleValue::F64(i as f64 * 2.0)),
+                        (gear, SampleValue::U64(i)),
+                    ],
+                )
+                .unwrap();
+        }
+        writer.stop_measurement(2_000_000_000);
+        writer.finalize_measurement();
+    }
+
+    let mut reader = MdfReader::new(file_path).expect("Failed to create MDF reader");
+    reader.read_everything_but_data().unwrap();
+    let file = reader.get_file().unwrap();
+    let mut dg = file.get_data_group(0).unwrap();
+    let cg = dg.get_channel_group("Shared").unwrap();
+
+    let mut observers = unsafe { create_channel_group_observers(dg.as_ptr(), cg.as_ptr()) }
+        .expect("Should be able to create channel observers");
+    assert_eq!(observers.len(), cg.get_channel_count());
+    reader.read_data(&mut dg).unwrap();
+
+    // The master observer stays alive for the others after being dropped
+    let time = observers.remove(0);
+    let expected = time.take_samples().values;
+    drop(time);
+    for observer in &observers {
+        assert_eq!(observer.take_samples().times.as_ref(), Some(&expected));
+    }
+    let pairs = observers[1]
+        .time_value_pairs()
+        .expect("Master not observed");
+    assert_eq!(pairs[3], (expected[3], 3.0));
+    assert!((expected[3] - 0.75).abs() < 1e-6);
+}
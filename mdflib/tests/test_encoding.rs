@@ -0,0 +1,53 @@
+//! Tests for decoding MDF3 text stored in a single byte code page
+//!
+//! Only built with the `encoding` feature enabled.
+#![cfg(feature = "encoding")]
+
+use mdflib::*;
+use tempfile::NamedTempFile;
+
+/// A Latin-1 channel name in an MDF3 file should be decoded, not replaced
+#[test]
+fn test_mdf3_latin1_channel_name() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+
+    {
+        let mut writer = MdfWriter::new(mdflib_sys::MdfWriterType::Mdf3Basic, file_path)
+            .expect("Failed to create MDF writer");
+        let mut data_group = writer.create_data_group().unwrap();
+        let mut channel_group = data_group.create_channel_group().unwrap();
+        let mut channel = channel_group.create_channel().unwrap();
+        channel.set_name("TempXrature");
+        channel.set_data_type(mdflib_sys::ChannelDataType::UnsignedIntegerLe as u8);
+        channel.set_data_bytes(4);
+
+        writer.init_measurement();
+        writer.start_measurement(1000);
+        writer.save_sample(&channel_group, 1000);
+        writer.stop_measurement(2000);
+        writer.finalize_measurement();
+    }
+
+    {
+        // Replace the placeholder with the Latin-1 encoded 'é' as an MDF3 tool would write it
+        let mut bytes = std::fs::read(file_path).unwrap();
+        let offset = bytes
+            .windows(11)
+            .position(|w| w == b"TempXrature")
+            .expect("Channel name not found");
+        bytes[offset + 4] = 0xE9;
+        std::fs::write(file_path, bytes).unwrap();
+    }
+
+    let mut reader = MdfReader::new(file_path).expect("Failed to create MDF reader");
+    reader.read_everything_but_data().unwrap();
+    assert!(!reader.get_file().unwrap().is_mdf4());
+
+    let data_group = reader.get_data_group(0).unwrap();
+    let channel_group = data_group.get_channel_group_by_index(0).unwrap();
+    let channel = channel_group.get_channel_by_index(0).unwrap();
+    assert_eq!(channel.decode_name(&reader), "Température");
+    // Without the file version the text is read as UTF-8
+    assert_eq!(channel.get_name(), "Temp\u{FFFD}rature");
+}
@@ -0,0 +1,45 @@
+//! Serialization tests for the plain CAN frame and catalog types
+//!
+//! Only built with the `serde` feature enabled.
+#![cfg(feature = "serde")]
+
+use mdflib::*;
+
+#[test]
+fn test_frame_serde_round_trip() {
+    let frame = Frame {
+        id: 0x123,
+        extended: false,
+        dlc: 4,
+        data: vec![0x01, 0x02, 0x03, 0x04],
+        timestamp: 42_000,
+    };
+
+    let json = serde_json::to_string(&frame).unwrap();
+    let back: Frame = serde_json::from_str(&json).unwrap();
+    assert_eq!(back, frame);
+}
+
+#[test]
+fn test_channel_info_serde_round_trip() {
+    let info = ChannelInfo {
+        name: "EngineSpeed".to_string(),
+        unit: "rpm".to_string(),
+        description: "Engine speed".to_string(),
+        channel_type: ChannelType::FixedLength as u8,
+        data_type: ChannelDataType::FloatLe as u8,
+        data_bytes: 8,
+    };
+
+    let json = serde_json::to_string(&info).unwrap();
+    let back: ChannelInfo = serde_json::from_str(&json).unwrap();
+    assert_eq!(back, info);
+
+    let header = HeaderInfo {
+        author: "Test Author".to_string(),
+        start_time: 1_000_000_000,
+        ..Default::default()
+    };
+    let json = serde_json::to_string(&header).unwrap();
+    assert_eq!(serde_json::from_str::<HeaderInfo>(&json).unwrap(), header);
+}
@@ -0,0 +1,85 @@
+//! Tests for routing mdflib log messages to `tracing`
+//!
+//! Only built with the `tracing` feature enabled.
+#![cfg(feature = "tracing")]
+
+use mdflib::log::{tracing_callback, MdfLogSeverity};
+use std::sync::{Arc, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Level, Metadata, Subscriber};
+
+/// Subscriber recording the level, target and message of every event
+#[derive(Clone, Default)]
+struct CaptureSubscriber {
+    events: Arc<Mutex<Vec<(Level, String, String)>>>,
+}
+
+struct MessageVisitor(String);
+
+impl Visit for MessageVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            self.0 = format!("{value:?}");
+        }
+    }
+}
+
+impl Subscriber for CaptureSubscriber {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, _span: &Attributes<'_>) -> Id {
+        Id::from_u64(1)
+    }
+
+    fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut visitor = MessageVisitor(String::new());
+        event.record(&mut visitor);
+        let metadata = event.metadata();
+        self.events.lock().unwrap().push((
+            *metadata.level(),
+            metadata.target().to_string(),
+            visitor.0,
+        ));
+    }
+
+    fn enter(&self, _span: &Id) {}
+
+    fn exit(&self, _span: &Id) {}
+}
+
+#[test]
+fn test_tracing_callback_emits_event() {
+    let subscriber = CaptureSubscriber::default();
+    let events = subscriber.events.clone();
+
+    tracing::subscriber::with_default(subscriber, || {
+        tracing_callback(
+            MdfLogSeverity::kWarning,
+            c"Block not found".as_ptr() as *const u8,
+        );
+        tracing_callback(
+            MdfLogSeverity::kCritical,
+            c"File corrupt".as_ptr() as *const u8,
+        );
+    });
+
+    let events = events.lock().unwrap();
+    assert_eq!(events.len(), 2);
+    assert_eq!(
+        events[0],
+        (
+            Level::WARN,
+            "mdflib".to_string(),
+            "Block not found".to_string()
+        )
+    );
+    assert_eq!(events[1].0, Level::ERROR);
+    assert_eq!(events[1].2, "File corrupt");
+}
@@ -0,0 +1,143 @@
+//! Integration tests for MDF file verification
+//!
+//! These tests write a small MDF4 file, verify it and then corrupt it to check
+//! that the inconsistencies are reported, or skipped when reading tolerantly.
+
+use mdflib::*;
+use std::io::{Seek, SeekFrom, Write};
+use tempfile::NamedTempFile;
+
+fn write_test_file(file_path: &std::path::Path) {
+    let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, file_path)
+        .expect("Failed to create MDF writer");
+
+    let mut header = writer.get_header().unwrap();
+    header.set_description("Test MDF4 file for verification");
+
+    let mut data_group = writer.create_data_group().unwrap();
+    let mut channel_group = data_group.create_channel_group().unwrap();
+    channel_group.set_name("VerifyGroup");
+
+    let mut channel = channel_group.create_channel().unwrap();
+    channel.set_name("Counter");
+    channel.set_data_type(mdflib_sys::ChannelDataType::UnsignedIntegerLe as u8);
+    channel.set_data_bytes(4);
+
+    writer.init_measurement();
+    writer.start_measurement(1000);
+    for i in 0..10 {
+        writer.save_sample(&channel_group, 1000 + i * 1000);
+    }
+    writer.stop_measurement(11000);
+    writer.finalize_measurement();
+}
+
+/// A freshly written file should pass verification
+#[test]
+fn test_verify_well_formed_file() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+    write_test_file(file_path);
+
+    let mut reader = MdfReader::new(file_path).expect("Failed to create MDF reader");
+    let report = reader.verify().expect("Verification should run");
+
+    assert!(report.is_ok(), "Unexpected issues: {:?}", report.issues);
+    assert!(report.finalized);
+    assert_eq!(report.data_group_count, 1);
+}
+
+/// A file with its header block overwritten should report issues
+#[test]
+fn test_verify_corrupted_file() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+    write_test_file(file_path);
+
+    {
+        // Keep the 64 byte ID block intact but destroy the HD block after it
+        let mut file = std::fs::OpenOptions::new()
+            .write(true)
+            .open(file_path)
+            .unwrap();
+        file.seek(SeekFrom::Start(64)).unwrap();
+        file.write_all(&[0xFF; 128]).unwrap();
+    }
+
+    let mut reader = MdfReader::new(file_path).expect("Failed to create MDF reader");
+    let report = reader.verify().expect("Verification should run");
+
+    assert!(!report.is_ok(), "Corrupted file should report issues");
+}
+
+/// Reading with one corrupt data group should skip only that group in tolerant mode
+#[test]
+fn test_tolerant_read_skips_unreadable_group() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+
+    {
+        let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, file_path)
+            .expect("Failed to create MDF writer");
+
+        for name in ["Good", "Bad"] {
+            let mut data_group = writer.create_data_group().unwrap();
+            let mut channel_group = data_group.create_channel_group().unwrap();
+            channel_group.set_name(name);
+            let mut channel = channel_group.create_channel().unwrap();
+            channel.set_name(&format!("{name}Counter"));
+            channel.set_data_type(mdflib_sys::ChannelDataType::UnsignedIntegerLe as u8);
+            channel.set_data_bytes(4);
+        }
+
+        writer.init_measurement();
+        writer.start_measurement(1000);
+        for dg_index in 0..2 {
+            let data_group = writer.get_file().unwrap().get_data_group(dg_index).unwrap();
+            let channel_group = data_group.get_channel_group_by_index(0).unwrap();
+            for i in 0..10 {
+                writer.save_sample(&channel_group, 1000 + i * 1000);
+            }
+        }
+        writer.stop_measurement(11000);
+        writer.finalize_measurement();
+    }
+
+    {
+        // Point the data link of the second DG block far beyond the end of file
+        let bytes = std::fs::read(file_path).unwrap();
+        let dg_offset = bytes
+            .windows(4)
+            .enumerate()
+            .filter(|(_, w)| *w == b"##DG")
+            .map(|(offset, _)| offset)
+            .nth(1)
+            .expect("Second DG block not found");
+        // Block header is 24 bytes, the data link follows the next DG and first CG links
+        let data_link = dg_offset as u64 + 24 + 16;
+
+        let mut file = std::fs::OpenOptions::new()
+            .write(true)
+            .open(file_path)
+            .unwrap();
+        file.seek(SeekFrom::Start(data_link)).unwrap();
+        file.write_all(&(bytes.len() as u64 * 1000).to_le_bytes())
+            .unwrap();
+    }
+
+    let mut reader = MdfReader::new(file_path).expect("Failed to create MDF reader");
+    reader.read_everything_but_data().unwrap();
+    assert!(reader.read_all_data().is_err());
+
+    reader.set_tolerant(true);
+    let skipped = reader
+        .read_all_data()
+        .expect("Tolerant read should succeed");
+    assert_eq!(skipped.len(), 1);
+    assert_eq!(skipped[0].index, 1);
+    assert_eq!(skipped[0].channel_groups, vec!["Bad".to_string()]);
+
+    let good = reader.get_data_group(0).unwrap();
+    let cg = good.get_channel_group_by_index(0).unwrap();
+    assert_eq!(cg.get_nof_samples(), 10);
+}
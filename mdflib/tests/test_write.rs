@@ -93,3 +93,2620 @@ fn test_etag_functionality() {
     assert_eq!(etag.get_unit(), "V");
     assert_eq!(etag.get_value_as_string(), "test_value");
 }
+
+/// Test writing a channel comment and tags and reading them back
+#[test]
+fn test_channel_comment_and_etags() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+
+    {
+        let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, file_path)
+            .expect("Failed to create MDF writer");
+
+        let mut data_group = writer.create_data_group().unwrap();
+        let mut channel_group = data_group.create_channel_group().unwrap();
+        channel_group.set_name("CommentGroup");
+
+        {
+            let mut channel = channel_group.create_channel().unwrap();
+            channel.set_name("EngineSpeed");
+            channel.set_unit("rpm");
+            channel.set_description("Crankshaft speed");
+            channel.set_data_type(mdflib_sys::ChannelDataType::FloatLe as u8);
+            channel.set_data_bytes(8);
+
+            let mut metadata = channel.create_metadata().unwrap();
+            let mut tag = ETag::new().unwrap();
+            tag.set_name("Sensor").unwrap();
+            tag.set_value_as_string("Hall").unwrap();
+            metadata.add_common_property(&tag);
+        }
+
+        writer.init_measurement();
+        writer.start_measurement(1000);
+        writer.save_sample(&channel_group, 1000);
+        writer.stop_measurement(2000);
+        writer.finalize_measurement();
+    }
+
+    let mut reader = MdfReader::new(file_path).expect("Failed to create MDF reader");
+    reader.read_everything_but_data().unwrap();
+
+    let dg = reader.get_data_group(0).unwrap();
+    let cg = dg.get_channel_group("CommentGroup").unwrap();
+    let channel = cg.get_channel("EngineSpeed").unwrap();
+
+    assert_eq!(channel.get_comment(), "Crankshaft speed");
+    assert!(channel
+        .get_etags()
+        .contains(&("Sensor".to_string(), "Hall".to_string())));
+}
+
+/// Test the typed event setters and getters
+#[test]
+fn test_event_typed_enums() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, temp_file.path())
+        .expect("Failed to create MDF writer");
+
+    let mut header = writer.get_header().unwrap();
+    let mut event = header.create_event().unwrap();
+
+    event.set_event_type(EventType::StartRecording);
+    event.set_sync_type(SyncType::SyncTime);
+    event.set_range_type(RangeType::RangeStart);
+    event.set_event_cause(EventCause::CauseUser);
+
+    assert_eq!(event.get_event_type().unwrap(), EventType::StartRecording);
+    assert_eq!(event.get_sync_type().unwrap(), SyncType::SyncTime);
+    assert_eq!(event.get_range_type().unwrap(), RangeType::RangeStart);
+    assert_eq!(event.get_event_cause().unwrap(), EventCause::CauseUser);
+
+    event.set_type(42);
+    assert!(matches!(
+        event.get_event_type(),
+        Err(MdfError::InvalidEnumValue("EventType", 42))
+    ));
+}
+
+/// Test writing an attachment that references an external file
+#[test]
+fn test_external_attachment() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+
+    let mut external = NamedTempFile::new().unwrap();
+    std::io::Write::write_all(&mut external, b"external log data").unwrap();
+    let external_path = external.path().to_str().unwrap().to_string();
+
+    {
+        let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, file_path)
+            .expect("Failed to create MDF writer");
+
+        let mut header = writer.get_header().unwrap();
+        let mut attachment = header.create_attachment().unwrap();
+        attachment
+            .set_external_file(&external_path)
+            .expect("Failed to set external file");
+        attachment.set_file_type("text/plain").unwrap();
+
+        let mut data_group = writer.create_data_group().unwrap();
+        let mut channel_group = data_group.create_channel_group().unwrap();
+        let mut channel = channel_group.create_channel().unwrap();
+        channel.set_name("Dummy");
+        channel.set_data_type(mdflib_sys::ChannelDataType::UnsignedIntegerLe as u8);
+        channel.set_data_bytes(4);
+
+        writer.init_measurement();
+        writer.start_measurement(1000);
+        writer.stop_measurement(2000);
+        writer.finalize_measurement();
+    }
+
+    let mut reader = MdfReader::new(file_path).expect("Failed to create MDF reader");
+    reader.read_everything_but_data().unwrap();
+
+    let file = reader.get_file().unwrap();
+    let attachments = file.get_attachments();
+    assert_eq!(attachments.len(), 1);
+    assert_eq!(attachments[0].get_filename(), external_path);
+    assert!(!attachments[0].get_embedded());
+}
+
+/// Test writing a data group comment and reading it back
+#[test]
+fn test_data_group_comment() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+
+    {
+        let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, file_path)
+            .expect("Failed to create MDF writer");
+
+        let mut data_group = writer.create_data_group().unwrap();
+        data_group
+            .set_comment("Cold start at -20 degC")
+            .expect("Failed to set comment");
+
+        let mut channel_group = data_group.create_channel_group().unwrap();
+        let mut channel = channel_group.create_channel().unwrap();
+        channel.set_name("Dummy");
+        channel.set_data_type(mdflib_sys::ChannelDataType::UnsignedIntegerLe as u8);
+        channel.set_data_bytes(4);
+
+        writer.init_measurement();
+        writer.start_measurement(1000);
+        writer.stop_measurement(2000);
+        writer.finalize_measurement();
+    }
+
+    let mut reader = MdfReader::new(file_path).expect("Failed to create MDF reader");
+    reader.read_everything_but_data().unwrap();
+
+    let dg = reader.get_data_group(0).unwrap();
+    assert!(dg.get_metadata().is_some());
+    assert_eq!(dg.get_comment(), "Cold start at -20 degC");
+}
+
+/// Test the record byte count and measurement byte size after writing
+#[test]
+fn test_record_byte_count() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+
+    {
+        let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, file_path)
+            .expect("Failed to create MDF writer");
+
+        let mut data_group = writer.create_data_group().unwrap();
+        let mut channel_group = data_group.create_channel_group().unwrap();
+        for (name, data_type, bytes) in [
+            ("Time", ChannelDataType::FloatLe, 8),
+            ("Counter", ChannelDataType::UnsignedIntegerLe, 4),
+            ("Flag", ChannelDataType::UnsignedIntegerLe, 1),
+        ] {
+            let mut channel = channel_group.create_channel().unwrap();
+            channel.set_name(name);
+            channel.set_data_type(data_type as u8);
+            channel.set_data_bytes(bytes);
+        }
+
+        writer.init_measurement();
+        writer.start_measurement(1000);
+        let group = data_group.get_channel_group_by_index(0).unwrap();
+        for i in 0..5 {
+            writer.save_sample(&group, 1000 + i * 1000);
+        }
+        writer.stop_measurement(6000);
+        writer.finalize_measurement();
+    }
+
+    let mut reader = MdfReader::new(file_path).expect("Failed to create MDF reader");
+    reader.read_everything_but_data().unwrap();
+
+    let dg = reader.get_data_group(0).unwrap();
+    let cg = dg.get_channel_group_by_index(0).unwrap();
+    let expected: u64 = cg.get_channels().iter().map(|cn| cn.get_data_bytes()).sum();
+    assert_eq!(expected, 13);
+    assert_eq!(cg.get_record_byte_count(), expected);
+
+    let file = reader.get_file().unwrap();
+    assert_eq!(
+        file.get_measurement_byte_size(),
+        cg.get_nof_samples() * expected
+    );
+}
+
+/// Test merging properties into existing metadata keeps the other keys
+#[test]
+fn test_metadata_merge_properties() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+
+    let writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, file_path)
+        .expect("Failed to create MDF writer");
+    let mut header = writer.get_header().unwrap();
+    let mut metadata = header.create_metadata().unwrap();
+
+    metadata
+        .set_property_as_string("vehicle", "Test Car")
+        .unwrap();
+    metadata
+        .merge_properties(&[
+            ("driver".to_string(), "Jane".to_string()),
+            ("track".to_string(), "North Loop".to_string()),
+        ])
+        .unwrap();
+
+    assert_eq!(
+        metadata.get_property_as_string("vehicle").unwrap(),
+        "Test Car"
+    );
+    assert_eq!(metadata.get_property_as_string("driver").unwrap(), "Jane");
+    assert_eq!(
+        metadata.get_property_as_string("track").unwrap(),
+        "North Loop"
+    );
+
+    // A key with a null byte is rejected without touching the metadata
+    assert!(metadata
+        .merge_properties(&[
+            ("vehicle".to_string(), "Other Car".to_string()),
+            ("bad\0key".to_string(), String::new()),
+        ])
+        .is_err());
+    assert_eq!(
+        metadata.get_property_as_string("vehicle").unwrap(),
+        "Test Car"
+    );
+}
+
+/// Test counting and indexed access of the header events
+#[test]
+fn test_header_event_access() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, temp_file.path())
+        .expect("Failed to create MDF writer");
+
+    let mut header = writer.get_header().unwrap();
+    for name in ["Start", "Marker", "Stop"] {
+        let mut event = header.create_event().unwrap();
+        event.set_name(name).unwrap();
+    }
+
+    assert_eq!(header.get_event_count(), 3);
+    assert_eq!(header.get_event(1).unwrap().get_name(), "Marker");
+    assert!(header.get_event(3).is_none());
+
+    let names: Vec<String> = header.events_iter().map(|event| event.get_name()).collect();
+    assert_eq!(names, ["Start", "Marker", "Stop"]);
+}
+
+/// Test copying a populated header into the header of another writer
+#[test]
+fn test_header_copy_from() {
+    let src_file = NamedTempFile::new().unwrap();
+    let dst_file = NamedTempFile::new().unwrap();
+    let mut src_writer =
+        writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, src_file.path())
+            .expect("Failed to create MDF writer");
+    let dst_writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, dst_file.path())
+        .expect("Failed to create MDF writer");
+
+    {
+        let mut header = src_writer.get_header().unwrap();
+        header.set_measurement_id("Measurement");
+        header.set_recorder_id("Recorder");
+        header.set_recorder_index(3);
+        header.set_author("Author");
+        header.set_department("Department");
+        header.set_project("Project");
+        header.set_subject("Subject");
+        header.set_description("Description");
+        header.set_start_time(1_700_000_000_000_000_000);
+        header
+            .set_comment_xml("<HDcomment><TX>Copied</TX></HDcomment>")
+            .unwrap();
+    }
+    src_writer
+        .add_file_history(FileHistoryEntry {
+            description: Some("Created".to_string()),
+            tool_name: Some("mdflib-rs".to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+
+    let src = src_writer.get_header().unwrap();
+    let mut dst = dst_writer.get_header().unwrap();
+    dst.copy_from(&src).unwrap();
+
+    assert_eq!(dst.get_measurement_id(), src.get_measurement_id());
+    assert_eq!(dst.get_recorder_id(), src.get_recorder_id());
+    assert_eq!(dst.get_recorder_index(), 3);
+    assert_eq!(dst.get_author(), "Author");
+    assert_eq!(dst.get_department(), "Department");
+    assert_eq!(dst.get_project(), "Project");
+    assert_eq!(dst.get_subject(), "Subject");
+    assert_eq!(dst.get_description(), "Description");
+    assert_eq!(dst.get_start_time(), src.get_start_time());
+    assert_eq!(dst.get_comment_xml(), src.get_comment_xml());
+
+    let history = dst
+        .get_file_histories()
+        .into_iter()
+        .find(|history| history.get_description() == "Created")
+        .expect("File history not copied");
+    assert_eq!(history.get_tool_name(), "mdflib-rs");
+}
+
+/// Test selecting the header events within a time range
+#[test]
+fn test_header_events_in_range() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, temp_file.path())
+        .expect("Failed to create MDF writer");
+
+    let start_time = 1_700_000_000_000_000_000;
+    let mut header = writer.get_header().unwrap();
+    header.set_start_time(start_time);
+    for (name, offset_ms) in [("Early", 500), ("Middle", 1500), ("Late", 3000)] {
+        let mut event = header.create_event().unwrap();
+        event.set_name(name).unwrap();
+        event.set_sync_type(SyncType::SyncTime);
+        event.set_sync_value(offset_ms);
+        event.set_sync_factor(0.001);
+    }
+    {
+        let mut event = header.create_event().unwrap();
+        event.set_name("Index").unwrap();
+        event.set_sync_type(SyncType::SyncIndex);
+        event.set_sync_value(1);
+        event.set_sync_factor(1.0);
+    }
+
+    let names: Vec<String> = header
+        .events_in_range(start_time + 1_000_000_000, start_time + 3_000_000_000)
+        .iter()
+        .map(|event| event.get_name())
+        .collect();
+    assert_eq!(names, ["Middle", "Late"]);
+    assert!(header
+        .events_in_range(start_time + 3_500_000_000, start_time + 4_000_000_000)
+        .is_empty());
+}
+
+/// Test pairing the begin and end events of a range
+#[test]
+fn test_header_event_ranges() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, temp_file.path())
+        .expect("Failed to create MDF writer");
+
+    let mut header = writer.get_header().unwrap();
+    header.create_event().unwrap().set_name("Marker").unwrap();
+    {
+        let (mut begin, mut end) = header.create_event_range().unwrap();
+        begin.set_name("Begin").unwrap();
+        begin.set_sync_value(1);
+        end.set_name("End").unwrap();
+        end.set_sync_value(5);
+    }
+
+    let ranges = header.get_event_ranges();
+    assert_eq!(ranges.len(), 1);
+    let (begin, end) = &ranges[0];
+    assert_eq!(begin.get_name(), "Begin");
+    assert_eq!(begin.get_range_type().unwrap(), RangeType::RangeStart);
+    assert_eq!(end.get_name(), "End");
+    assert_eq!(end.get_range_type().unwrap(), RangeType::RangeEnd);
+    assert_eq!(end.get_range_event().unwrap().get_name(), "Begin");
+}
+
+/// Test configuring a CAN source on a channel group
+#[test]
+fn test_source_information_configure_can() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, temp_file.path())
+        .expect("Failed to create MDF writer");
+
+    let mut data_group = writer.create_data_group().unwrap();
+    let mut channel_group = data_group.create_channel_group().unwrap();
+    let mut source = channel_group.create_source_information().unwrap();
+    source.configure_can("CAN1", "can0").unwrap();
+
+    assert_eq!(source.get_name(), "CAN1");
+    assert_eq!(source.get_path(), "can0");
+    assert_eq!(source.get_type_enum().unwrap(), SourceType::Bus);
+    assert_eq!(source.get_bus_enum().unwrap(), BusType::Can);
+}
+
+/// Test adding a file history entry in one call
+#[test]
+fn test_add_file_history() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+
+    let entry = FileHistoryEntry {
+        time: Some(1_700_000_000_000_000_000),
+        description: Some("Reprocessed".to_string()),
+        tool_name: Some("mdflib-rs".to_string()),
+        tool_vendor: Some("Example Vendor".to_string()),
+        tool_version: Some("1.2.3".to_string()),
+        user_name: Some("Test User".to_string()),
+    };
+
+    {
+        let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, file_path)
+            .expect("Failed to create MDF writer");
+        writer.add_file_history(entry.clone()).unwrap();
+
+        let mut data_group = writer.create_data_group().unwrap();
+        let mut channel_group = data_group.create_channel_group().unwrap();
+        let mut channel = channel_group.create_channel().unwrap();
+        channel.set_name("Dummy");
+        channel.set_data_type(mdflib_sys::ChannelDataType::UnsignedIntegerLe as u8);
+        channel.set_data_bytes(4);
+
+        writer.init_measurement();
+        writer.start_measurement(1000);
+        writer.stop_measurement(2000);
+        writer.finalize_measurement();
+    }
+
+    let mut reader = MdfReader::new(file_path).expect("Failed to create MDF reader");
+    reader.read_everything_but_data().unwrap();
+
+    let header = reader.get_header().unwrap();
+    let histories = header.get_file_histories();
+    let history = histories
+        .iter()
+        .find(|history| history.get_description() == "Reprocessed")
+        .expect("File history not found");
+    assert_eq!(history.get_time(), entry.time.unwrap());
+    assert_eq!(history.get_tool_name(), "mdflib-rs");
+    assert_eq!(history.get_tool_vendor(), "Example Vendor");
+    assert_eq!(history.get_tool_version(), "1.2.3");
+    assert_eq!(history.get_user_name(), "Test User");
+}
+
+#[test]
+fn test_file_history_timezone_offsets() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+
+    {
+        let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, file_path)
+            .expect("Failed to create MDF writer");
+        {
+            let mut header = writer.get_header().unwrap();
+            let mut history = header.create_file_history().unwrap();
+            history.set_time(1_700_000_000_000_000_000);
+            history.set_timezone_offset_min(60);
+            history.set_dst_offset_min(60);
+            history.set_description("Summer time").unwrap();
+            assert_eq!(history.get_timezone_offset_min(), 60);
+            assert_eq!(history.get_dst_offset_min(), 60);
+        }
+
+        let mut data_group = writer.create_data_group().unwrap();
+        let mut channel_group = data_group.create_channel_group().unwrap();
+        let mut channel = channel_group.create_channel().unwrap();
+        channel.set_name("Dummy");
+        channel.set_data_type(mdflib_sys::ChannelDataType::UnsignedIntegerLe as u8);
+        channel.set_data_bytes(4);
+
+        writer.init_measurement();
+        writer.start_measurement(1000);
+        writer.stop_measurement(2000);
+        writer.finalize_measurement();
+    }
+
+    let mut reader = MdfReader::new(file_path).expect("Failed to create MDF reader");
+    reader.read_everything_but_data().unwrap();
+
+    let header = reader.get_header().unwrap();
+    let histories = header.get_file_histories();
+    let history = histories
+        .iter()
+        .find(|history| history.get_description() == "Summer time")
+        .expect("File history not found");
+    assert_eq!(history.get_time(), 1_700_000_000_000_000_000);
+    assert_eq!(history.get_timezone_offset_min(), 60);
+    assert_eq!(history.get_dst_offset_min(), 60);
+}
+
+/// Test labeling an attachment with a typed file type
+#[test]
+fn test_attachment_file_type_typed() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, temp_file.path())
+        .expect("Failed to create MDF writer");
+
+    let mut header = writer.get_header().unwrap();
+    let mut attachment = header.create_attachment().unwrap();
+    attachment.set_filename("vehicle.dbc").unwrap();
+    attachment
+        .set_file_type_typed(AttachmentFileType::Dbc)
+        .unwrap();
+
+    assert_eq!(attachment.get_file_type(), "application/x-dbc");
+    assert_eq!(
+        attachment.get_file_type(),
+        AttachmentFileType::Dbc.to_string()
+    );
+}
+
+/// Test listing the attachments of the file and header once each
+#[test]
+fn test_all_attachments() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+
+    let mut dbc = NamedTempFile::new().unwrap();
+    std::io::Write::write_all(&mut dbc, b"VERSION \"\"").unwrap();
+    let mut notes = NamedTempFile::new().unwrap();
+    std::io::Write::write_all(&mut notes, b"test notes").unwrap();
+
+    {
+        let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, file_path)
+            .expect("Failed to create MDF writer");
+
+        let mut file = writer.get_file().unwrap();
+        let mut file_attachment = file.create_attachment().unwrap();
+        file_attachment.set_external_file(dbc.path()).unwrap();
+
+        let mut header = writer.get_header().unwrap();
+        let mut header_attachment = header.create_attachment().unwrap();
+        header_attachment.set_external_file(notes.path()).unwrap();
+
+        let mut data_group = writer.create_data_group().unwrap();
+        let mut channel_group = data_group.create_channel_group().unwrap();
+        let mut channel = channel_group.create_channel().unwrap();
+        channel.set_name("Dummy");
+        channel.set_data_type(mdflib_sys::ChannelDataType::UnsignedIntegerLe as u8);
+        channel.set_data_bytes(4);
+
+        writer.init_measurement();
+        writer.start_measurement(1000);
+        writer.stop_measurement(2000);
+        writer.finalize_measurement();
+    }
+
+    let mut reader = MdfReader::new(file_path).expect("Failed to create MDF reader");
+    reader.read_everything_but_data().unwrap();
+
+    let attachments = reader.all_attachments();
+    assert_eq!(attachments.len(), 2);
+    let mut filenames: Vec<String> = attachments.iter().map(|at| at.get_filename()).collect();
+    filenames.sort();
+    let mut expected = vec![
+        dbc.path().to_str().unwrap().to_string(),
+        notes.path().to_str().unwrap().to_string(),
+    ];
+    expected.sort();
+    assert_eq!(filenames, expected);
+}
+
+fn write_minimal_file(file_path: &std::path::Path) {
+    let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, file_path)
+        .expect("Failed to create MDF writer");
+
+    let mut data_group = writer.create_data_group().unwrap();
+    let mut channel_group = data_group.create_channel_group().unwrap();
+    let mut channel = channel_group.create_channel().unwrap();
+    channel.set_name("Dummy");
+    channel.set_data_type(mdflib_sys::ChannelDataType::UnsignedIntegerLe as u8);
+    channel.set_data_bytes(4);
+
+    writer.init_measurement();
+    writer.start_measurement(1000);
+    writer.stop_measurement(2000);
+    writer.finalize_measurement();
+}
+
+/// Test opening, closing and re-opening the reader
+#[test]
+fn test_reader_open_close_open() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+    write_minimal_file(file_path);
+
+    let mut reader = MdfReader::new(file_path).expect("Failed to create MDF reader");
+    reader.open().expect("Failed to open");
+    assert!(reader.is_open());
+    reader.open().expect("Opening twice should succeed");
+    assert!(reader.is_open());
+
+    reader.close();
+    assert!(!reader.is_open());
+
+    reader.open().expect("Failed to re-open");
+    assert!(reader.is_open());
+    reader.read_everything_but_data().unwrap();
+    assert_eq!(reader.get_data_group_count(), 1);
+    reader.close();
+    assert!(!reader.is_open());
+}
+
+/// Test closing the reader twice
+#[test]
+fn test_reader_double_close() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+    write_minimal_file(file_path);
+
+    let mut reader = MdfReader::new(file_path).expect("Failed to create MDF reader");
+    reader.read_everything_but_data().unwrap();
+    reader.close();
+    reader.close();
+    assert!(!reader.is_open());
+
+    // The structure read before closing is still available
+    assert_eq!(reader.get_data_group_count(), 1);
+}
+
+/// Test the declared and stored sample counts of a channel group
+#[test]
+fn test_stored_sample_count() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+
+    {
+        let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, file_path)
+            .expect("Failed to create MDF writer");
+
+        let mut data_group = writer.create_data_group().unwrap();
+        let mut channel_group = data_group.create_channel_group().unwrap();
+        let mut channel = channel_group.create_channel().unwrap();
+        channel.set_name("Counter");
+        channel.set_data_type(ChannelDataType::UnsignedIntegerLe as u8);
+        channel.set_data_bytes(4);
+
+        writer.init_measurement();
+        writer.start_measurement(1000);
+        let group = data_group.get_channel_group_by_index(0).unwrap();
+        for i in 0..10 {
+            writer.save_sample(&group, 1000 + i * 1000);
+        }
+        writer.stop_measurement(11000);
+        writer.finalize_measurement();
+    }
+
+    let mut reader = MdfReader::new(file_path).expect("Failed to create MDF reader");
+    reader.read_everything_but_data().unwrap();
+
+    let dg = reader.get_data_group(0).unwrap();
+    let cg = dg.get_channel_group_by_index(0).unwrap();
+    assert_eq!(cg.get_nof_samples(), 10);
+    assert_eq!(cg.get_stored_sample_count(&reader).unwrap(), 10);
+    // Counting isn't added to the records read
+    assert_eq!(reader.records_read(), 0);
+}
+
+/// Test importing a CSV file and reading the channels back
+#[test]
+fn test_import_csv() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+    let csv = "Time,Speed,Temp\n0.0,1.5,20\n0.1,,21\n0.2,3.5,22\n";
+
+    {
+        let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, file_path)
+            .expect("Failed to create MDF writer");
+        writer
+            .import_csv(csv.as_bytes(), "Time")
+            .expect("Failed to import CSV");
+    }
+
+    let mut reader = MdfReader::new(file_path).expect("Failed to create MDF reader");
+    reader.read_everything_but_data().unwrap();
+
+    let file = reader.get_file().unwrap();
+    let mut dg = file.get_data_group(0).unwrap();
+    let cg = dg.get_channel_group_by_index(0).unwrap();
+    assert_eq!(cg.get_nof_samples(), 3);
+    assert_eq!(cg.get_channel_count(), 3);
+
+    let time = cg.get_channel("Time").unwrap();
+    assert_eq!(time.get_type(), ChannelType::Master as u8);
+    assert_eq!(time.get_unit(), "s");
+
+    let speed = cg.get_channel("Speed").unwrap();
+    let temp = cg.get_channel("Temp").unwrap();
+    let speed_observer =
+        unsafe { create_channel_observer(dg.as_ptr(), cg.as_ptr(), speed.as_ptr()) }.unwrap();
+    let temp_observer =
+        unsafe { create_channel_observer(dg.as_ptr(), cg.as_ptr(), temp.as_ptr()) }.unwrap();
+    reader.read_data(&mut dg).unwrap();
+
+    assert_eq!(
+        speed_observer.get_all_eng_values(),
+        [Some(1.5), None, Some(3.5)]
+    );
+    assert_eq!(
+        temp_observer.get_all_eng_values(),
+        [Some(20.0), Some(21.0), Some(22.0)]
+    );
+}
+
+/// Test importing a CSV file without the time column
+#[test]
+fn test_import_csv_missing_time_column() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, temp_file.path())
+        .expect("Failed to create MDF writer");
+
+    let result = writer.import_csv("Speed\n1.0\n".as_bytes(), "Time");
+    assert!(matches!(result, Err(MdfError::NotFound(_))));
+}
+
+/// Test that invalid CSV times are rejected before the measurement is started
+#[test]
+fn test_import_csv_invalid_time() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, temp_file.path())
+        .expect("Failed to create MDF writer");
+
+    for csv in [
+        "Time,Speed\n0.0,1.0\n-0.1,2.0\n",
+        "Time,Speed\n0.0,1.0\nNaN,2.0\n",
+        "Time,Speed\n0.0,1.0\n,2.0\n",
+    ] {
+        let result = writer.import_csv(csv.as_bytes(), "Time");
+        assert!(matches!(result, Err(MdfError::InvalidFormat)), "{csv:?}");
+    }
+    // Nothing was written, so a valid file can still be imported
+    assert!(writer.get_file().unwrap().get_data_groups().is_empty());
+    writer
+        .import_csv("Time,Speed\n0.0,1.0\n".as_bytes(), "Time")
+        .expect("Failed to import CSV");
+
+    // The measurement is finalized, so a second import is rejected
+    let result = writer.import_csv("Time,Speed\n0.0,1.0\n".as_bytes(), "Time");
+    assert!(matches!(result, Err(MdfError::InvalidWriterState(_))));
+}
+
+/// Test transcoding an MDF4 file into another MDF4 file keeps the channel data
+#[test]
+fn test_transcode() {
+    let src_file = NamedTempFile::new().unwrap();
+    let dst_file = NamedTempFile::new().unwrap();
+
+    {
+        let mut writer =
+            writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, src_file.path())
+                .expect("Failed to create MDF writer");
+        writer.get_header().unwrap().set_author("Transcoder");
+
+        let mut data_group = writer.create_data_group().unwrap();
+        let mut channel_group = data_group.create_channel_group().unwrap();
+        channel_group.set_name("Counters");
+
+        let mut master = channel_group.create_channel().unwrap();
+        master.set_name("Time");
+        master.set_type(ChannelType::Master as u8);
+        master.set_sync(ChannelSyncType::Time as u8);
+        master.set_data_type(ChannelDataType::FloatLe as u8);
+        master.set_data_bytes(8);
+        master.set_unit("s");
+
+        let mut channel = channel_group.create_channel().unwrap();
+        channel.set_name("Counter");
+        channel.set_unit("rpm");
+        channel.set_data_type(ChannelDataType::UnsignedIntegerLe as u8);
+        channel.set_data_bytes(4);
+        let mut conversion = channel.create_channel_conversion().unwrap();
+        conversion.set_type(ConversionType::Linear as u8);
+        conversion.set_parameter_as_double(0, 10.0);
+        conversion.set_parameter_as_double(1, 2.0);
+
+        writer.init_measurement();
+        writer.start_measurement(1_000_000_000);
+        let group = data_group.get_channel_group_by_index(0).unwrap();
+        for i in 0..5u64 {
+            channel.set_channel_value_u64(i, true);
+            writer.save_sample(&group, 1_000_000_000 + i * 100_000_000);
+        }
+        writer.stop_measurement(1_500_000_000);
+        writer.finalize_measurement();
+    }
+
+    transcode(src_file.path(), dst_file.path(), MdfWriterType::Mdf4Basic)
+        .expect("Failed to transcode");
+
+    let read_counter = |path: &std::path::Path| {
+        let mut reader = MdfReader::new(path).expect("Failed to create MDF reader");
+        reader.read_everything_but_data().unwrap();
+        let file = reader.get_file().unwrap();
+        assert_eq!(file.get_header().get_author(), "Transcoder");
+        let mut dg = file.get_data_group(0).unwrap();
+        let cg = dg.get_channel_group("Counters").unwrap();
+        let channel = cg.get_channel("Counter").unwrap();
+        assert_eq!(channel.get_unit(), "rpm");
+        let observer =
+            unsafe { create_channel_observer(dg.as_ptr(), cg.as_ptr(), channel.as_ptr()) }.unwrap();
+        reader.read_data(&mut dg).unwrap();
+        (observer.read_typed(), observer.take_samples())
+    };
+
+    let (src_raw, src_samples) = read_counter(src_file.path());
+    let (dst_raw, dst_samples) = read_counter(dst_file.path());
+    assert_eq!(src_raw, TypedSamples::U64(vec![0, 1, 2, 3, 4]));
+    assert_eq!(dst_raw, src_raw);
+    assert_eq!(dst_samples.values, [10.0, 12.0, 14.0, 16.0, 18.0]);
+    assert_eq!(dst_samples, src_samples);
+}
+
+/// Test reading back the texts of a value to text conversion
+#[test]
+fn test_channel_conversion_value_to_text() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+    let labels = ["Off", "On", "Error", "Unknown"];
+
+    {
+        let mut writer = writer::MdfWriter::new(MdfWriterType::Mdf4Basic, file_path)
+            .expect("Failed to create MDF writer");
+        let mut data_group = writer.create_data_group().unwrap();
+        let mut channel_group = data_group.create_channel_group().unwrap();
+        let mut channel = channel_group.create_channel().unwrap();
+        channel.set_name("State");
+        channel.set_data_type(ChannelDataType::UnsignedIntegerLe as u8);
+        channel.set_data_bytes(1);
+        let mut conversion = channel.create_channel_conversion().unwrap();
+        conversion.set_type(ConversionType::ValueToText as u8);
+        for (index, label) in labels.iter().enumerate() {
+            if index < 3 {
+                conversion.set_parameter_as_double(index as u16, index as f64);
+            }
+            conversion.set_reference(index as u16, label).unwrap();
+        }
+
+        writer.init_measurement();
+        writer.start_measurement(1000);
+        writer.stop_measurement(2000);
+        writer.finalize_measurement();
+    }
+
+    let mut reader = MdfReader::new(file_path).expect("Failed to create MDF reader");
+    reader.read_everything_but_data().unwrap();
+    let data_group = reader.get_data_group(0).unwrap();
+    let channel_group = data_group.get_channel_group_by_index(0).unwrap();
+    let channel = channel_group.get_channel("State").unwrap();
+    let conversion = channel.get_channel_conversion().unwrap();
+
+    assert_eq!(conversion.get_type(), ConversionType::ValueToText as u8);
+    assert_eq!(conversion.get_nof_references(), 4);
+    let read: Vec<String> = (0..conversion.get_nof_references())
+        .map(|index| conversion.get_reference_as_string(index))
+        .collect();
+    assert_eq!(read, labels);
+}
+
+/// Test resolving a channel's unit reference against the header unit catalog
+#[test]
+fn test_channel_resolve_unit() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+
+    {
+        let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, file_path)
+            .expect("Failed to create MDF writer");
+
+        {
+            let mut header = writer.get_header().unwrap();
+            let mut catalog = header.create_metadata().unwrap();
+            let mut unit = ETag::new().unwrap();
+            unit.set_name("unit_degC").unwrap();
+            unit.set_value_as_string("°C").unwrap();
+            catalog.add_common_property(&unit);
+        }
+
+        let mut data_group = writer.create_data_group().unwrap();
+        let mut channel_group = data_group.create_channel_group().unwrap();
+        channel_group.set_name("UnitGroup");
+
+        {
+            let mut channel = channel_group.create_channel().unwrap();
+            channel.set_name("Temperature");
+            channel.set_data_type(mdflib_sys::ChannelDataType::FloatLe as u8);
+            channel.set_data_bytes(8);
+
+            let mut metadata = channel.create_metadata().unwrap();
+            let mut tag = ETag::new().unwrap();
+            tag.set_name("Unit").unwrap();
+            tag.set_unit_ref("unit_degC").unwrap();
+            metadata.add_common_property(&tag);
+        }
+        {
+            let mut channel = channel_group.create_channel().unwrap();
+            channel.set_name("Speed");
+            channel.set_unit("km/h");
+            channel.set_data_type(mdflib_sys::ChannelDataType::FloatLe as u8);
+            channel.set_data_bytes(8);
+        }
+
+        writer.init_measurement();
+        writer.start_measurement(1000);
+        writer.stop_measurement(2000);
+        writer.finalize_measurement();
+    }
+
+    let mut reader = MdfReader::new(file_path).expect("Failed to create MDF reader");
+    reader.read_everything_but_data().unwrap();
+
+    let dg = reader.get_data_group(0).unwrap();
+    let cg = dg.get_channel_group("UnitGroup").unwrap();
+    let temperature = cg.get_channel("Temperature").unwrap();
+    assert_eq!(temperature.get_unit(), "");
+    assert_eq!(temperature.resolve_unit(&reader), "°C");
+    let speed = cg.get_channel("Speed").unwrap();
+    assert_eq!(speed.resolve_unit(&reader), "km/h");
+}
+
+/// Test writing the header comment XML and reading it back
+#[test]
+fn test_header_comment_xml() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+    let xml = "<HDcomment><TX>Endurance run</TX><common_properties>\
+               <e name=\"vehicle\">Test car</e></common_properties></HDcomment>";
+
+    {
+        let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, file_path)
+            .expect("Failed to create MDF writer");
+
+        let mut header = writer.get_header().unwrap();
+        assert!(header.set_comment_xml("<HDcomment>\0</HDcomment>").is_err());
+        header
+            .set_comment_xml(xml)
+            .expect("Failed to set comment XML");
+        assert_eq!(header.get_comment_xml(), xml);
+
+        let mut data_group = writer.create_data_group().unwrap();
+        let mut channel_group = data_group.create_channel_group().unwrap();
+        let mut channel = channel_group.create_channel().unwrap();
+        channel.set_name("Dummy");
+        channel.set_data_type(mdflib_sys::ChannelDataType::UnsignedIntegerLe as u8);
+        channel.set_data_bytes(4);
+
+        writer.init_measurement();
+        writer.start_measurement(1000);
+        writer.stop_measurement(2000);
+        writer.finalize_measurement();
+    }
+
+    let mut reader = MdfReader::new(file_path).expect("Failed to create MDF reader");
+    reader.read_everything_but_data().unwrap();
+
+    let header = reader.get_header().unwrap();
+    let read_xml = header.get_comment_xml();
+    assert!(read_xml.contains("<TX>Endurance run</TX>"), "{read_xml}");
+    assert!(read_xml.contains("Test car"), "{read_xml}");
+}
+
+/// Test linking an inverse conversion and reading its type back
+#[test]
+fn test_channel_conversion_inverse() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+
+    {
+        let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, file_path)
+            .expect("Failed to create MDF writer");
+
+        let mut data_group = writer.create_data_group().unwrap();
+
+        // The raw channel's conversion serves as the template for the inverse
+        let mut raw_group = data_group.create_channel_group().unwrap();
+        raw_group.set_name("RawGroup");
+        let mut raw = raw_group.create_channel().unwrap();
+        raw.set_name("Raw");
+        raw.set_data_type(ChannelDataType::FloatLe as u8);
+        raw.set_data_bytes(8);
+        let mut template = raw.create_channel_conversion().unwrap();
+        template.set_type(ConversionType::Linear as u8);
+        template.set_parameter_as_double(0, -5.0);
+        template.set_parameter_as_double(1, 0.5);
+
+        let mut channel_group = data_group.create_channel_group().unwrap();
+        channel_group.set_name("ScaledGroup");
+        let mut channel = channel_group.create_channel().unwrap();
+        channel.set_name("Scaled");
+        channel.set_data_type(ChannelDataType::UnsignedIntegerLe as u8);
+        channel.set_data_bytes(4);
+        let mut conversion = channel.create_channel_conversion().unwrap();
+        conversion.set_type(ConversionType::Linear as u8);
+        conversion.set_parameter_as_double(0, 10.0);
+        conversion.set_parameter_as_double(1, 2.0);
+        conversion
+            .set_inverse(&template)
+            .expect("Failed to set inverse");
+
+        writer.init_measurement();
+        writer.start_measurement(1000);
+        writer.stop_measurement(2000);
+        writer.finalize_measurement();
+    }
+
+    let mut reader = MdfReader::new(file_path).expect("Failed to create MDF reader");
+    reader.read_everything_but_data().unwrap();
+
+    let dg = reader.get_data_group(0).unwrap();
+    let cg = dg.get_channel_group("ScaledGroup").unwrap();
+    let channel = cg.get_channel("Scaled").unwrap();
+    let conversion = channel.get_channel_conversion().unwrap();
+    let inverse = conversion
+        .get_inverse()
+        .expect("Inverse conversion missing");
+    assert_eq!(inverse.get_type(), ConversionType::Linear as u8);
+    assert_eq!(inverse.get_parameter_as_double(0), -5.0);
+    assert_eq!(inverse.get_parameter_as_double(1), 0.5);
+}
+
+#[test]
+fn test_channel_groups_by_size() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+
+    {
+        let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, file_path)
+            .expect("Failed to create MDF writer");
+
+        for name in ["Small", "Large"] {
+            let mut data_group = writer.create_data_group().unwrap();
+            let mut channel_group = data_group.create_channel_group().unwrap();
+            channel_group.set_name(name);
+            let mut channel = channel_group.create_channel().unwrap();
+            channel.set_name(&format!("{name}Counter"));
+            channel.set_data_type(mdflib_sys::ChannelDataType::UnsignedIntegerLe as u8);
+            channel.set_data_bytes(4);
+        }
+
+        writer.init_measurement();
+        writer.start_measurement(1000);
+        for (dg_index, count) in [(0, 5), (1, 20)] {
+            let data_group = writer.get_file().unwrap().get_data_group(dg_index).unwrap();
+            let channel_group = data_group.get_channel_group_by_index(0).unwrap();
+            for i in 0..count {
+                writer.save_sample(&channel_group, 1000 + i * 1000);
+            }
+        }
+        writer.stop_measurement(21000);
+        writer.finalize_measurement();
+    }
+
+    let mut reader = MdfReader::new(file_path).expect("Failed to create MDF reader");
+    reader.read_everything_but_data().unwrap();
+
+    let groups = reader.get_file().unwrap().channel_groups_by_size();
+    assert_eq!(groups.len(), 2);
+    assert_eq!(groups[0].1.get_name(), "Large");
+    assert_eq!(groups[0].2, 20);
+    assert_eq!(groups[1].1.get_name(), "Small");
+    assert_eq!(groups[1].2, 5);
+}
+
+#[test]
+fn test_try_save_sample_after_finalize() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, temp_file.path())
+        .expect("Failed to create MDF writer");
+
+    let mut data_group = writer.create_data_group().unwrap();
+    let mut channel_group = data_group.create_channel_group().unwrap();
+    let mut channel = channel_group.create_channel().unwrap();
+    channel.set_name("Counter");
+    channel.set_data_type(mdflib_sys::ChannelDataType::UnsignedIntegerLe as u8);
+    channel.set_data_bytes(4);
+    let group = data_group.get_channel_group_by_index(0).unwrap();
+
+    assert!(matches!(
+        writer.try_save_sample(&group, 1000),
+        Err(MdfError::InvalidWriterState(_))
+    ));
+
+    assert!(writer.init_measurement());
+    writer.start_measurement(1000);
+    channel.set_channel_value(1, true);
+    writer.try_save_sample(&group, 2000).unwrap();
+    writer.stop_measurement(3000);
+    assert!(writer.finalize_measurement());
+
+    assert!(matches!(
+        writer.try_save_sample(&group, 4000),
+        Err(MdfError::InvalidWriterState(_))
+    ));
+}
+
+#[test]
+fn test_channel_precision() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+
+    {
+        let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, file_path)
+            .expect("Failed to create MDF writer");
+        let mut data_group = writer.create_data_group().unwrap();
+        let mut channel_group = data_group.create_channel_group().unwrap();
+        let mut channel = channel_group.create_channel().unwrap();
+        channel.set_name("Temperature");
+        channel.set_data_type(mdflib_sys::ChannelDataType::FloatLe as u8);
+        channel.set_data_bytes(8);
+        assert!(!channel.is_precision_used());
+        channel.set_precision(3);
+
+        writer.init_measurement();
+        writer.start_measurement(1000);
+        writer.stop_measurement(2000);
+        writer.finalize_measurement();
+    }
+
+    let mut reader = MdfReader::new(file_path).expect("Failed to create MDF reader");
+    reader.read_everything_but_data().unwrap();
+    let data_group = reader.get_data_group(0).unwrap();
+    let channel_group = data_group.get_channel_group_by_index(0).unwrap();
+    let channel = channel_group.get_channel("Temperature").unwrap();
+    assert!(channel.is_precision_used());
+    assert_eq!(channel.get_precision(), 3);
+}
+
+#[test]
+fn test_channel_array_axis_values() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+    let axis = [0.0, 10.0, 20.0, 30.0];
+
+    {
+        let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, file_path)
+            .expect("Failed to create MDF writer");
+        let mut data_group = writer.create_data_group().unwrap();
+        let mut channel_group = data_group.create_channel_group().unwrap();
+        let mut channel = channel_group.create_channel().unwrap();
+        channel.set_name("Map");
+        channel.set_data_type(mdflib_sys::ChannelDataType::FloatLe as u8);
+        channel.set_data_bytes(8);
+        let mut array = channel.create_channel_array().unwrap();
+        array.set_type(ArrayType::LookUp as u8);
+        array.set_shape(&[axis.len() as u64]);
+        array.set_axis_values(0, &axis).unwrap();
+        assert_eq!(array.get_axis_values(0), axis);
+        assert!(array.get_axis_values(1).is_empty());
+
+        // The axis values must match the dimension, which isn't resized
+        assert!(matches!(
+            array.set_axis_values(0, &axis[..3]),
+            Err(MdfError::InvalidLayout(_))
+        ));
+        assert!(matches!(
+            array.set_axis_values(1, &axis),
+            Err(MdfError::IndexOutOfBounds(1))
+        ));
+        assert_eq!(array.get_shape(), [4]);
+        assert_eq!(array.get_axis_values(0), axis);
+
+        writer.init_measurement();
+        writer.start_measurement(1000);
+        writer.stop_measurement(2000);
+        writer.finalize_measurement();
+    }
+
+    let mut reader = MdfReader::new(file_path).expect("Failed to create MDF reader");
+    reader.read_everything_but_data().unwrap();
+    let data_group = reader.get_data_group(0).unwrap();
+    let channel_group = data_group.get_channel_group_by_index(0).unwrap();
+    let channel = channel_group.get_channel("Map").unwrap();
+    let array = channel.get_channel_array().unwrap();
+    assert_eq!(array.get_axis_values(0), axis);
+}
+
+#[test]
+fn test_measurement_guard_finalizes_on_drop() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+
+    {
+        let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, file_path)
+            .expect("Failed to create MDF writer");
+        let mut data_group = writer.create_data_group().unwrap();
+        let mut channel_group = data_group.create_channel_group().unwrap();
+        let mut channel = channel_group.create_channel().unwrap();
+        channel.set_name("Counter");
+        channel.set_data_type(mdflib_sys::ChannelDataType::UnsignedIntegerLe as u8);
+        channel.set_data_bytes(4);
+        let group = data_group.get_channel_group_by_index(0).unwrap();
+
+        let mut guard = writer.measurement_guard(1000).unwrap();
+        for i in 0..5u32 {
+            channel.set_channel_value(i, true);
+            guard.save_sample(&group, 1000 + i as u64 * 1000);
+        }
+        // Dropped without commit
+    }
+
+    let mut reader = MdfReader::new(file_path).expect("Failed to create MDF reader");
+    reader.read_everything_but_data().unwrap();
+    assert!(reader.get_file().unwrap().is_finalized_done());
+    let data_group = reader.get_data_group(0).unwrap();
+    let channel_group = data_group.get_channel_group_by_index(0).unwrap();
+    assert_eq!(channel_group.get_nof_samples(), 5);
+}
+
+#[test]
+fn test_measurement_guard_states() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+
+    {
+        let started_file = NamedTempFile::new().unwrap();
+        let mut writer =
+            writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, started_file.path())
+                .expect("Failed to create MDF writer");
+        writer.create_data_group().unwrap();
+
+        assert!(writer.init_measurement());
+        writer.start_measurement(1000);
+        // A running measurement isn't restarted
+        assert!(matches!(
+            writer.measurement_guard(2000),
+            Err(MdfError::InvalidWriterState(_))
+        ));
+        writer.stop_measurement(1000);
+        assert!(matches!(
+            writer.measurement_guard(2000),
+            Err(MdfError::InvalidWriterState(_))
+        ));
+    }
+
+    {
+        let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, file_path)
+            .expect("Failed to create MDF writer");
+        let mut data_group = writer.create_data_group().unwrap();
+        let mut channel_group = data_group.create_channel_group().unwrap();
+        let mut channel = channel_group.create_channel().unwrap();
+        channel.set_name("Counter");
+        channel.set_data_type(mdflib_sys::ChannelDataType::UnsignedIntegerLe as u8);
+        channel.set_data_bytes(4);
+        let group = data_group.get_channel_group_by_index(0).unwrap();
+
+        let mut guard = writer.measurement_guard(1000).unwrap();
+        for i in 0..3u32 {
+            channel.set_channel_value(i, true);
+            guard.save_sample(&group, 1000 + i as u64 * 1000);
+        }
+        // Finalized through the writer, so dropping the guard does nothing
+        guard.stop_measurement(10_000);
+        assert!(guard.finalize_measurement());
+        drop(guard);
+
+        assert!(matches!(
+            writer.measurement_guard(20_000),
+            Err(MdfError::InvalidWriterState(_))
+        ));
+    }
+
+    let mut reader = MdfReader::new(file_path).expect("Failed to create MDF reader");
+    reader.read_everything_but_data().unwrap();
+    let file = reader.get_file().unwrap();
+    assert!(file.is_finalized_done());
+    let data_group = reader.get_data_group(0).unwrap();
+    let channel_group = data_group.get_channel_group_by_index(0).unwrap();
+    assert_eq!(channel_group.get_nof_samples(), 3);
+}
+
+#[test]
+fn test_block_file_positions() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+
+    {
+        let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, file_path)
+            .expect("Failed to create MDF writer");
+        let mut data_group = writer.create_data_group().unwrap();
+        let mut channel_group = data_group.create_channel_group().unwrap();
+        for name in ["First", "Second"] {
+            let mut channel = channel_group.create_channel().unwrap();
+            channel.set_name(name);
+            channel.set_data_type(mdflib_sys::ChannelDataType::UnsignedIntegerLe as u8);
+            channel.set_data_bytes(4);
+        }
+
+        writer.init_measurement();
+        writer.start_measurement(1000);
+        writer.save_sample(&channel_group, 1000);
+        writer.stop_measurement(2000);
+        writer.finalize_measurement();
+    }
+
+    let bytes = std::fs::read(file_path).unwrap();
+    let mut reader = MdfReader::new(file_path).expect("Failed to create MDF reader");
+    reader.read_everything_but_data().unwrap();
+    let data_group = reader.get_data_group(0).unwrap();
+    let channel_group = data_group.get_channel_group_by_index(0).unwrap();
+    let first = channel_group.get_channel("First").unwrap();
+    let second = channel_group.get_channel("Second").unwrap();
+
+    let positions = [
+        (channel_group.get_file_position(), b"##CG"),
+        (first.get_file_position(), b"##CN"),
+        (second.get_file_position(), b"##CN"),
+    ];
+    for (position, id) in positions {
+        assert!(position > 0);
+        let position = position as usize;
+        assert_eq!(&bytes[position..position + 4], id);
+    }
+    assert_ne!(first.get_file_position(), second.get_file_position());
+}
+
+#[test]
+fn test_save_record() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+
+    {
+        let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, file_path)
+            .expect("Failed to create MDF writer");
+        let mut data_group = writer.create_data_group().unwrap();
+        let mut channel_group = data_group.create_channel_group().unwrap();
+        let mut speed = channel_group.create_channel().unwrap();
+        speed.set_name("Speed");
+        speed.set_data_type(mdflib_sys::ChannelDataType::FloatLe as u8);
+        speed.set_data_bytes(8);
+        let mut gear = channel_group.create_channel().unwrap();
+        gear.set_name("Gear");
+        gear.set_data_type(mdflib_sys::ChannelDataType::SignedIntegerLe as u8);
+        gear.set_data_bytes(4);
+
+        let group = data_group.get_channel_group_by_index(0).unwrap();
+        let mut channels = channel_group.get_channels_mut();
+
+        writer.init_measurement();
+        writer.start_measurement(1000);
+        for i in 0..3 {
+            let [speed, gear] = &mut channels[..] else {
+                panic!("Expected two channels");
+            };
+            writer
+                .save_record(
+                    &group,
+                    1000 + i as u64 * 1000,
+                    &mut [
+                        (speed, SampleValue::F64(12.5 * i as f64)),
+                        (gear, SampleValue::I64(i - 1)),
+                    ],
+                )
+                .unwrap();
+        }
+        writer.stop_measurement(4000);
+        writer.finalize_measurement();
+    }
+
+    let mut reader = MdfReader::new(file_path).expect("Failed to create MDF reader");
+    reader.read_everything_but_data().unwrap();
+
+    let file = reader.get_file().unwrap();
+    let mut dg = file.get_data_group(0).unwrap();
+    let cg = dg.get_channel_group_by_index(0).unwrap();
+    assert_eq!(cg.get_nof_samples(), 3);
+
+    let speed = cg.get_channel("Speed").unwrap();
+    let gear = cg.get_channel("Gear").unwrap();
+    let speed_observer =
+        unsafe { create_channel_observer(dg.as_ptr(), cg.as_ptr(), speed.as_ptr()) }.unwrap();
+    let gear_observer =
+        unsafe { create_channel_observer(dg.as_ptr(), cg.as_ptr(), gear.as_ptr()) }.unwrap();
+    reader.read_data(&mut dg).unwrap();
+
+    assert_eq!(
+        speed_observer.get_all_eng_values(),
+        [Some(0.0), Some(12.5), Some(25.0)]
+    );
+    assert_eq!(
+        gear_observer.get_all_eng_values(),
+        [Some(-1.0), Some(0.0), Some(1.0)]
+    );
+}
+
+#[test]
+fn test_sample_value_variants() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+    let channels = [
+        ("Unsigned", ChannelDataType::UnsignedIntegerLe, 8),
+        ("Signed", ChannelDataType::SignedIntegerLe, 8),
+        ("Float", ChannelDataType::FloatLe, 8),
+        ("Text", ChannelDataType::StringAscii, 16),
+        ("Raw", ChannelDataType::ByteArray, 4),
+    ];
+
+    {
+        let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, file_path)
+            .expect("Failed to create MDF writer");
+        let mut data_group = writer.create_data_group().unwrap();
+        let mut channel_group = data_group.create_channel_group().unwrap();
+        for (name, data_type, bytes) in channels {
+            let mut channel = channel_group.create_channel().unwrap();
+            channel.set_name(name);
+            channel.set_data_type(data_type as u8);
+            channel.set_data_bytes(bytes);
+        }
+        let group = data_group.get_channel_group_by_index(0).unwrap();
+        let mut created = channel_group.get_channels_mut();
+
+        let values = [
+            SampleValue::from(u64::MAX),
+            SampleValue::from(i64::MIN),
+            SampleValue::from(2.5),
+            SampleValue::from("status"),
+            SampleValue::from(vec![1, 2, 3, 4]),
+        ];
+        writer.init_measurement();
+        writer.start_measurement(1000);
+        let mut record: Vec<_> = created.iter_mut().zip(values).collect();
+        writer.save_record(&group, 1000, &mut record).unwrap();
+        let mut invalid: Vec<_> = created
+            .iter_mut()
+            .map(|channel| (channel, SampleValue::Invalid))
+            .collect();
+        writer.save_record(&group, 2000, &mut invalid).unwrap();
+        writer.stop_measurement(3000);
+        writer.finalize_measurement();
+    }
+
+    let mut reader = MdfReader::new(file_path).expect("Failed to create MDF reader");
+    reader.read_everything_but_data().unwrap();
+
+    let file = reader.get_file().unwrap();
+    let mut dg = file.get_data_group(0).unwrap();
+    let cg = dg.get_channel_group_by_index(0).unwrap();
+    let observers: Vec<_> = channels
+        .iter()
+        .map(|(name, _, _)| {
+            let channel = cg.get_channel(name).unwrap();
+            unsafe { create_channel_observer(dg.as_ptr(), cg.as_ptr(), channel.as_ptr()) }.unwrap()
+        })
+        .collect();
+    reader.read_data(&mut dg).unwrap();
+
+    assert_eq!(
+        observers[0].read_typed(),
+        TypedSamples::U64(vec![u64::MAX, 0])
+    );
+    assert_eq!(
+        observers[1].read_typed(),
+        TypedSamples::I64(vec![i64::MIN, 0])
+    );
+    assert_eq!(observers[2].get_eng_value(0), Some(2.5));
+    assert_eq!(
+        observers[3].get_value_as_string(0).as_deref(),
+        Some("status")
+    );
+    match observers[4].read_typed() {
+        TypedSamples::Bytes(values) => assert_eq!(values[0], [1, 2, 3, 4]),
+        other => panic!("Unexpected samples {other:?}"),
+    }
+    for observer in &observers {
+        assert!(observer.is_valid(0));
+        assert!(!observer.is_valid(1));
+    }
+}
+
+#[test]
+fn test_channel_identity_conversion() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+
+    let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, file_path)
+        .expect("Failed to create MDF writer");
+    let mut data_group = writer.create_data_group().unwrap();
+    let mut channel_group = data_group.create_channel_group().unwrap();
+
+    let mut plain = channel_group.create_channel().unwrap();
+    plain.set_name("Plain");
+    assert!(plain.get_channel_conversion().is_none());
+    assert!(plain.effective_conversion().is_none());
+    assert_eq!(plain.apply_conversion(3.5), 3.5);
+
+    let mut identity = channel_group.create_channel().unwrap();
+    identity.set_name("Identity");
+    let mut conversion = identity.create_channel_conversion().unwrap();
+    conversion.set_type(ConversionType::NoConversion as u8);
+    assert!(identity.get_channel_conversion().is_some());
+    assert!(identity.effective_conversion().is_none());
+    assert_eq!(identity.apply_conversion(-7.0), -7.0);
+
+    let mut scaled = channel_group.create_channel().unwrap();
+    scaled.set_name("Scaled");
+    let mut conversion = scaled.create_channel_conversion().unwrap();
+    conversion.set_type(ConversionType::Linear as u8);
+    conversion.set_parameter_as_double(0, 10.0);
+    conversion.set_parameter_as_double(1, 2.0);
+    assert!(scaled.effective_conversion().is_some());
+    assert_eq!(scaled.apply_conversion(3.0), 16.0);
+}
+
+#[test]
+fn test_conversion_invert() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+
+    let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, file_path)
+        .expect("Failed to create MDF writer");
+    let mut data_group = writer.create_data_group().unwrap();
+    let mut channel_group = data_group.create_channel_group().unwrap();
+
+    let mut scaled = channel_group.create_channel().unwrap();
+    let mut linear = scaled.create_channel_conversion().unwrap();
+    linear.set_type(ConversionType::Linear as u8);
+    linear.set_parameter_as_double(0, -40.0);
+    linear.set_parameter_as_double(1, 0.5);
+    for raw in [0.0, 1.0, 81.0, 255.0, -12.5] {
+        let eng = linear.convert(raw).unwrap();
+        assert!((linear.invert(eng).unwrap() - raw).abs() < 1e-9);
+    }
+
+    let mut ratio = channel_group.create_channel().unwrap();
+    let mut rational = ratio.create_channel_conversion().unwrap();
+    rational.set_type(ConversionType::Rational as u8);
+    for (index, value) in [0.0, 2.0, 1.0, 0.0, 0.0, 4.0].into_iter().enumerate() {
+        rational.set_parameter_as_double(index as u16, value);
+    }
+    let eng = rational.convert(6.0).unwrap();
+    assert!((rational.invert(eng).unwrap() - 6.0).abs() < 1e-9);
+
+    rational.set_parameter_as_double(0, 1.0);
+    assert!(rational.invert(eng).is_none());
+}
+
+/// Test merging two files appends their groups on a common time base
+#[test]
+fn test_merge_files() {
+    let first = NamedTempFile::new().unwrap();
+    let second = NamedTempFile::new().unwrap();
+    let merged = NamedTempFile::new().unwrap();
+
+    let write_run = |path: &std::path::Path, name: &str, start: u64, count: u64| {
+        let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, path)
+            .expect("Failed to create MDF writer");
+        let mut data_group = writer.create_data_group().unwrap();
+        let mut channel_group = data_group.create_channel_group().unwrap();
+        channel_group.set_name(name);
+
+        let mut master = channel_group.create_channel().unwrap();
+        master.set_name("Time");
+        master.set_type(ChannelType::Master as u8);
+        master.set_sync(ChannelSyncType::Time as u8);
+        master.set_data_type(ChannelDataType::FloatLe as u8);
+        master.set_data_bytes(8);
+
+        let mut channel = channel_group.create_channel().unwrap();
+        channel.set_name("Counter");
+        channel.set_data_type(ChannelDataType::UnsignedIntegerLe as u8);
+        channel.set_data_bytes(4);
+
+        writer.init_measurement();
+        writer.start_measurement(start);
+        let group = data_group.get_channel_group_by_index(0).unwrap();
+        for i in 0..count {
+            channel.set_channel_value_u64(i, true);
+            writer.save_sample(&group, start + i * 100_000_000);
+        }
+        writer.stop_measurement(start + count * 100_000_000);
+        writer.finalize_measurement();
+    };
+    write_run(first.path(), "RunA", 1_000_000_000, 5);
+    write_run(second.path(), "RunB", 3_000_000_000, 3);
+
+    merge_files(&[first.path(), second.path()], merged.path()).expect("Failed to merge");
+
+    let mut reader = MdfReader::new(merged.path()).expect("Failed to create MDF reader");
+    reader.read_everything_but_data().unwrap();
+    let file = reader.get_file().unwrap();
+    assert_eq!(file.get_data_group_count(), 2);
+    assert_eq!(file.get_header().get_start_time(), 1_000_000_000);
+
+    let mut second_dg = file.get_data_group(1).unwrap();
+    let first_cg = file
+        .get_data_group(0)
+        .unwrap()
+        .get_channel_group("RunA")
+        .unwrap();
+    let second_cg = second_dg.get_channel_group("RunB").unwrap();
+    assert_eq!(first_cg.get_nof_samples(), 5);
+    assert_eq!(second_cg.get_nof_samples(), 3);
+
+    let time = second_cg.get_channel("Time").unwrap();
+    let observer =
+        unsafe { create_channel_observer(second_dg.as_ptr(), second_cg.as_ptr(), time.as_ptr()) }
+            .unwrap();
+    reader.read_data(&mut second_dg).unwrap();
+    assert_eq!(
+        observer.get_all_eng_values(),
+        [Some(2.0), Some(2.1), Some(2.2)]
+    );
+}
+
+#[test]
+fn test_copy_channel_group() {
+    let src_file = NamedTempFile::new().unwrap();
+    let dst_file = NamedTempFile::new().unwrap();
+    let channels = [("Speed", "km/h"), ("Temp", "degC"), ("Pressure", "bar")];
+
+    {
+        let mut writer =
+            writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, src_file.path())
+                .expect("Failed to create MDF writer");
+        let mut data_group = writer.create_data_group().unwrap();
+        let mut channel_group = data_group.create_channel_group().unwrap();
+        channel_group.set_name("Template");
+        channel_group
+            .create_source_information()
+            .unwrap()
+            .set_name("ECU")
+            .unwrap();
+        for (name, unit) in channels {
+            let mut channel = channel_group.create_channel().unwrap();
+            channel.set_name(name);
+            channel.set_unit(unit);
+            channel.set_data_type(ChannelDataType::FloatLe as u8);
+            channel.set_data_bytes(8);
+            if name == "Temp" {
+                let mut conversion = channel.create_channel_conversion().unwrap();
+                conversion.set_type(ConversionType::Linear as u8);
+                conversion.set_parameter_as_double(0, -40.0);
+                conversion.set_parameter_as_double(1, 0.5);
+            }
+        }
+
+        writer.init_measurement();
+        writer.start_measurement(1000);
+        writer.stop_measurement(2000);
+        writer.finalize_measurement();
+    }
+
+    let mut reader = MdfReader::new(src_file.path()).expect("Failed to create MDF reader");
+    reader.read_everything_but_data().unwrap();
+    let src_dg = reader.get_data_group(0).unwrap();
+    let src_cg = src_dg.get_channel_group("Template").unwrap();
+
+    let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, dst_file.path())
+        .expect("Failed to create MDF writer");
+    let mut data_group = writer.create_data_group().unwrap();
+    let copy = data_group.copy_channel_group(&src_cg).unwrap();
+
+    assert_eq!(copy.get_name(), "Template");
+    assert_eq!(copy.get_nof_samples(), 0);
+    assert_eq!(copy.get_source_information().unwrap().get_name(), "ECU");
+    let copied: Vec<_> = copy
+        .get_channels()
+        .iter()
+        .map(|channel| (channel.get_name(), channel.get_unit()))
+        .collect();
+    let expected: Vec<_> = channels
+        .iter()
+        .map(|(name, unit)| (name.to_string(), unit.to_string()))
+        .collect();
+    assert_eq!(copied, expected);
+    let temp = copy.get_channel("Temp").unwrap();
+    assert_eq!(temp.apply_conversion(100.0), 10.0);
+}
+
+/// Test the MD5 checksum of an embedded attachment against its data
+#[cfg(feature = "md5")]
+#[test]
+fn test_attachment_check_md5() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+
+    let mut notes = NamedTempFile::new().unwrap();
+    std::io::Write::write_all(&mut notes, b"embedded test notes").unwrap();
+
+    {
+        let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, file_path)
+            .expect("Failed to create MDF writer");
+        let mut header = writer.get_header().unwrap();
+        let mut attachment = header.create_attachment().unwrap();
+        attachment
+            .set_filename(notes.path().to_str().unwrap())
+            .unwrap();
+        attachment.set_embedded(true);
+        attachment.set_file_type("text/plain").unwrap();
+        writer.init_measurement();
+        writer.start_measurement(1000);
+        writer.stop_measurement(2000);
+        writer.finalize_measurement();
+    }
+
+    let verify = |path: &std::path::Path| {
+        let mut reader = MdfReader::new(path).expect("Failed to create MDF reader");
+        reader.read_everything_but_data().unwrap();
+        let file = reader.get_file().unwrap();
+        let attachment = file.get_attachments()[0];
+        assert!(attachment.get_embedded());
+        attachment.check_md5(&mut reader)
+    };
+    assert!(verify(file_path).unwrap());
+
+    {
+        // Flip the first byte of the MD5 stored after the AT block links and flags
+        let mut bytes = std::fs::read(file_path).unwrap();
+        let at_offset = bytes
+            .windows(4)
+            .position(|w| w == b"##AT")
+            .expect("AT block not found");
+        bytes[at_offset + 64] ^= 0xFF;
+        std::fs::write(file_path, bytes).unwrap();
+    }
+    assert!(!verify(file_path).unwrap());
+}
+
+/// Test writing and reading a file with a non-ASCII path on Windows
+#[cfg(windows)]
+#[test]
+fn test_unicode_path() {
+    let dir = tempfile::tempdir().unwrap();
+    let file_path = dir.path().join("Messung_äöü_测试.mf4");
+
+    write_minimal_file(&file_path);
+
+    let mut reader = MdfReader::new(&file_path).expect("Failed to create MDF reader");
+    reader.read_everything_but_data().unwrap();
+    assert_eq!(reader.get_file().unwrap().get_data_group_count(), 1);
+}
+
+#[test]
+fn test_estimate_file_size() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+    let samples = 10_000u64;
+
+    let estimate = {
+        let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, file_path)
+            .expect("Failed to create MDF writer");
+        let mut data_group = writer.create_data_group().unwrap();
+        let mut channel_group = data_group.create_channel_group().unwrap();
+
+        let mut master = channel_group.create_channel().unwrap();
+        master.set_name("Time");
+        master.set_type(ChannelType::Master as u8);
+        master.set_sync(ChannelSyncType::Time as u8);
+        master.set_data_type(ChannelDataType::FloatLe as u8);
+        master.set_data_bytes(8);
+
+        let mut speed = channel_group.create_channel().unwrap();
+        speed.set_name("Speed");
+        speed.set_data_type(ChannelDataType::FloatLe as u8);
+        speed.set_data_bytes(8);
+
+        let mut counter = channel_group.create_channel().unwrap();
+        counter.set_name("Counter");
+        counter.set_data_type(ChannelDataType::UnsignedIntegerLe as u8);
+        counter.set_data_bytes(4);
+
+        let group = data_group.get_channel_group_by_index(0).unwrap();
+        assert_eq!(group.estimated_bytes_per_sample(), 8 + 8 + 4 + 1);
+        let estimate = estimate_file_size(&[(*group, samples)]);
+
+        writer.init_measurement();
+        writer.start_measurement(1000);
+        for i in 0..samples {
+            writer.save_sample(&group, 1000 + i * 1000);
+        }
+        writer.stop_measurement(1000 + samples * 1000);
+        writer.finalize_measurement();
+        estimate
+    };
+
+    let actual = std::fs::metadata(file_path).unwrap().len();
+    let error = estimate.abs_diff(actual) as f64 / actual as f64;
+    assert!(
+        error < 0.1,
+        "Estimate {estimate} too far from actual size {actual}"
+    );
+}
+
+#[test]
+fn test_total_channel_count() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+
+    {
+        let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, file_path)
+            .expect("Failed to create MDF writer");
+
+        for (dg_name, groups) in [("First", [1, 3]), ("Second", [2, 4])] {
+            let mut data_group = writer.create_data_group().unwrap();
+            for (cg_index, count) in groups.into_iter().enumerate() {
+                let mut channel_group = data_group.create_channel_group().unwrap();
+                for i in 0..count {
+                    let mut channel = channel_group.create_channel().unwrap();
+                    channel.set_name(&format!("{dg_name}{cg_index}_{i}"));
+                    channel.set_data_type(mdflib_sys::ChannelDataType::UnsignedIntegerLe as u8);
+                    channel.set_data_bytes(4);
+                }
+            }
+        }
+
+        writer.init_measurement();
+        writer.start_measurement(1000);
+        writer.stop_measurement(2000);
+        writer.finalize_measurement();
+    }
+
+    let mut reader = MdfReader::new(file_path).expect("Failed to create MDF reader");
+    reader.read_everything_but_data().unwrap();
+
+    let file = reader.get_file().unwrap();
+    let mut manual = 0;
+    for dg in file.get_data_groups() {
+        for cg in dg.get_channel_groups() {
+            manual += cg.get_channels().len();
+        }
+    }
+    assert_eq!(manual, 10);
+    assert_eq!(file.total_channel_count(), manual);
+}
+
+#[test]
+fn test_configure_time_master() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+
+    let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, file_path)
+        .expect("Failed to create MDF writer");
+    let mut data_group = writer.create_data_group().unwrap();
+    let mut channel_group = data_group.create_channel_group().unwrap();
+    let mut channel = channel_group.create_channel().unwrap();
+    channel.set_name("Speed");
+    channel.set_data_type(ChannelDataType::FloatLe as u8);
+    channel.set_data_bytes(8);
+
+    writer
+        .configure_time_master(&mut channel_group, "s")
+        .unwrap();
+    let master = channel_group
+        .get_channel("Time")
+        .expect("Master channel created");
+    assert_eq!(master.get_type(), ChannelType::Master as u8);
+    assert_eq!(master.get_sync(), ChannelSyncType::Time as u8);
+    assert_eq!(master.get_unit(), "s");
+
+    // A second call reuses the existing master
+    writer
+        .configure_time_master(&mut channel_group, "ms")
+        .unwrap();
+    assert_eq!(channel_group.get_channel_count(), 2);
+    assert_eq!(channel_group.get_channel("Time").unwrap().get_unit(), "ms");
+
+    writer.init_measurement();
+    assert!(matches!(
+        writer.configure_time_master(&mut channel_group, "s"),
+        Err(MdfError::InvalidWriterState(_))
+    ));
+}
+
+#[test]
+fn test_data_group_builder() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+
+    {
+        let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, file_path)
+            .expect("Failed to create MDF writer");
+        let data_group = DataGroupBuilder::new("Engine")
+            .description("Engine signals")
+            .channel(ChannelSpec::new("Speed", ChannelDataType::FloatLe).unit("rpm"))
+            .channel(
+                ChannelSpec::new("Temp", ChannelDataType::UnsignedIntegerLe)
+                    .data_bytes(1)
+                    .unit("degC")
+                    .linear(-40.0, 1.0),
+            )
+            .channel(ChannelSpec::new("Gear", ChannelDataType::SignedIntegerLe))
+            .build(&mut writer)
+            .unwrap();
+        let group = data_group.get_channel_group_by_index(0).unwrap();
+
+        writer.init_measurement();
+        writer.start_measurement(1000);
+        writer.save_sample(&group, 1000);
+        writer.stop_measurement(2000);
+        writer.finalize_measurement();
+    }
+
+    let mut reader = MdfReader::new(file_path).expect("Failed to create MDF reader");
+    reader.read_everything_but_data().unwrap();
+    let data_group = reader.get_data_group(0).unwrap();
+    assert_eq!(data_group.get_description(), "Engine signals");
+    let cg = data_group.get_channel_group("Engine").unwrap();
+
+    let names: Vec<String> = cg.get_channels().iter().map(|cn| cn.get_name()).collect();
+    assert_eq!(names, ["Time", "Speed", "Temp", "Gear"]);
+
+    let time = cg.get_channel("Time").unwrap();
+    assert_eq!(time.get_type(), ChannelType::Master as u8);
+    assert_eq!(time.get_unit(), "s");
+
+    let speed = cg.get_channel("Speed").unwrap();
+    assert_eq!(speed.get_unit(), "rpm");
+    assert_eq!(speed.get_data_bytes(), 8);
+
+    let temp = cg.get_channel("Temp").unwrap();
+    assert_eq!(temp.get_data_bytes(), 1);
+    assert_eq!(
+        temp.get_data_type(),
+        ChannelDataType::UnsignedIntegerLe as u8
+    );
+    assert_eq!(temp.apply_conversion(100.0), 60.0);
+
+    let gear = cg.get_channel("Gear").unwrap();
+    assert_eq!(gear.get_data_bytes(), 4);
+    assert!(gear.effective_conversion().is_none());
+}
+
+#[test]
+fn test_channel_hierarchy_descendants() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+
+    {
+        let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, file_path)
+            .expect("Failed to create MDF writer");
+
+        let mut data_group = writer.create_data_group().unwrap();
+        let mut channel_group = data_group.create_channel_group().unwrap();
+        for name in ["Speed", "Temp"] {
+            let mut channel = channel_group.create_channel().unwrap();
+            channel.set_name(name);
+            channel.set_data_type(mdflib_sys::ChannelDataType::FloatLe as u8);
+            channel.set_data_bytes(8);
+        }
+        let dg = *data_group;
+        let cg = *channel_group;
+        let speed = cg.get_channel("Speed").unwrap();
+        let temp = cg.get_channel("Temp").unwrap();
+
+        let mut header = writer.get_header().unwrap();
+        let mut root = header.create_channel_hierarchy().unwrap();
+        root.set_name("Root").unwrap();
+        root.set_type(ChType::Group);
+        {
+            let mut engine = root.create_child().unwrap();
+            engine.set_name("Engine").unwrap();
+            engine.set_type(ChType::Function);
+            engine.add_channel(&dg, &cg, &speed);
+            let mut sensors = engine.create_child().unwrap();
+            sensors.set_name("Sensors").unwrap();
+            sensors.set_type(ChType::Structure);
+            sensors.add_channel(&dg, &cg, &temp);
+        }
+        {
+            let mut body = root.create_child().unwrap();
+            body.set_name("Body").unwrap();
+        }
+
+        let names: Vec<String> = root.iter_descendants().map(|ch| ch.get_name()).collect();
+        assert_eq!(names, ["Engine", "Sensors", "Body"]);
+        let channels: Vec<String> = root
+            .iter_descendants()
+            .flat_map(|ch| ch.get_channels())
+            .map(|channel| channel.get_name())
+            .collect();
+        assert_eq!(channels, ["Speed", "Temp"]);
+
+        writer.init_measurement();
+        writer.start_measurement(1000);
+        writer.stop_measurement(2000);
+        writer.finalize_measurement();
+    }
+
+    let mut reader = MdfReader::new(file_path).expect("Failed to create MDF reader");
+    reader.read_everything_but_data().unwrap();
+
+    let file = reader.get_file().unwrap();
+    let header = file.get_header();
+    let hierarchies = header.get_channel_hierarchies();
+    assert_eq!(hierarchies.len(), 1);
+    let root = hierarchies[0];
+    assert_eq!(root.get_name(), "Root");
+    assert_eq!(root.get_ch_type().unwrap(), ChType::Group);
+    assert_eq!(root.get_children().len(), 2);
+    assert_eq!(root.iter_descendants().count(), 3);
+}
+
+#[test]
+fn test_records_read() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+
+    {
+        let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, file_path)
+            .expect("Failed to create MDF writer");
+
+        let mut groups = Vec::new();
+        for name in ["First", "Second"] {
+            let mut data_group = writer.create_data_group().unwrap();
+            let mut channel_group = data_group.create_channel_group().unwrap();
+            channel_group.set_name(name);
+            let mut channel = channel_group.create_channel().unwrap();
+            channel.set_name("Counter");
+            channel.set_data_type(mdflib_sys::ChannelDataType::UnsignedIntegerLe as u8);
+            channel.set_data_bytes(4);
+            groups.push(data_group.get_channel_group_by_index(0).unwrap());
+        }
+
+        writer.init_measurement();
+        writer.start_measurement(1000);
+        for i in 0..5 {
+            writer.save_sample(&groups[0], 1000 + i * 100);
+            if i < 3 {
+                writer.save_sample(&groups[1], 1000 + i * 100);
+            }
+        }
+        writer.stop_measurement(2000);
+        writer.finalize_measurement();
+    }
+
+    let mut reader = MdfReader::new(file_path).expect("Failed to create MDF reader");
+    reader.read_everything_but_data().unwrap();
+    assert_eq!(reader.records_read(), 0);
+
+    let file = reader.get_file().unwrap();
+    let mut first = file.get_data_group(0).unwrap();
+    reader.read_data(&mut first).unwrap();
+    assert_eq!(reader.records_read(), 5);
+
+    let mut second = file.get_data_group(1).unwrap();
+    reader.read_data(&mut second).unwrap();
+    assert_eq!(reader.records_read(), 8);
+}
+
+#[test]
+fn test_total_sample_count() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+
+    {
+        let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, file_path)
+            .expect("Failed to create MDF writer");
+        let fast = DataGroupBuilder::new("Fast")
+            .channel(ChannelSpec::new("Speed", ChannelDataType::FloatLe))
+            .build(&mut writer)
+            .unwrap()
+            .get_channel_group_by_index(0)
+            .unwrap();
+        let slow = DataGroupBuilder::new("Slow")
+            .channel(ChannelSpec::new("Temp", ChannelDataType::FloatLe))
+            .build(&mut writer)
+            .unwrap()
+            .get_channel_group_by_index(0)
+            .unwrap();
+
+        writer.init_measurement();
+        writer.start_measurement(1_000_000_000);
+        for i in 0..7 {
+            writer.save_sample(&fast, 1_000_000_000 + i * 100_000_000);
+            if i % 2 == 0 {
+                writer.save_sample(&slow, 1_000_000_000 + i * 100_000_000);
+            }
+        }
+        writer.stop_measurement(2_000_000_000);
+        writer.finalize_measurement();
+    }
+
+    let mut reader = MdfReader::new(file_path).expect("Failed to create MDF reader");
+    reader.read_everything_but_data().unwrap();
+    assert_eq!(reader.get_data_group_count(), 2);
+    assert_eq!(reader.total_sample_count(), 11);
+}
+
+#[test]
+fn test_header_only_file() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+
+    {
+        let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, file_path)
+            .expect("Failed to create MDF writer");
+        writer.get_header().unwrap().set_author("Empty");
+        writer.init_measurement();
+        writer.start_measurement(1000);
+        writer.stop_measurement(2000);
+        writer.finalize_measurement();
+    }
+
+    let mut reader = MdfReader::new(file_path).expect("Failed to create MDF reader");
+    reader.read_everything_but_data().unwrap();
+    assert_eq!(reader.get_data_group_count(), 0);
+    assert!(reader.get_data_group(0).is_none());
+
+    let file = reader.get_file().unwrap();
+    assert!(file.is_empty());
+    assert!(file.get_data_groups().is_empty());
+    assert!(file.channel_groups_by_size().is_empty());
+    assert_eq!(file.total_channel_count(), 0);
+    assert_eq!(file.get_measurement_byte_size(), 0);
+    assert_eq!(file.get_header().get_author(), "Empty");
+
+    assert!(reader.read_all_data().unwrap().is_empty());
+    assert_eq!(reader.records_read(), 0);
+    let document = reader.read_all().unwrap();
+    assert!(document.data_groups.is_empty());
+    assert_eq!(reader.verify().unwrap().data_group_count, 0);
+}
+
+#[test]
+fn test_schema_summary() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+
+    {
+        let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, file_path)
+            .expect("Failed to create MDF writer");
+        DataGroupBuilder::new("Engine")
+            .channel(ChannelSpec::new("Speed", ChannelDataType::FloatLe).unit("rpm"))
+            .channel(ChannelSpec::new("Gear", ChannelDataType::UnsignedIntegerLe).data_bytes(1))
+            .build(&mut writer)
+            .unwrap();
+        DataGroupBuilder::new("Info")
+            .without_master()
+            .channel(ChannelSpec::new("Vin", ChannelDataType::StringAscii).data_bytes(17))
+            .build(&mut writer)
+            .unwrap();
+
+        writer.init_measurement();
+        writer.start_measurement(1000);
+        writer.stop_measurement(2000);
+        writer.finalize_measurement();
+    }
+
+    let mut reader = MdfReader::new(file_path).expect("Failed to create MDF reader");
+    reader.read_everything_but_data().unwrap();
+    let file = reader.get_file().unwrap();
+
+    let summary = file.schema_summary();
+    assert_eq!(summary.len(), file.total_channel_count());
+    assert_eq!(
+        summary,
+        [
+            (
+                "0/Engine/Time".to_string(),
+                ChannelDataType::FloatLe,
+                "s".to_string()
+            ),
+            (
+                "0/Engine/Speed".to_string(),
+                ChannelDataType::FloatLe,
+                "rpm".to_string()
+            ),
+            (
+                "0/Engine/Gear".to_string(),
+                ChannelDataType::UnsignedIntegerLe,
+                String::new()
+            ),
+            (
+                "1/Info/Vin".to_string(),
+                ChannelDataType::StringAscii,
+                String::new()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_id_text() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+
+    {
+        let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, file_path)
+            .expect("Failed to create MDF writer");
+        writer
+            .get_file()
+            .unwrap()
+            .set_program_id("MdfTest")
+            .unwrap();
+        writer.init_measurement();
+        writer.start_measurement(1000);
+        writer.stop_measurement(2000);
+        writer.finalize_measurement();
+    }
+
+    let mut reader = MdfReader::new(file_path).expect("Failed to create MDF reader");
+    reader.read_everything_but_data().unwrap();
+    let file = reader.get_file().unwrap();
+    assert_eq!(file.get_program_id(), "MdfTest");
+
+    let id_text = file.get_id_text();
+    assert_eq!(id_text.len(), 24);
+    assert!(id_text.starts_with("MDF     4."));
+    assert!(id_text.ends_with("MdfTest "));
+    let bytes = std::fs::read(file_path).unwrap();
+    assert_eq!(id_text.as_bytes(), &bytes[..24]);
+}
+
+#[test]
+fn test_attach_description_file() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+
+    let mut dbc = tempfile::Builder::new().suffix(".dbc").tempfile().unwrap();
+    std::io::Write::write_all(&mut dbc, b"VERSION \"\"\n\nBU_: ECU\n").unwrap();
+
+    {
+        let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, file_path)
+            .expect("Failed to create MDF writer");
+        let attachment = writer
+            .attach_description_file(dbc.path(), AttachmentFileType::Dbc.as_str())
+            .unwrap();
+        assert!(attachment.get_embedded());
+        assert_eq!(attachment.get_file_type(), "application/x-dbc");
+
+        let missing = dbc.path().with_extension("missing");
+        assert!(matches!(
+            writer.attach_description_file(&missing, "text/plain"),
+            Err(MdfError::FileOpen(_))
+        ));
+
+        writer.init_measurement();
+        writer.start_measurement(1000);
+        writer.stop_measurement(2000);
+        writer.finalize_measurement();
+    }
+
+    let mut reader = MdfReader::new(file_path).expect("Failed to create MDF reader");
+    reader.read_everything_but_data().unwrap();
+    let file = reader.get_file().unwrap();
+    let attachments = file.get_attachments();
+    assert_eq!(attachments.len(), 1);
+    let attachment = attachments[0];
+    assert!(attachment.get_embedded());
+    assert_eq!(attachment.get_file_type(), "application/x-dbc");
+    assert!(attachment.get_filename().ends_with(".dbc"));
+    #[cfg(feature = "md5")]
+    assert!(attachment.check_md5(&mut reader).unwrap());
+}
+
+#[test]
+fn test_written_sample_count() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+    let start = 10_000_000_000;
+
+    {
+        let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, file_path)
+            .expect("Failed to create MDF writer");
+        writer.set_pre_trig_time(0.0);
+        let data_group = DataGroupBuilder::new("Buffered")
+            .channel(ChannelSpec::new(
+                "Counter",
+                ChannelDataType::UnsignedIntegerLe,
+            ))
+            .build(&mut writer)
+            .unwrap();
+        let group = data_group.get_channel_group_by_index(0).unwrap();
+
+        let mut attempted = 0;
+        writer.init_measurement();
+        // Saved before the start and outside the pre-trigger time so dropped
+        for i in 0..5 {
+            writer.save_sample(&group, start - 5_000_000_000 + i * 100_000_000);
+            attempted += 1;
+        }
+        writer.start_measurement(start);
+        for i in 0..3 {
+            writer.save_sample(&group, start + i * 100_000_000);
+            attempted += 1;
+        }
+        writer.stop_measurement(start + 1_000_000_000);
+        // Only counted once the file is complete
+        assert!(matches!(
+            writer.get_written_sample_count(&group),
+            Err(MdfError::InvalidWriterState(_))
+        ));
+        assert!(writer.finalize_measurement());
+
+        let written = writer.get_written_sample_count(&group).unwrap();
+        assert_eq!(attempted, 8);
+        assert_eq!(written, 3);
+        assert_ne!(attempted, written);
+    }
+
+    let mut reader = MdfReader::new(file_path).expect("Failed to create MDF reader");
+    reader.read_everything_but_data().unwrap();
+    let cg = reader
+        .get_data_group(0)
+        .unwrap()
+        .get_channel_group("Buffered")
+        .unwrap();
+    assert_eq!(cg.get_nof_samples(), 3);
+}
+
+#[test]
+fn test_channel_group_write_block() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+
+    {
+        let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, file_path)
+            .expect("Failed to create MDF writer");
+        let data_group = DataGroupBuilder::new("Block")
+            .channel(ChannelSpec::new("Counter", ChannelDataType::UnsignedIntegerLe).data_bytes(4))
+            .build(&mut writer)
+            .unwrap();
+        let mut channel_group = data_group.get_channel_group("Block").unwrap();
+        assert_eq!(channel_group.get_sample_buffer_size(), 0);
+
+        writer.init_measurement();
+        writer.start_measurement(1_000_000_000);
+
+        let time = channel_group.get_channel("Time").unwrap();
+        let counter = channel_group.get_channel("Counter").unwrap();
+        let (time_offset, counter_offset) = (
+            time.get_byte_offset() as usize,
+            counter.get_byte_offset() as usize,
+        );
+        let record_size = channel_group.get_sample_buffer_size();
+        assert!(record_size >= 12);
+
+        let mut data = vec![0u8; record_size * 5];
+        for (i, record) in data.chunks_exact_mut(record_size).enumerate() {
+            record[time_offset..time_offset + 8].copy_from_slice(&(i as f64 * 0.1).to_le_bytes());
+            record[counter_offset..counter_offset + 4]
+                .copy_from_slice(&(i as u32 * 10).to_le_bytes());
+        }
+
+        assert!(matches!(
+            channel_group.write_block(&mut writer, &data[..record_size * 2], 3),
+            Err(MdfError::BufferTooSmall { .. })
+        ));
+        assert!(matches!(
+            channel_group.write_block(&mut writer, &data, u64::MAX),
+            Err(MdfError::InvalidLayout(_))
+        ));
+        let mut negative = data.clone();
+        negative[record_size + time_offset..record_size + time_offset + 8]
+            .copy_from_slice(&(-0.1f64).to_le_bytes());
+        assert!(matches!(
+            channel_group.write_block(&mut writer, &negative, 5),
+            Err(MdfError::InvalidFormat)
+        ));
+        channel_group.write_block(&mut writer, &data, 5).unwrap();
+
+        writer.stop_measurement(2_000_000_000);
+        assert!(writer.finalize_measurement());
+    }
+
+    let mut reader = MdfReader::new(file_path).expect("Failed to create MDF reader");
+    reader.read_everything_but_data().unwrap();
+
+    let file = reader.get_file().unwrap();
+    let mut dg = file.get_data_group(0).unwrap();
+    let cg = dg.get_channel_group("Block").unwrap();
+    assert_eq!(cg.get_nof_samples(), 5);
+    let channel = cg.get_channel("Counter").unwrap();
+    let observer =
+        unsafe { create_timed_channel_observer(dg.as_ptr(), cg.as_ptr(), channel.as_ptr()) }
+            .expect("Should be able to create channel observer");
+    reader.read_data(&mut dg).unwrap();
+
+    let pairs = observer.time_value_pairs().expect("Master not observed");
+    assert_eq!(pairs.len(), 5);
+    for (i, (time, value)) in pairs.iter().enumerate() {
+        assert!((time - i as f64 * 0.1).abs() < 1e-6);
+        assert_eq!(*value, i as f64 * 10.0);
+    }
+}
+
+#[test]
+fn test_channel_group_write_block_without_master() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, temp_file.path())
+        .expect("Failed to create MDF writer");
+    let data_group = DataGroupBuilder::new("Untimed")
+        .channel(ChannelSpec::new("Counter", ChannelDataType::UnsignedIntegerLe).data_bytes(4))
+        .without_master()
+        .build(&mut writer)
+        .unwrap();
+    let mut channel_group = data_group.get_channel_group("Untimed").unwrap();
+
+    writer.init_measurement();
+    writer.start_measurement(1_000_000_000);
+    let data = vec![0u8; channel_group.get_sample_buffer_size() * 2];
+    assert!(matches!(
+        channel_group.write_block(&mut writer, &data, 2),
+        Err(MdfError::InvalidLayout(_))
+    ));
+}
+
+#[test]
+fn test_channel_byte_order() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+
+    let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, file_path)
+        .expect("Failed to create MDF writer");
+    let mut data_group = writer.create_data_group().unwrap();
+    let mut channel_group = data_group.create_channel_group().unwrap();
+
+    for (data_type, byte_order) in [
+        (ChannelDataType::UnsignedIntegerLe, ByteOrder::LittleEndian),
+        (ChannelDataType::SignedIntegerBe, ByteOrder::BigEndian),
+        (ChannelDataType::FloatLe, ByteOrder::LittleEndian),
+        (ChannelDataType::FloatBe, ByteOrder::BigEndian),
+        (ChannelDataType::StringUTF16Be, ByteOrder::BigEndian),
+        (ChannelDataType::StringAscii, ByteOrder::NotApplicable),
+        (ChannelDataType::ByteArray, ByteOrder::NotApplicable),
+    ] {
+        let mut channel = channel_group.create_channel().unwrap();
+        channel.set_data_type(data_type as u8);
+        assert_eq!(channel.byte_order(), byte_order, "{data_type:?}");
+        assert_eq!(data_type_byte_order(data_type), byte_order);
+    }
+}
+
+#[test]
+fn test_add_event_at() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+    let start = 1_700_000_000_000_000_000;
+
+    {
+        let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, file_path)
+            .expect("Failed to create MDF writer");
+        let data_group = DataGroupBuilder::new("Signals")
+            .channel(ChannelSpec::new("Value", ChannelDataType::FloatLe))
+            .build(&mut writer)
+            .unwrap();
+        let group = data_group.get_channel_group_by_index(0).unwrap();
+
+        assert!(matches!(
+            writer.add_event_at(start, "Early", EventType::Trigger),
+            Err(MdfError::InvalidWriterState(_))
+        ));
+
+        writer.init_measurement();
+        writer.start_measurement(start);
+        for sample in 0..4u64 {
+            let time = start + sample * 500_000_000;
+            writer.save_sample(&group, time);
+            if sample == 2 {
+                let event = writer
+                    .add_event_at(time, "Overshoot", EventType::Trigger)
+                    .unwrap();
+                assert_eq!(event.get_sync_value(), 1_000_000_000);
+            }
+        }
+        writer.stop_measurement(start + 2_000_000_000);
+        writer.finalize_measurement();
+    }
+
+    let mut reader = MdfReader::new(file_path).expect("Failed to create MDF reader");
+    reader.read_everything_but_data().unwrap();
+    let header = reader.get_file().unwrap().get_header();
+    let events = header.events_in_range(start + 999_000_000, start + 1_001_000_000);
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].get_name(), "Overshoot");
+    assert_eq!(events[0].get_event_type().unwrap(), EventType::Trigger);
+    assert_eq!(events[0].get_sync_type().unwrap(), SyncType::SyncTime);
+}
+
+#[test]
+fn test_channel_group_finalize_layout() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, temp_file.path())
+        .expect("Failed to create MDF writer");
+    let mut data_group = writer.create_data_group().unwrap();
+    let mut channel_group = data_group.create_channel_group().unwrap();
+
+    {
+        let mut master = channel_group.create_channel().unwrap();
+        master.set_name("Time");
+        master.set_type(ChannelType::Master as u8);
+        master.set_data_type(ChannelDataType::FloatLe as u8);
+        master.set_data_bytes(8);
+    }
+    {
+        // 12 bits starting at bit 10 of its bytes need 2 bytes after skipping one
+        let mut packed = channel_group.create_channel().unwrap();
+        packed.set_name("Packed");
+        packed.set_data_type(ChannelDataType::UnsignedIntegerLe as u8);
+        packed.set_bit_offset(10);
+        packed.set_bit_count(12);
+    }
+    {
+        let mut counter = channel_group.create_channel().unwrap();
+        counter.set_name("Counter");
+        counter.set_data_type(ChannelDataType::UnsignedIntegerLe as u8);
+        counter.set_data_bytes(4);
+        // Overwritten by the layout
+        counter.set_byte_offset(100);
+    }
+
+    assert_eq!(channel_group.finalize_layout().unwrap(), 8 + 3 + 4);
+    let master = channel_group.get_channel("Time").unwrap();
+    assert_eq!(master.get_byte_offset(), 0);
+    let packed = channel_group.get_channel("Packed").unwrap();
+    assert_eq!(packed.get_byte_offset(), 9);
+    assert_eq!(packed.get_bit_offset(), 2);
+    assert_eq!(packed.get_data_bytes(), 2);
+    assert_eq!(packed.get_bit_count(), 12);
+    let counter = channel_group.get_channel("Counter").unwrap();
+    assert_eq!(counter.get_byte_offset(), 11);
+    assert_eq!(counter.get_bit_count(), 32);
+
+    {
+        let mut inconsistent = channel_group.create_channel().unwrap();
+        inconsistent.set_name("Inconsistent");
+        inconsistent.set_data_type(ChannelDataType::UnsignedIntegerLe as u8);
+        inconsistent.set_data_bytes(1);
+        inconsistent.set_bit_offset(4);
+        inconsistent.set_bit_count(8);
+    }
+    assert!(matches!(
+        channel_group.finalize_layout(),
+        Err(MdfError::InvalidLayout(_))
+    ));
+}
+
+#[test]
+fn test_find_all_channels() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let file_path = temp_file.path();
+
+    {
+        let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, file_path)
+            .expect("Failed to create MDF writer");
+        for bus in ["Can1", "Can2"] {
+            DataGroupBuilder::new(bus)
+                .channel(ChannelSpec::new("WheelSpeed", ChannelDataType::FloatLe).unit("km/h"))
+                .channel(ChannelSpec::new(
+                    &format!("{bus}Load"),
+                    ChannelDataType::FloatLe,
+                ))
+                .build(&mut writer)
+                .unwrap();
+        }
+
+        writer.init_measurement();
+        writer.start_measurement(1000);
+        writer.stop_measurement(2000);
+        writer.finalize_measurement();
+    }
+
+    let mut reader = MdfReader::new(file_path).expect("Failed to create MDF reader");
+    reader.read_everything_but_data().unwrap();
+    let file = reader.get_file().unwrap();
+
+    let matches = file.find_all_channels("WheelSpeed");
+    assert_eq!(matches.len(), 2);
+    let groups: Vec<String> = matches.iter().map(|(_, cg, _)| cg.get_name()).collect();
+    assert_eq!(groups, ["Can1", "Can2"]);
+    assert_ne!(matches[0].0.as_ptr(), matches[1].0.as_ptr());
+    assert!(matches.iter().all(|(_, _, cn)| cn.get_unit() == "km/h"));
+
+    assert_eq!(file.find_all_channels("Can2Load").len(), 1);
+    assert!(file.find_all_channels("wheelspeed").is_empty());
+}
+
+#[test]
+fn test_writer_into_reader() {
+    let temp_file = NamedTempFile::new().unwrap();
+
+    let mut writer = writer::MdfWriter::new(mdflib_sys::MdfWriterType::Mdf4Basic, temp_file.path())
+        .expect("Failed to create MDF writer");
+    let mut data_group = writer.create_data_group().unwrap();
+    let mut channel_group = data_group.create_channel_group().unwrap();
+    channel_group.set_name("Verify");
+    let mut channel = channel_group.create_channel().unwrap();
+    channel.set_name("Counter");
+    channel.set_data_type(mdflib_sys::ChannelDataType::UnsignedIntegerLe as u8);
+    channel.set_data_bytes(4);
+
+    let group = data_group.get_channel_group_by_index(0).unwrap();
+    assert!(writer.init_measurement());
+    writer.start_measurement(1000);
+    for i in 0..5u32 {
+        channel.set_channel_value(i * 10, true);
+        writer.save_sample(&group, 1000 + u64::from(i) * 1000);
+    }
+
+    // Still running, so stopped and finalized before reading
+    let mut reader = writer.into_reader().expect("Failed to reopen as reader");
+    reader.read_everything_but_data().unwrap();
+    let file = reader.get_file().unwrap();
+    assert!(file.is_finalized_done());
+    let mut dg = file.get_data_group(0).unwrap();
+    let cg = dg.get_channel_group("Verify").unwrap();
+    assert_eq!(cg.get_nof_samples(), 5);
+    let counter = cg.get_channel("Counter").unwrap();
+    let observer =
+        unsafe { create_channel_observer(dg.as_ptr(), cg.as_ptr(), counter.as_ptr()) }.unwrap();
+    reader.read_data(&mut dg).unwrap();
+    assert_eq!(
+        observer.read_typed(),
+        TypedSamples::U64(vec![0, 10, 20, 30, 40])
+    );
+}
@@ -0,0 +1,12 @@
+use mdflib::*;
+
+fn main() {
+    let reader = MdfReader::new("can_data.mf4").unwrap();
+    let file = reader.get_file().unwrap();
+    let dg = file.get_data_group(0).unwrap();
+    let cg = dg.get_channel_group_by_index(0).unwrap();
+    let observer = create_can_bus_observer(&reader, &dg, &cg).unwrap();
+
+    drop(reader);
+    println!("{}", observer.get_nof_samples());
+}
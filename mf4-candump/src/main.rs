@@ -5,7 +5,7 @@
 use anyhow::{Context, Result};
 use chrono::{DateTime, Local};
 use clap::Parser;
-use mdflib::{writer, CanMessage, MdfBusType};
+use mdflib::{writer, CanMessage, FileHistoryEntry, MdfBusType};
 use signal_hook::consts::{SIGINT, SIGTERM};
 use signal_hook_tokio::Signals;
 use socketcan::{CanFilter, CanFrame, CanSocketTimestamp, EmbeddedFrame, Socket, SocketOptions};
@@ -104,16 +104,13 @@ fn setup_mdf_writer(
         header.set_description(&format!("CAN bus log from interface {interface}"));
 
         // Create file history entry
-        if let Some(mut history) = header.create_file_history() {
-            history.set_description(&format!("CAN message capture from {interface}"))?;
-            history.set_tool_name("mf4-candump")?;
-            history.set_tool_version(env!("CARGO_PKG_VERSION"))?;
-            history
-                .set_user_name(&std::env::var("USER").unwrap_or_else(|_| "unknown".to_string()))?;
-
-            let now: DateTime<Local> = Local::now();
-            history.set_time(now.timestamp_nanos_opt().unwrap_or(0) as u64);
-        }
+        writer.add_file_history(FileHistoryEntry {
+            description: Some(format!("CAN message capture from {interface}")),
+            tool_name: Some("mf4-candump".to_string()),
+            tool_version: Some(env!("CARGO_PKG_VERSION").to_string()),
+            user_name: Some(std::env::var("USER").unwrap_or_else(|_| "unknown".to_string())),
+            ..Default::default()
+        })?;
 
         if let Some(meta) = metadata {
             let mut header_meta = header.create_metadata().unwrap();